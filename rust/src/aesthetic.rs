@@ -0,0 +1,201 @@
+//! Structural "aesthetic" pattern classes.
+//!
+//! These targets care about the shape of an address rather than its exact
+//! characters: a run of identical characters, a palindrome, a long stretch
+//! of digits. Each class knows how to test an address and how likely a
+//! random address is to satisfy it.
+
+use crate::config::{ConfigError, BASE58_ALPHABET, TYPICAL_ADDRESS_LENGTH};
+use std::fmt;
+
+/// Longest structural pattern accepted (longer would take impractical time)
+pub const MAX_CLASS_LENGTH: usize = 16;
+
+/// Digits in the Base58 alphabet (1-9)
+const BASE58_DIGITS: f64 = 9.0;
+
+/// Uppercase letters in the Base58 alphabet (A-Z without I and O)
+const BASE58_UPPERCASE: f64 = 24.0;
+
+/// A structural predicate over an address
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PatternClass {
+    /// The first N characters are identical
+    RepeatPrefix(usize),
+    /// The last N characters are identical
+    RepeatSuffix(usize),
+    /// The first N characters read the same backwards
+    PalindromePrefix(usize),
+    /// The last N characters read the same backwards
+    PalindromeSuffix(usize),
+    /// At least N consecutive digits somewhere in the address
+    DigitRun(usize),
+    /// At least N consecutive uppercase letters somewhere in the address
+    UppercaseRun(usize),
+}
+
+impl PatternClass {
+    /// Validate the class length
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let (len, min) = match *self {
+            Self::RepeatPrefix(n)
+            | Self::RepeatSuffix(n)
+            | Self::PalindromePrefix(n)
+            | Self::PalindromeSuffix(n) => (n, 2),
+            Self::DigitRun(n) | Self::UppercaseRun(n) => (n, 1),
+        };
+        if len < min || len > MAX_CLASS_LENGTH {
+            return Err(ConfigError::InvalidPatternClass(format!(
+                "{self} needs a length between {min} and {MAX_CLASS_LENGTH}"
+            )));
+        }
+        Ok(())
+    }
+
+    /// The length parameter of the class
+    pub fn length(&self) -> usize {
+        match *self {
+            Self::RepeatPrefix(n)
+            | Self::RepeatSuffix(n)
+            | Self::PalindromePrefix(n)
+            | Self::PalindromeSuffix(n)
+            | Self::DigitRun(n)
+            | Self::UppercaseRun(n) => n,
+        }
+    }
+
+    /// Check whether an address satisfies this class
+    #[inline]
+    pub fn matches(&self, address: &str) -> bool {
+        let bytes = address.as_bytes();
+        match *self {
+            Self::RepeatPrefix(n) => bytes.len() >= n && all_same(&bytes[..n]),
+            Self::RepeatSuffix(n) => bytes.len() >= n && all_same(&bytes[bytes.len() - n..]),
+            Self::PalindromePrefix(n) => bytes.len() >= n && is_palindrome(&bytes[..n]),
+            Self::PalindromeSuffix(n) => {
+                bytes.len() >= n && is_palindrome(&bytes[bytes.len() - n..])
+            }
+            Self::DigitRun(n) => longest_run_where(bytes, |b| b.is_ascii_digit()) >= n,
+            Self::UppercaseRun(n) => longest_run_where(bytes, |b| b.is_ascii_uppercase()) >= n,
+        }
+    }
+
+    /// Probability that a random address satisfies this class
+    ///
+    /// Positional classes are exact for uniformly random characters; run
+    /// classes use the expected number of qualifying runs, which is a close
+    /// upper bound for the lengths that are practical to search for.
+    pub fn probability(&self) -> f64 {
+        let alphabet = BASE58_ALPHABET.len() as f64;
+        match *self {
+            // The first character is free; every other one must repeat it
+            Self::RepeatPrefix(n) | Self::RepeatSuffix(n) => {
+                (1.0 / alphabet).powi(n as i32 - 1)
+            }
+            // Each character in the second half must mirror one in the first
+            Self::PalindromePrefix(n) | Self::PalindromeSuffix(n) => {
+                (1.0 / alphabet).powi((n / 2) as i32)
+            }
+            Self::DigitRun(n) => run_probability(BASE58_DIGITS / alphabet, n),
+            Self::UppercaseRun(n) => run_probability(BASE58_UPPERCASE / alphabet, n),
+        }
+    }
+}
+
+impl fmt::Display for PatternClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::RepeatPrefix(n) => write!(f, "{n} identical leading characters"),
+            Self::RepeatSuffix(n) => write!(f, "{n} identical trailing characters"),
+            Self::PalindromePrefix(n) => write!(f, "{n}-character palindrome prefix"),
+            Self::PalindromeSuffix(n) => write!(f, "{n}-character palindrome suffix"),
+            Self::DigitRun(n) => write!(f, "run of {n}+ digits"),
+            Self::UppercaseRun(n) => write!(f, "run of {n}+ uppercase letters"),
+        }
+    }
+}
+
+fn all_same(bytes: &[u8]) -> bool {
+    bytes.windows(2).all(|w| w[0] == w[1])
+}
+
+fn is_palindrome(bytes: &[u8]) -> bool {
+    bytes.iter().eq(bytes.iter().rev())
+}
+
+fn longest_run_where(bytes: &[u8], pred: impl Fn(u8) -> bool) -> usize {
+    let mut longest = 0;
+    let mut current = 0;
+    for &b in bytes {
+        if pred(b) {
+            current += 1;
+            longest = longest.max(current);
+        } else {
+            current = 0;
+        }
+    }
+    longest
+}
+
+/// Probability of a run of at least `n` characters from a class with per-character probability `q`
+fn run_probability(q: f64, n: usize) -> f64 {
+    if n > TYPICAL_ADDRESS_LENGTH {
+        return 0.0;
+    }
+    // A run can start at position 0, or after any character outside the class
+    let starts = (TYPICAL_ADDRESS_LENGTH - n) as f64;
+    let expected_runs = q.powi(n as i32) * (1.0 + starts * (1.0 - q));
+    expected_runs.min(1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repeat_classes() {
+        assert!(PatternClass::RepeatPrefix(4).matches("7777abcdefghijkmnopqrstuvwxyz1234567"));
+        assert!(!PatternClass::RepeatPrefix(4).matches("777Aabcdefghijkmnopqrstuvwxyz1234567"));
+        assert!(PatternClass::RepeatSuffix(3).matches("abcdefghijkmnopqrstuvwxyz1234567ZZZ"));
+    }
+
+    #[test]
+    fn test_palindrome_classes() {
+        assert!(PatternClass::PalindromeSuffix(5).matches("abcdefghijkmnopqrstuvwxyz12345abXba"));
+        assert!(!PatternClass::PalindromeSuffix(5).matches("abcdefghijkmnopqrstuvwxyz12345abXbc"));
+        assert!(PatternClass::PalindromePrefix(4).matches("ABBAdefghijkmnopqrstuvwxyz123456789"));
+    }
+
+    #[test]
+    fn test_run_classes() {
+        let address = "abc123456defghijkmnopqrstuvwxyzABCDEF";
+        assert!(PatternClass::DigitRun(6).matches(address));
+        assert!(!PatternClass::DigitRun(7).matches(address));
+        assert!(PatternClass::UppercaseRun(6).matches(address));
+        assert!(!PatternClass::UppercaseRun(7).matches(address));
+    }
+
+    #[test]
+    fn test_probabilities() {
+        let repeat = PatternClass::RepeatPrefix(3).probability();
+        assert!((repeat - 1.0 / (58.0 * 58.0)).abs() < 1e-12);
+
+        // Odd-length palindromes have a free middle character
+        assert_eq!(
+            PatternClass::PalindromeSuffix(4).probability(),
+            PatternClass::PalindromeSuffix(5).probability()
+        );
+
+        let short = PatternClass::DigitRun(2).probability();
+        let long = PatternClass::DigitRun(6).probability();
+        assert!(short > long && long > 0.0 && short <= 1.0);
+    }
+
+    #[test]
+    fn test_validation() {
+        assert!(PatternClass::RepeatPrefix(5).validate().is_ok());
+        assert!(PatternClass::RepeatPrefix(1).validate().is_err());
+        assert!(PatternClass::DigitRun(0).validate().is_err());
+        assert!(PatternClass::UppercaseRun(MAX_CLASS_LENGTH + 1).validate().is_err());
+    }
+}
@@ -0,0 +1,534 @@
+//! Batch job files: many vanity searches in one run.
+//!
+//! A job file lists named jobs, each with its own pattern, count, case rules,
+//! output directory and keypair format. It is TOML unless its extension is
+//! `.json`:
+//!
+//! ```toml
+//! [[jobs]]
+//! name = "treasury"
+//! prefix = "Tres"
+//! count = 3
+//! output-dir = "keys/treasury"
+//!
+//! [[jobs]]
+//! name = "ops"
+//! suffix = "ops"
+//! case = "insensitive"
+//! format = "base58"
+//! output-template = "{job}-{index}-{pubkey}.b58"
+//! ```
+//!
+//! [`BatchSearch`] checks every generated key against all jobs that still
+//! need keys, so the whole file is searched in one pool. Keys written are
+//! recorded in a [`BatchState`] file, and a later run of the same file only
+//! searches for what is still missing.
+
+use crate::config::ConfigError;
+use crate::generator::{slot_search, GeneratorError, GeneratorResult, ParallelSearch, SearchProgress, VanityGeneratorConfig};
+use crate::matcher::{CaseRule, MatchTarget, OptimizedMatcher};
+use crate::output::{GeneratedAddress, KeypairEncoding, OutputFields, OutputResult, OutputTemplate};
+use crate::security::{secure_write_file, verify_rng_quality};
+use crate::series::expected_attempts_to_fill_counts;
+use serde::{Deserialize, Serialize};
+use solana_sdk::signer::keypair::Keypair;
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// One job as written in the file
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+struct JobSpec {
+    name: String,
+    prefix: Option<String>,
+    suffix: Option<String>,
+    #[serde(default = "default_count")]
+    count: u64,
+    case: Option<String>,
+    prefix_case: Option<String>,
+    suffix_case: Option<String>,
+    output_dir: Option<PathBuf>,
+    output_template: Option<String>,
+    format: Option<String>,
+}
+
+fn default_count() -> u64 {
+    1
+}
+
+/// Most keys one job may ask for; each key is a file
+pub const MAX_JOB_COUNT: u64 = 1_000_000;
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct JobFileSpec {
+    jobs: Vec<JobSpec>,
+}
+
+/// A validated job
+#[derive(Debug, Clone)]
+pub struct Job {
+    /// Unique name, used in the state file and reports
+    pub name: String,
+    /// The target keys must match, with the job's case rules applied
+    pub target: MatchTarget,
+    /// Prefix as written in the job file
+    pub prefix: Option<String>,
+    /// Suffix as written in the job file
+    pub suffix: Option<String>,
+    /// Number of keys to find
+    pub count: u64,
+    /// Directory the keypair files go in
+    pub output_dir: PathBuf,
+    /// Keypair file name, by default `{pubkey}` plus the format's extension
+    pub template: OutputTemplate,
+    /// Keypair file format, from `format`
+    pub encoding: KeypairEncoding,
+    /// Directory of the job file; recorded key files are relative to it
+    pub base_dir: PathBuf,
+}
+
+impl Job {
+    fn from_spec(spec: JobSpec, base_dir: &Path) -> Result<Self, ConfigError> {
+        if spec.count == 0 {
+            return Err(ConfigError::InvalidCount(0));
+        }
+        if spec.count > MAX_JOB_COUNT {
+            return Err(ConfigError::CountTooLarge {
+                count: spec.count,
+                max: MAX_JOB_COUNT,
+            });
+        }
+        let case = |rule: &Option<String>| -> Result<CaseRule, ConfigError> {
+            match rule.as_ref().or(spec.case.as_ref()) {
+                Some(rule) => rule.parse(),
+                None => Ok(CaseRule::Sensitive),
+            }
+        };
+        let prefix_case = case(&spec.prefix_case)?;
+        let suffix_case = case(&spec.suffix_case)?;
+        let target = MatchTarget::segments(
+            spec.prefix.as_deref().map(|p| (p, prefix_case)),
+            spec.suffix.as_deref().map(|s| (s, suffix_case)),
+        )?;
+        let encoding = match spec.format {
+            Some(ref format) => format.parse()?,
+            None => KeypairEncoding::default(),
+        };
+        let template = match spec.output_template {
+            Some(ref template) => OutputTemplate::new(template)?,
+            None => OutputTemplate::new(&format!("{{pubkey}}.{}", encoding.extension()))?,
+        };
+        // Relative directories are relative to the job file, not the caller
+        let output_dir = match spec.output_dir {
+            Some(dir) => base_dir.join(dir),
+            None => base_dir.to_path_buf(),
+        };
+
+        Ok(Self {
+            name: spec.name,
+            target,
+            prefix: spec.prefix,
+            suffix: spec.suffix,
+            count: spec.count,
+            output_dir,
+            template,
+            encoding,
+            base_dir: base_dir.to_path_buf(),
+        })
+    }
+
+    /// Template values for the job's `index`th key, on `date`
+    pub fn output_fields(&self, index: usize, date: &str) -> OutputFields {
+        OutputFields {
+            prefix: self.prefix.clone().unwrap_or_default(),
+            suffix: self.suffix.clone().unwrap_or_default(),
+            index,
+            date: date.to_string(),
+            job: Some(self.name.clone()),
+        }
+    }
+
+    /// Path of the keypair file for `pubkey`
+    pub fn output_path(&self, pubkey: &str, fields: &OutputFields) -> PathBuf {
+        self.output_dir.join(self.template.render(pubkey, fields))
+    }
+
+    /// State record for `pubkey` written to `path`
+    ///
+    /// The file is stored relative to the job file's directory, so the state
+    /// stays valid when the batch is run from another directory.
+    pub fn key(&self, pubkey: String, path: &Path) -> JobKey {
+        let file = path.strip_prefix(&self.base_dir).unwrap_or(path);
+        JobKey {
+            pubkey,
+            file: file.display().to_string(),
+        }
+    }
+
+    /// Where a recorded key's file is, from the current directory
+    pub fn key_path(&self, key: &JobKey) -> PathBuf {
+        self.base_dir.join(&key.file)
+    }
+}
+
+/// A parsed job file
+#[derive(Debug, Clone)]
+pub struct JobFile {
+    path: PathBuf,
+    jobs: Vec<Job>,
+}
+
+impl JobFile {
+    /// Parse the text of the job file at `path`
+    pub fn parse(text: &str, path: &Path) -> Result<Self, ConfigError> {
+        let invalid = |message: String| {
+            ConfigError::InvalidJobFile(format!("{}: {message}", path.display()))
+        };
+        let spec: JobFileSpec = if path.extension().is_some_and(|ext| ext == "json") {
+            serde_json::from_str(text).map_err(|e| invalid(e.to_string()))?
+        } else {
+            toml::from_str(text).map_err(|e: toml::de::Error| invalid(e.message().to_string()))?
+        };
+        if spec.jobs.is_empty() {
+            return Err(invalid("no jobs".to_string()));
+        }
+
+        let base_dir = path.parent().unwrap_or(Path::new(""));
+        let mut names = HashSet::new();
+        let mut jobs = Vec::with_capacity(spec.jobs.len());
+        for job in spec.jobs {
+            if job.name.trim().is_empty() {
+                return Err(invalid("every job needs a name".to_string()));
+            }
+            if !names.insert(job.name.clone()) {
+                return Err(invalid(format!("job name '{}' is used twice", job.name)));
+            }
+            let name = job.name.clone();
+            let job = Job::from_spec(job, base_dir).map_err(|source| ConfigError::InvalidJob {
+                job: name,
+                source: Box::new(source),
+            })?;
+            jobs.push(job);
+        }
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            jobs,
+        })
+    }
+
+    /// Path the file was read from
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The jobs, in file order
+    pub fn jobs(&self) -> &[Job] {
+        &self.jobs
+    }
+
+    /// Default state file: `jobs.toml` records its progress in `jobs.state.json`
+    pub fn state_path(&self) -> PathBuf {
+        self.path.with_extension("state.json")
+    }
+}
+
+/// A key written for a job
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JobKey {
+    /// Base58 public key
+    pub pubkey: String,
+    /// Keypair file the key was written to, relative to the job file's directory
+    pub file: String,
+}
+
+/// Keys written for each job of a job file, across runs
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BatchState {
+    /// Keys by job name
+    pub jobs: BTreeMap<String, Vec<JobKey>>,
+}
+
+impl BatchState {
+    /// Read a state file; a missing file is an empty state
+    pub fn load(path: &Path) -> OutputResult<Self> {
+        match std::fs::read(path) {
+            Ok(data) => Ok(serde_json::from_slice(&data)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Write the state as pretty-printed JSON
+    pub fn save(&self, path: &Path) -> OutputResult<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        secure_write_file(path, json.as_bytes())?;
+        Ok(())
+    }
+
+    /// Keys recorded for `job` that still count toward it
+    ///
+    /// A key counts while its file exists and it matches the job's current
+    /// pattern, so editing a job or deleting its files makes it run again.
+    pub fn completed(&self, job: &Job) -> Vec<&JobKey> {
+        self.jobs
+            .get(&job.name)
+            .map(|keys| {
+                keys.iter()
+                    .filter(|key| job.key_path(key).exists() && job.target.matches(&key.pubkey))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Number of keys ever recorded for the job named `job`
+    ///
+    /// New keys are numbered after these, so `{index}` is never reused.
+    pub fn recorded(&self, job: &str) -> usize {
+        self.jobs.get(job).map_or(0, Vec::len)
+    }
+
+    /// Record a key written for the job named `job`
+    pub fn record(&mut self, job: &str, key: JobKey) {
+        self.jobs.entry(job.to_string()).or_default().push(key);
+    }
+}
+
+/// A key found for one job of a [`BatchSearch`]
+#[derive(Debug)]
+pub struct BatchMatch {
+    /// Index of the job, in the order given to [`BatchSearch::new`]
+    pub job: usize,
+    /// The keypair and search stats
+    pub address: GeneratedAddress,
+}
+
+/// Parallel search that finds keys for many targets at once
+pub struct BatchSearch {
+    config: VanityGeneratorConfig,
+    matchers: Vec<OptimizedMatcher>,
+    counts: Vec<u64>,
+    progress: SearchProgress,
+}
+
+impl BatchSearch {
+    /// Create a search for `count` keys of each target
+    pub fn new(targets: Vec<(MatchTarget, u64)>, config: VanityGeneratorConfig) -> GeneratorResult<Self> {
+        if targets.is_empty() {
+            return Err(GeneratorError::ConfigError(
+                ConfigError::NoPatternSpecified.to_string(),
+            ));
+        }
+        targets
+            .iter()
+            .try_fold(0u64, |total, (_, count)| total.checked_add(*count))
+            .ok_or_else(|| GeneratorError::ConfigError("the counts add up to more than 2^64 keys".to_string()))?;
+        verify_rng_quality().map_err(|e| GeneratorError::RngError(e.to_string()))?;
+
+        let (matchers, counts) = targets
+            .into_iter()
+            .map(|(target, count)| (OptimizedMatcher::new(target), count))
+            .unzip();
+        Ok(Self {
+            config,
+            matchers,
+            counts,
+            progress: SearchProgress::default(),
+        })
+    }
+
+    /// Expected attempts to find every key
+    pub fn expected_attempts(&self) -> f64 {
+        let groups = self
+            .matchers
+            .iter()
+            .zip(&self.counts)
+            .map(|(matcher, &count)| (matcher.target().probability(), count))
+            .collect();
+        expected_attempts_to_fill_counts(groups)
+    }
+
+    /// Search until every target has its count or the search is cancelled
+    ///
+    /// Each key counts toward the first target it matches and is passed to
+    /// `on_match` as soon as it is found, from the thread that found it.
+    pub fn run<F>(&self, on_match: F) -> GeneratorResult<()>
+    where
+        F: Fn(BatchMatch) + Sync,
+    {
+        slot_search(
+            &self.config,
+            &self.progress,
+            &self.matchers,
+            &self.counts,
+            || Some((Keypair::new(), ())),
+            |_| true,
+            |job, mut address, ()| {
+                for (label, value) in self.matchers[job].match_details(&address.public_key()) {
+                    address.add_detail(label, value);
+                }
+                on_match(BatchMatch { job, address });
+            },
+        )
+    }
+}
+
+impl ParallelSearch for BatchSearch {
+    fn progress(&self) -> &SearchProgress {
+        &self.progress
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::config;
+    use std::sync::atomic::Ordering;
+    use std::sync::Mutex;
+    use tempfile::tempdir;
+
+    const JOBS: &str = r#"
+[[jobs]]
+name = "treasury"
+prefix = "A"
+count = 2
+output-dir = "keys"
+
+[[jobs]]
+name = "ops"
+suffix = "b"
+case = "insensitive"
+format = "base58"
+"#;
+
+    #[test]
+    fn test_parse_toml_and_json() {
+        let file = JobFile::parse(JOBS, Path::new("batch/jobs.toml")).unwrap();
+        let jobs = file.jobs();
+        assert_eq!(jobs.len(), 2);
+        assert_eq!((jobs[0].name.as_str(), jobs[0].count), ("treasury", 2));
+        assert_eq!(jobs[0].output_dir, Path::new("batch/keys"));
+        assert_eq!(jobs[0].encoding, KeypairEncoding::SolanaJson);
+        assert_eq!((jobs[1].count, jobs[1].encoding), (1, KeypairEncoding::Base58));
+        assert_eq!(jobs[1].output_dir, Path::new("batch"));
+        assert_eq!(jobs[1].template.as_str(), "{pubkey}.b58");
+        let fields = jobs[0].output_fields(2, "2026-10-18");
+        assert_eq!((fields.prefix.as_str(), fields.job.as_deref()), ("A", Some("treasury")));
+        assert_eq!(jobs[0].output_path("Axyz", &fields), Path::new("batch/keys/Axyz.json"));
+        assert!(jobs[1].target.matches("xxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxB"));
+        assert!(!jobs[0].target.matches("axxxxxxxxxxxxxxxxxxxxxxxxxxxxxxx"));
+        assert_eq!(file.state_path(), Path::new("batch/jobs.state.json"));
+
+        let json = r#"{"jobs": [{"name": "mixed", "prefix": "ab", "prefix-case": "is"}]}"#;
+        let file = JobFile::parse(json, Path::new("jobs.json")).unwrap();
+        assert!(file.jobs()[0].target.matches("Abxxxxxxxxxxxxxxxxxxxxxxxxxxxxxx"));
+        assert!(!file.jobs()[0].target.matches("aBxxxxxxxxxxxxxxxxxxxxxxxxxxxxxx"));
+    }
+
+    #[test]
+    fn test_rejects_bad_jobs() {
+        let parse = |text: &str| JobFile::parse(text, Path::new("jobs.toml"));
+        assert!(matches!(parse("jobs = []"), Err(ConfigError::InvalidJobFile(_))));
+        assert!(matches!(parse("[[jobs]]\nprefix = \"A\""), Err(ConfigError::InvalidJobFile(_))));
+        assert!(parse("[[jobs]]\nname = \"a\"\nprefix = \"A\"\ncolor = 1").is_err());
+        assert!(parse("[[jobs]]\nname = \"a\"\nprefix = \"A\"\n[[jobs]]\nname = \"a\"\nprefix = \"B\"").is_err());
+
+        let err = parse("[[jobs]]\nname = \"bad\"\nprefix = \"A0\"").unwrap_err();
+        assert!(matches!(err, ConfigError::InvalidJob { ref job, .. } if job == "bad"));
+        assert!(parse("[[jobs]]\nname = \"none\"").is_err());
+        assert!(parse("[[jobs]]\nname = \"zero\"\nprefix = \"A\"\ncount = 0").is_err());
+        let err = parse("[[jobs]]\nname = \"huge\"\nprefix = \"A\"\ncount = 1000001").unwrap_err();
+        assert!(matches!(
+            err,
+            ConfigError::InvalidJob { ref source, .. } if matches!(**source, ConfigError::CountTooLarge { .. })
+        ));
+        assert!(parse("[[jobs]]\nname = \"fmt\"\nprefix = \"A\"\nformat = \"pem\"").is_err());
+        assert!(parse("[[jobs]]\nname = \"mask\"\nprefix = \"AB\"\ncase = \"sis\"").is_err());
+        assert!(parse("[[jobs]]\nname = \"t\"\nprefix = \"A\"\noutput-template = \"{key}\"").is_err());
+    }
+
+    #[test]
+    fn test_search_fills_every_job() {
+        let targets = vec![
+            (MatchTarget::prefix("A", false).unwrap(), 2),
+            (MatchTarget::suffix("b", false).unwrap(), 1),
+        ];
+        let search = BatchSearch::new(targets, config()).unwrap();
+        let found = Mutex::new(Vec::new());
+        search.run(|m| found.lock().unwrap().push(m)).unwrap();
+        let found = found.into_inner().unwrap();
+
+        assert_eq!(found.len(), 3);
+        assert_eq!(found.iter().filter(|m| m.job == 0).count(), 2);
+        for m in &found {
+            let pubkey = m.address.public_key();
+            assert!(if m.job == 0 { pubkey.starts_with('A') } else { pubkey.ends_with('b') });
+        }
+        assert!(search.attempts() >= 3);
+    }
+
+    #[test]
+    fn test_cancelled_search_stops() {
+        let targets = vec![(MatchTarget::prefix("ZZZZZ", false).unwrap(), 1)];
+        let search = BatchSearch::new(targets, config()).unwrap();
+        search.cancel_handle().store(true, Ordering::SeqCst);
+        search.run(|_| panic!("nothing should be found")).unwrap();
+        assert!(BatchSearch::new(Vec::new(), config()).is_err());
+    }
+
+    #[test]
+    fn test_huge_counts() {
+        let target = MatchTarget::prefix("A", false).unwrap();
+        let search = BatchSearch::new(vec![(target.clone(), u64::MAX)], config()).unwrap();
+        assert!(search.expected_attempts().is_finite());
+
+        let targets = vec![(target.clone(), u64::MAX), (target, 1)];
+        assert!(matches!(BatchSearch::new(targets, config()), Err(GeneratorError::ConfigError(_))));
+    }
+
+    #[test]
+    fn test_state_tracks_completed_keys() {
+        let dir = tempdir().unwrap();
+        let file = JobFile::parse(JOBS, &dir.path().join("jobs.toml")).unwrap();
+        let job = &file.jobs()[0];
+        let state_path = file.state_path();
+        assert_eq!(BatchState::load(&state_path).unwrap(), BatchState::default());
+
+        let written = dir.path().join("A1.json");
+        std::fs::write(&written, "[]").unwrap();
+        let mut state = BatchState::default();
+        let key = |pubkey: &str, file: &Path| JobKey {
+            pubkey: pubkey.to_string(),
+            file: file.display().to_string(),
+        };
+        state.record("treasury", key("A1", &written));
+        // File gone, or no longer matching the job's pattern
+        state.record("treasury", key("A2", &dir.path().join("missing.json")));
+        state.record("treasury", key("B1", &written));
+        state.save(&state_path).unwrap();
+        assert_eq!(state.recorded("treasury"), 3);
+        assert_eq!(state.recorded("ops"), 0);
+
+        let state = BatchState::load(&state_path).unwrap();
+        let completed = state.completed(job);
+        assert_eq!(completed.len(), 1);
+        assert_eq!(completed[0].pubkey, "A1");
+        assert!(state.completed(&file.jobs()[1]).is_empty());
+    }
+
+    #[test]
+    fn test_key_files_are_relative_to_the_job_file() {
+        let file = JobFile::parse(JOBS, Path::new("batch/jobs.toml")).unwrap();
+        let job = &file.jobs()[0];
+        let path = job.output_path("Axyz", &job.output_fields(1, "2026-10-18"));
+        let key = job.key("Axyz".to_string(), &path);
+        assert_eq!(Path::new(&key.file), Path::new("keys/Axyz.json"));
+        assert_eq!(job.key_path(&key), path);
+
+        // Files outside the job file's directory keep their full path
+        let elsewhere = std::env::temp_dir().join("Axyz.json");
+        let key = job.key("Axyz".to_string(), &elsewhere);
+        assert_eq!(job.key_path(&key), elsewhere);
+    }
+}
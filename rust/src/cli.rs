@@ -0,0 +1,223 @@
+//! Subcommands of the command-line tool and the pieces they share.
+
+pub mod batch;
+pub mod bench;
+pub mod config;
+pub mod convert;
+pub mod estimate;
+pub mod export;
+pub mod generate;
+pub mod grind;
+pub mod inspect;
+pub mod mnemonic;
+pub mod pattern;
+pub mod pda;
+pub mod pump;
+pub mod report;
+pub mod seeded;
+pub mod series;
+pub mod verify;
+pub mod wordlist;
+
+#[cfg(test)]
+pub mod test_support;
+
+use solana_vanity::exit::ExitCode;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use thiserror::Error;
+
+/// CLI Error type
+#[derive(Error, Debug)]
+pub enum CliError {
+    #[error("Invalid argument: {0}")]
+    InvalidArgument(String),
+    #[error("Generation error: {0}")]
+    GenerationError(#[from] solana_vanity::GeneratorError),
+    #[error("Output error: {0}")]
+    OutputError(#[from] solana_vanity::output::OutputError),
+    #[error("Key format error: {0}")]
+    Export(#[from] solana_vanity::ExportError),
+    #[error("Config error: {0}")]
+    Config(#[from] solana_vanity::ConfigError),
+    #[error("Config error: Invalid {what}: {source}")]
+    InvalidPattern {
+        what: &'static str,
+        source: solana_vanity::ConfigError,
+    },
+    #[error("Config error: Invalid wordlist: {0}")]
+    Wordlist(#[from] solana_vanity::WordlistError),
+    #[error("Config error: Create transaction failed: {0}")]
+    CreateTransaction(#[from] solana_vanity::PumpError),
+    #[error("Cannot read {what} '{}': {source}", path.display())]
+    Read {
+        what: &'static str,
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("Output file '{}' already exists. Use --overwrite to replace.", .0.display())]
+    OutputExists(PathBuf),
+    #[error("Verification failed: {0}")]
+    VerificationFailed(String),
+}
+
+impl CliError {
+    /// Process exit code for this error's category
+    pub fn exit_code(&self) -> ExitCode {
+        match self {
+            CliError::InvalidArgument(_) => ExitCode::Usage,
+            CliError::GenerationError(e) => e.into(),
+            CliError::OutputError(e) => e.into(),
+            CliError::Export(e) => e.into(),
+            CliError::Config(e) | CliError::InvalidPattern { source: e, .. } => e.into(),
+            CliError::Wordlist(e) => e.into(),
+            CliError::CreateTransaction(e) => e.into(),
+            CliError::Read { source, .. } => source.into(),
+            CliError::OutputExists(_) => ExitCode::OutputConflict,
+            CliError::VerificationFailed(_) => ExitCode::VerificationFailed,
+        }
+    }
+}
+
+/// Map a pattern error, naming the part of the pattern it came from
+pub fn invalid(what: &'static str) -> impl FnOnce(solana_vanity::ConfigError) -> CliError {
+    move |source| CliError::InvalidPattern { what, source }
+}
+
+pub type Result<T> = std::result::Result<T, CliError>;
+
+/// Outcome of a search that keeps partial results: cancelled if the user
+/// interrupted it, exhausted if it kept nothing
+pub fn search_outcome(interrupted: &AtomicBool, nothing_found: bool) -> Result<()> {
+    if interrupted.load(Ordering::SeqCst) {
+        return Err(solana_vanity::GeneratorError::Cancelled.into());
+    }
+    if nothing_found {
+        return Err(solana_vanity::GeneratorError::Exhausted.into());
+    }
+    Ok(())
+}
+
+/// Set `cancel` on Ctrl+C; the returned flag records that the user interrupted
+pub fn cancel_on_interrupt(cancel: Arc<AtomicBool>, action: &'static str) -> Arc<AtomicBool> {
+    let interrupted = Arc::new(AtomicBool::new(false));
+    let flag = Arc::clone(&interrupted);
+    ctrlc::set_handler(move || {
+        flag.store(true, Ordering::SeqCst);
+        cancel.store(true, Ordering::SeqCst);
+        eprintln!("\nReceived Ctrl+C, {}...", action);
+    }).expect("Error setting Ctrl+C handler");
+    interrupted
+}
+
+pub fn format_number(n: u64) -> String {
+    let s = n.to_string();
+    let mut result = String::new();
+    for (i, c) in s.chars().rev().enumerate() {
+        if i > 0 && i % 3 == 0 {
+            result.insert(0, ',');
+        }
+        result.insert(0, c);
+    }
+    result
+}
+
+pub fn format_duration(seconds: f64) -> String {
+    if seconds < 1.0 {
+        format!("{:.0}ms", seconds * 1000.0)
+    } else if seconds < 60.0 {
+        format!("{:.1}s", seconds)
+    } else if seconds < 3600.0 {
+        let minutes = seconds / 60.0;
+        format!("{:.1} minutes", minutes)
+    } else if seconds < 86400.0 {
+        let hours = seconds / 3600.0;
+        format!("{:.1} hours", hours)
+    } else if seconds < 31536000.0 {
+        let days = seconds / 86400.0;
+        format!("{:.1} days", days)
+    } else {
+        let years = seconds / 31536000.0;
+        format!("{:.1} years", years)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::generate::save_address_to;
+    use crate::cli::pattern::{create_exclusions, pattern_target, validate_pattern};
+    use crate::cli::report::Reporter;
+    use crate::cli::test_support::parse;
+    use crate::cli::verify::verify;
+    use crate::{Cli, Command};
+    use clap::Parser;
+    use solana_vanity::output::{write_keypair_as, KeypairEncoding};
+
+    #[test]
+    fn test_format_number() {
+        assert_eq!(format_number(123), "123");
+        assert_eq!(format_number(1234), "1,234");
+        assert_eq!(format_number(1234567), "1,234,567");
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert!(format_duration(0.5).contains("ms"));
+        assert!(format_duration(30.0).contains("s"));
+        assert!(format_duration(120.0).contains("minutes"));
+        assert!(format_duration(7200.0).contains("hours"));
+        assert!(format_duration(172800.0).contains("days"));
+    }
+
+    #[test]
+    fn test_exit_codes() {
+        let code = |args: &[&str]| {
+            let cli = parse(args.iter().copied());
+            validate_pattern(&cli.pattern)
+                .and_then(|_| pattern_target(&cli.pattern))
+                .and_then(|_| create_exclusions(&cli.pattern))
+                .unwrap_err()
+                .exit_code()
+        };
+        assert_eq!(code(&["solana-vanity", "--prefix", "A0"]), ExitCode::InvalidPattern);
+        assert_eq!(code(&["solana-vanity", "--prefix", "ABCDEFGHJ"]), ExitCode::ImpossiblePattern);
+        assert_eq!(
+            code(&["solana-vanity", "--key-min", &"80".repeat(32), "--key-below", &"40".repeat(32)]),
+            ExitCode::ImpossiblePattern
+        );
+        assert_eq!(
+            code(&["solana-vanity", "--prefix", "A", "--profanity-list", "/nonexistent/words.txt"]),
+            ExitCode::Io
+        );
+        assert_eq!(code(&["solana-vanity", "--prefix", "A", "--max-run", "0"]), ExitCode::Usage);
+
+        let cancelled: CliError = solana_vanity::GeneratorError::Cancelled.into();
+        assert_eq!(cancelled.exit_code().code(), 130);
+        let exhausted: CliError = solana_vanity::GeneratorError::Exhausted.into();
+        assert_eq!(exhausted.exit_code(), ExitCode::BudgetExhausted);
+        assert_eq!(search_outcome(&AtomicBool::new(true), false).unwrap_err().exit_code(), ExitCode::Cancelled);
+        assert_eq!(search_outcome(&AtomicBool::new(false), true).unwrap_err().exit_code(), ExitCode::BudgetExhausted);
+        assert!(search_outcome(&AtomicBool::new(false), false).is_ok());
+
+        // Refusing to overwrite an existing file is an output conflict
+        let dir = tempfile::tempdir().unwrap();
+        let existing = dir.path().join("key.json");
+        let keypair = solana_sdk::signature::Keypair::new();
+        write_keypair_as(&keypair, KeypairEncoding::SolanaJson, &existing).unwrap();
+        let cli = parse(["solana-vanity", "--prefix", "A"]);
+        let reporter = Reporter::new(&cli);
+        let address = solana_vanity::GeneratedAddress::new(keypair, 1, 1);
+        let err = save_address_to(&cli, &reporter, &address, "prefix 'A'", existing.clone()).unwrap_err();
+        assert_eq!(err.exit_code(), ExitCode::OutputConflict);
+        let Some(Command::Verify(args)) =
+            Cli::try_parse_from(["solana-vanity", "verify", existing.to_str().unwrap(), "--pubkey", "11111111111111111111111111111111"])
+                .unwrap()
+                .command
+        else {
+            panic!("expected verify")
+        };
+        assert_eq!(verify(&args).unwrap_err().exit_code(), ExitCode::VerificationFailed);
+    }
+}
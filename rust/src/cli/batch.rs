@@ -0,0 +1,322 @@
+//! The `batch` command: every job of a job file in one search.
+
+use crate::cli::{cancel_on_interrupt, format_number, search_outcome, CliError, Result};
+use crate::cli::generate::run_date;
+use crate::cli::report::Reporter;
+use clap::Args;
+use solana_vanity::batch::{BatchMatch, BatchSearch, BatchState, JobFile};
+use solana_vanity::generator::{ParallelSearch, VanityGeneratorConfig};
+use solana_vanity::output::{verify_keypair_file, write_keypair_file};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Options for running a job file
+#[derive(Args, Debug)]
+pub struct BatchArgs {
+    /// Job file: TOML, or JSON with a .json extension
+    #[arg(value_name = "JOBS")]
+    jobs: PathBuf,
+
+    /// Number of threads (default: all CPUs)
+    #[arg(short, long, value_name = "NUM")]
+    threads: Option<usize>,
+
+    /// Verify each keypair file after writing it
+    #[arg(long)]
+    verify: bool,
+
+    /// File recording the keys written so far (default: JOBS with a .state.json extension)
+    #[arg(long, value_name = "FILE")]
+    state: Option<PathBuf>,
+}
+
+/// Progress of one job in a batch run
+struct JobProgress {
+    /// Keys that existed before this run
+    before: u64,
+    /// Keys found in this run
+    found: u64,
+    /// Keys ever recorded before this run, which `{index}` counts on from
+    recorded: usize,
+}
+
+/// Keys found during a batch run, saved as they arrive
+struct BatchProgress {
+    state: BatchState,
+    jobs: Vec<JobProgress>,
+    error: Option<CliError>,
+}
+
+pub fn batch(args: &BatchArgs) -> Result<()> {
+    run_batch(args, |cancel| cancel_on_interrupt(cancel, "stopping batch"))
+}
+
+/// Run a job file; `on_interrupt` is given the search's cancel flag and
+/// returns a flag recording whether the user interrupted
+fn run_batch<I>(args: &BatchArgs, on_interrupt: I) -> Result<()>
+where
+    I: FnOnce(Arc<AtomicBool>) -> Arc<AtomicBool>,
+{
+    let text = std::fs::read_to_string(&args.jobs).map_err(|source| CliError::Read {
+        what: "job file",
+        path: args.jobs.clone(),
+        source,
+    })?;
+    let file = JobFile::parse(&text, &args.jobs)?;
+    let state_path = args.state.clone().unwrap_or_else(|| file.state_path());
+    let state = BatchState::load(&state_path)?;
+
+    let jobs: Vec<JobProgress> = file
+        .jobs()
+        .iter()
+        .map(|job| JobProgress {
+            before: (state.completed(job).len() as u64).min(job.count),
+            found: 0,
+            recorded: state.recorded(&job.name),
+        })
+        .collect();
+    // Only jobs that still need keys join the search
+    let pending: Vec<usize> = (0..jobs.len())
+        .filter(|&i| jobs[i].before < file.jobs()[i].count)
+        .collect();
+    check_batch_paths(&file, &jobs, &pending)?;
+
+    let threads = args.threads.unwrap_or_else(num_cpus::get);
+    let targets: Vec<_> = pending
+        .iter()
+        .map(|&i| {
+            let job = &file.jobs()[i];
+            (job.target.clone(), job.count - jobs[i].before)
+        })
+        .collect();
+    if !targets.is_empty() {
+        println!("Searching with {} threads for:", threads);
+        for (&i, (_, count)) in pending.iter().zip(&targets) {
+            let job = &file.jobs()[i];
+            println!("\t{}: {} x {}", job.name, count, job.target.description());
+        }
+    }
+
+    let progress = std::sync::Mutex::new(BatchProgress { state, jobs, error: None });
+    let mut interrupted = Arc::new(AtomicBool::new(false));
+    let mut attempts = 0;
+
+    if !targets.is_empty() {
+        let config = VanityGeneratorConfig {
+            threads,
+            verify_keypairs: true,
+            progress_interval: 50_000,
+        };
+        let search = BatchSearch::new(targets, config)?;
+
+        let cancel = search.cancel_handle();
+        interrupted = on_interrupt(search.cancel_handle());
+        let on_match = |m: BatchMatch| {
+            let i = pending[m.job];
+            let job = &file.jobs()[i];
+            let mut progress = progress.lock().unwrap();
+            if progress.error.is_some() {
+                return;
+            }
+            let index = progress.jobs[i].recorded + progress.jobs[i].found as usize + 1;
+            match save_batch_key(args, job, &m.address, index) {
+                Ok(path) => {
+                    println!("[{}] {} -> {}", job.name, m.address.public_key(), path.display());
+                    progress.state.record(&job.name, job.key(m.address.public_key(), &path));
+                    progress.jobs[i].found += 1;
+                    if let Err(e) = progress.state.save(&state_path) {
+                        progress.error = Some(e.into());
+                    }
+                }
+                Err(e) => progress.error = Some(e),
+            }
+            if progress.error.is_some() {
+                cancel.store(true, Ordering::SeqCst);
+            }
+        };
+        Reporter::plain().track(false, || search.attempts(), || search.run(on_match))?;
+        attempts = search.attempts();
+    }
+
+    let progress = progress.into_inner().unwrap();
+    println!();
+    print_batch_summary(&file, &progress.jobs);
+    if attempts > 0 {
+        println!("\nTotal attempts: {}", format_number(attempts));
+    }
+
+    match progress.error {
+        Some(e) => Err(e),
+        None => search_outcome(&interrupted, false),
+    }
+}
+
+/// Check the file names of every pending job before the search starts
+///
+/// Names that don't depend on the key must be distinct across all jobs and
+/// must not exist yet.
+fn check_batch_paths(file: &JobFile, jobs: &[JobProgress], pending: &[usize]) -> Result<()> {
+    let mut planned = std::collections::HashSet::new();
+    for &i in pending {
+        let job = &file.jobs()[i];
+        let needed = (job.count - jobs[i].before) as usize;
+        let fields: Vec<_> = (1..=needed)
+            .map(|n| job.output_fields(jobs[i].recorded + n, run_date()))
+            .collect();
+        let Some(paths) = job.template.planned_paths(&job.output_dir, &fields)? else {
+            continue;
+        };
+        for path in paths {
+            if path.exists() {
+                return Err(CliError::OutputExists(path));
+            }
+            if !planned.insert(path.clone()) {
+                return Err(solana_vanity::ConfigError::OutputCollision(path.display().to_string()).into());
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Write the `index`th key of `job`, returning the keypair file
+fn save_batch_key(
+    args: &BatchArgs,
+    job: &solana_vanity::batch::Job,
+    address: &solana_vanity::GeneratedAddress,
+    index: usize,
+) -> Result<PathBuf> {
+    let path = job.output_path(&address.public_key(), &job.output_fields(index, run_date()));
+    if path.exists() {
+        return Err(CliError::OutputExists(path));
+    }
+    write_keypair_file(address, job.encoding, &path)?;
+
+    if args.verify {
+        let report = verify_keypair_file(&path)?;
+        if !(report.signature_valid && report.keypair_format_valid && report.file_permissions_secure) {
+            return Err(CliError::VerificationFailed(format!(
+                "{} failed verification",
+                path.display()
+            )));
+        }
+    }
+    Ok(path)
+}
+
+/// Print one row per job: its pattern, keys found and status
+fn print_batch_summary(file: &JobFile, jobs: &[JobProgress]) {
+    let rows: Vec<[String; 4]> = file
+        .jobs()
+        .iter()
+        .zip(jobs)
+        .map(|(job, progress)| {
+            let have = progress.before + progress.found;
+            let status = if progress.before >= job.count {
+                "skipped (already complete)"
+            } else if have >= job.count {
+                "complete"
+            } else {
+                "incomplete"
+            };
+            [
+                job.name.clone(),
+                job.target.description(),
+                format!("{}/{}", have, job.count),
+                status.to_string(),
+            ]
+        })
+        .collect();
+
+    let header = ["JOB", "PATTERN", "FOUND", "STATUS"].map(str::to_string);
+    let widths: Vec<usize> = (0..3)
+        .map(|col| rows.iter().chain([&header]).map(|row| row[col].len()).max().unwrap_or(0))
+        .collect();
+    for row in [&header].into_iter().chain(&rows) {
+        println!(
+            "{:<w0$}  {:<w1$}  {:>w2$}  {}",
+            row[0],
+            row[1],
+            row[2],
+            row[3],
+            w0 = widths[0],
+            w1 = widths[1],
+            w2 = widths[2]
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Cli, Command};
+    use clap::Parser;
+    use solana_sdk::signer::Signer;
+    use solana_vanity::exit::ExitCode;
+    use solana_vanity::output::{read_keypair_file, KeypairEncoding};
+    use std::path::Path;
+
+    #[test]
+    fn test_batch_subcommand() {
+        let dir = tempfile::tempdir().unwrap();
+        let jobs = dir.path().join("jobs.toml");
+        std::fs::write(
+            &jobs,
+            "[[jobs]]\nname = \"a\"\nprefix = \"A\"\ncount = 2\noutput-dir = \"keys\"\n\n\
+             [[jobs]]\nname = \"b\"\nsuffix = \"b\"\ncase = \"insensitive\"\nformat = \"base58\"\n",
+        )
+        .unwrap();
+        let argv = ["solana-vanity", "batch", jobs.to_str().unwrap(), "--threads", "2", "--verify"];
+        let Some(Command::Batch(args)) = Cli::try_parse_from(argv).unwrap().command else {
+            panic!("expected batch")
+        };
+        // Ctrl+C can only be handled once per process
+        let run = |args: &BatchArgs| run_batch(args, |_| Arc::new(AtomicBool::new(false)));
+        run(&args).unwrap();
+
+        let state_path = dir.path().join("jobs.state.json");
+        let state = BatchState::load(&state_path).unwrap();
+        assert_eq!(state.jobs["a"].len(), 2);
+        assert_eq!(state.jobs["b"].len(), 1);
+        // Files are recorded relative to the job file's directory
+        for key in &state.jobs["a"] {
+            assert!(Path::new(&key.file).starts_with("keys"));
+            let (keypair, _) = read_keypair_file(&dir.path().join(&key.file)).unwrap();
+            assert_eq!(keypair.pubkey().to_string(), key.pubkey);
+        }
+        let (_, encoding) = read_keypair_file(&dir.path().join(&state.jobs["b"][0].file)).unwrap();
+        assert_eq!(encoding, KeypairEncoding::Base58);
+
+        // A second run finds every job complete and searches for nothing
+        run(&args).unwrap();
+        assert_eq!(BatchState::load(&state_path).unwrap(), state);
+
+        // A deleted key is searched for again
+        std::fs::remove_file(dir.path().join(&state.jobs["a"][0].file)).unwrap();
+        run(&args).unwrap();
+        let state = BatchState::load(&state_path).unwrap();
+        assert_eq!(state.jobs["a"].len(), 3);
+        let file = JobFile::parse(&std::fs::read_to_string(&jobs).unwrap(), &jobs).unwrap();
+        assert_eq!(state.completed(&file.jobs()[0]).len(), 2);
+
+        std::fs::write(&jobs, "[[jobs]]\nname = \"bad\"\nprefix = \"A0\"\n").unwrap();
+        assert_eq!(run(&args).unwrap_err().exit_code(), ExitCode::InvalidPattern);
+
+        // Templated names are numbered per job and checked before the search
+        std::fs::write(
+            &jobs,
+            "[[jobs]]\nname = \"t\"\nprefix = \"A\"\ncount = 2\noutput-template = \"{job}/{prefix}-{index}.json\"\n",
+        )
+        .unwrap();
+        run(&args).unwrap();
+        assert!(dir.path().join("t/A-1.json").exists() && dir.path().join("t/A-2.json").exists());
+        std::fs::write(
+            &jobs,
+            "[[jobs]]\nname = \"x\"\nprefix = \"A\"\noutput-template = \"same.json\"\n\n\
+             [[jobs]]\nname = \"y\"\nprefix = \"B\"\noutput-template = \"same.json\"\n",
+        )
+        .unwrap();
+        assert_eq!(run(&args).unwrap_err().exit_code(), ExitCode::OutputConflict);
+        assert!(!BatchState::load(&state_path).unwrap().jobs.contains_key("x"));
+    }
+}
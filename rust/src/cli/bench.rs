@@ -0,0 +1,105 @@
+//! The `bench` command.
+
+use crate::cli::{format_duration, format_number, CliError, Result};
+use clap::Args;
+use solana_vanity::generator::benchmark_generation_rate;
+use solana_vanity::mnemonic::benchmark_mnemonic_rate;
+
+/// Options for measuring generation speed
+#[derive(Args, Debug)]
+pub struct BenchArgs {
+    /// Seconds to run each measurement
+    #[arg(long, value_name = "SECS", default_value_t = 3, value_parser = clap::value_parser!(u64).range(1..))]
+    duration: u64,
+
+    /// Number of threads for the parallel measurement (default: all CPUs)
+    #[arg(short, long, value_name = "NUM")]
+    threads: Option<usize>,
+
+    /// Measure keys derived from BIP39 mnemonics instead of plain keypairs
+    #[arg(long)]
+    use_mnemonic: bool,
+}
+
+pub fn bench(args: &BenchArgs) -> Result<()> {
+    let threads = args.threads.unwrap_or_else(num_cpus::get);
+    if threads == 0 {
+        return Err(CliError::InvalidArgument("--threads must be at least 1".to_string()));
+    }
+    let (measure, unit): (fn(u64) -> u64, &str) = if args.use_mnemonic {
+        (benchmark_mnemonic_rate, "mnemonics")
+    } else {
+        (benchmark_generation_rate, "keys")
+    };
+
+    println!();
+    println!("Generation Benchmark");
+    println!("====================");
+    println!("Measuring one thread for {}s...", args.duration);
+    let single = measure(args.duration);
+    println!("Measuring {} threads for {}s...", threads, args.duration);
+    let duration = args.duration;
+    let total: u64 = (0..threads)
+        .map(|_| std::thread::spawn(move || measure(duration)))
+        .collect::<Vec<_>>()
+        .into_iter()
+        .map(|handle| handle.join().unwrap_or(0))
+        .sum();
+
+    println!();
+    println!("{:<15}{} {}/second", "Single thread:", format_number(single), unit);
+    println!("{:<15}{} {}/second", format!("{} threads:", threads), format_number(total), unit);
+    println!(
+        "{:<15}{:.0}% of linear",
+        "Scaling:",
+        total as f64 / (single * threads as u64) as f64 * 100.0
+    );
+
+    // Case-sensitive prefixes: each character is one of 58
+    println!();
+    println!("Expected time for a case-sensitive prefix at this rate:");
+    for length in 3..=6 {
+        let attempts = 58f64.powi(length);
+        println!("  {} characters: {}", length, format_duration(attempts / total as f64));
+    }
+    println!();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Cli, Command};
+    use clap::Parser;
+    use solana_vanity::exit::ExitCode;
+
+    fn parse(argv: &[&str]) -> std::result::Result<BenchArgs, clap::Error> {
+        Cli::try_parse_from(argv).map(|cli| match cli.command {
+            Some(Command::Bench(args)) => args,
+            _ => panic!("expected bench"),
+        })
+    }
+
+    #[test]
+    fn test_bench_arguments() {
+        let args = parse(&["solana-vanity", "bench"]).unwrap();
+        assert_eq!(args.duration, 3);
+        assert_eq!(args.threads, None);
+        assert!(!args.use_mnemonic);
+
+        // A measurement takes at least a second
+        assert!(parse(&["solana-vanity", "bench", "--duration", "0"]).is_err());
+        assert!(parse(&["solana-vanity", "bench", "--duration", "-1"]).is_err());
+        assert_eq!(parse(&["solana-vanity", "bench", "--duration", "1"]).unwrap().duration, 1);
+
+        // Zero threads is rejected before anything is measured
+        let args = parse(&["solana-vanity", "bench", "--threads", "0"]).unwrap();
+        assert_eq!(bench(&args).unwrap_err().exit_code(), ExitCode::Usage);
+    }
+
+    #[test]
+    fn test_bench_runs() {
+        let args = parse(&["solana-vanity", "bench", "--duration", "1", "-t", "1"]).unwrap();
+        assert!(bench(&args).is_ok());
+    }
+}
@@ -0,0 +1,364 @@
+//! Config files: loading them, turning them into option defaults, and
+//! `config show`.
+
+use crate::cli::generate::GenerateArgs;
+use crate::cli::{CliError, Result};
+use clap::builder::ArgPredicate;
+use clap::parser::ValueSource;
+use clap::{Arg, ArgAction, ArgMatches, Args, Subcommand};
+use solana_vanity::settings::{
+    user_config_path, ConfigFile, Settings, ValueKind, PROJECT_CONFIG_FILE, SETTING_KEYS,
+};
+use std::path::{Path, PathBuf};
+
+/// Options for the config files
+#[derive(Args, Debug)]
+pub struct ConfigArgs {
+    #[command(subcommand)]
+    pub action: ConfigAction,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ConfigAction {
+    /// Print the effective generate settings and where each comes from
+    Show,
+}
+
+/// Config files in the order they apply: user, then project
+pub fn config_paths() -> Vec<PathBuf> {
+    user_config_path()
+        .into_iter()
+        .chain([PathBuf::from(PROJECT_CONFIG_FILE)])
+        .collect()
+}
+
+/// Read and parse the config files that exist
+pub fn load_config_files(paths: &[PathBuf]) -> Result<Vec<ConfigFile>> {
+    let mut files = Vec::new();
+    for path in paths {
+        files.extend(load_config_file(path)?);
+    }
+    Ok(files)
+}
+
+/// Read and parse one config file, if it exists
+///
+/// A file named like the project file is held to the project file's rules.
+fn load_config_file(path: &Path) -> Result<Option<ConfigFile>> {
+    let text = match std::fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(source) => {
+            return Err(CliError::Read {
+                what: "config file",
+                path: path.to_path_buf(),
+                source,
+            })
+        }
+    };
+    let file = if path.file_name() == Some(PROJECT_CONFIG_FILE.as_ref()) {
+        ConfigFile::parse_project(&text, path)?
+    } else {
+        ConfigFile::parse(&text, path)?
+    };
+    Ok(Some(file))
+}
+
+/// Give the search commands their settings as option defaults, so files <
+/// environment < flags
+pub fn with_settings(command: clap::Command, settings: &Settings) -> clap::Command {
+    apply_settings(command, settings)
+        .mut_subcommand("generate", |sub| apply_settings(sub, settings))
+        .mut_subcommand("estimate", |sub| apply_settings(sub, settings))
+        .mut_subcommand("batch", |sub| apply_settings(sub, settings))
+}
+
+/// Set defaults from `settings`, and add a `--no-NAME` flag for each switch
+/// so a switch that a file or variable turns on can be turned off again
+fn apply_settings(mut command: clap::Command, settings: &Settings) -> clap::Command {
+    for key in SETTING_KEYS {
+        let id = key.arg_id();
+        if !command.get_arguments().any(|arg| arg.get_id() == id.as_str()) {
+            continue;
+        }
+        if key.kind == ValueKind::Bool {
+            let negation = format!("no_{id}");
+            command = command
+                .arg(
+                    Arg::new(negation.clone())
+                        .long(format!("no-{}", key.name))
+                        .action(ArgAction::SetTrue)
+                        .overrides_with(id.clone())
+                        .help(format!("Turn off --{} set by a config file or environment variable", key.name)),
+                )
+                .mut_arg(&id, |arg| {
+                    arg.overrides_with(negation.clone())
+                        .default_value_if(negation, ArgPredicate::Equals("true".into()), Some("false"))
+                });
+        }
+        if let Some((values, _)) = settings.get(key.name) {
+            let values = values.to_vec();
+            command = command.mut_arg(id, |arg| arg.default_values(values));
+        }
+    }
+    command
+}
+
+/// Drop the settings that conflict with options given on the command line
+///
+/// Clap only checks conflicts between options that were given, so a setting
+/// meant for another mode (`rpc-url` with `--wordlist`, `exclude` with
+/// `--pda-program`) would be silently dropped by the search. Each dropped
+/// setting is returned as a warning, to be printed before parsing again.
+/// Two settings that conflict with each other are an error, since there's
+/// no telling which one was meant.
+pub fn drop_conflicting_settings(
+    command: &clap::Command,
+    matches: &ArgMatches,
+    settings: &mut Settings,
+) -> Result<Vec<String>> {
+    let (command, matches) = match matches.subcommand() {
+        Some((name, sub)) => (command.find_subcommand(name).expect("parsed subcommand exists"), sub),
+        None => (command, matches),
+    };
+    let conflicts = |a: &Arg, b: &Arg| {
+        let listed = |x: &Arg, y: &Arg| command.get_arg_conflicts_with(x).iter().any(|c| c.get_id() == y.get_id());
+        listed(a, b) || listed(b, a)
+    };
+
+    // Settings in effect: set by a file or variable and not overridden by a flag
+    let configured: Vec<(&Arg, &str, String)> = SETTING_KEYS
+        .iter()
+        .filter_map(|key| {
+            let (_, source) = settings.get(key.name)?;
+            let id = key.arg_id();
+            let arg = command.get_arguments().find(|arg| arg.get_id() == id.as_str())?;
+            if matches.value_source(&id) != Some(ValueSource::DefaultValue) {
+                return None;
+            }
+            let switched_off = key.kind == ValueKind::Bool
+                && matches.get_raw(&id).into_iter().flatten().all(|value| value == "false");
+            (!switched_off).then(|| (arg, key.name, format!("'{}' from {}", key.name, source)))
+        })
+        .collect();
+    let given: Vec<&Arg> = command
+        .get_arguments()
+        .filter(|arg| matches.value_source(arg.get_id().as_str()) == Some(ValueSource::CommandLine))
+        .collect();
+
+    for (i, (a, _, a_setting)) in configured.iter().enumerate() {
+        if let Some((_, _, b_setting)) = configured[i + 1..].iter().find(|(b, _, _)| conflicts(a, b)) {
+            return Err(CliError::InvalidArgument(format!(
+                "{a_setting} and {b_setting} can't be used together"
+            )));
+        }
+    }
+
+    let mut warnings = Vec::new();
+    for (arg, name, setting) in configured {
+        if let Some(flag) = given.iter().find(|flag| conflicts(arg, flag)) {
+            let flag = flag.get_long().map_or_else(|| flag.get_id().to_string(), |long| format!("--{long}"));
+            warnings.push(format!("{setting} doesn't apply with {flag}; ignoring it"));
+            settings.remove(name);
+        }
+    }
+    Ok(warnings)
+}
+
+/// Print each setting's effective value for `generate` and its source
+///
+/// Problems are printed in place so the rest is still shown; the first one
+/// is then returned.
+pub fn config_show(profile: Option<&str>, paths: &[PathBuf]) -> Result<()> {
+    let mut errors = Vec::new();
+    println!("# Profile: {}", profile.unwrap_or("(none)"));
+    let mut files = Vec::new();
+    for path in paths {
+        let status = match load_config_file(path) {
+            Ok(Some(file)) => {
+                files.push(file);
+                String::new()
+            }
+            Ok(None) => " (not found)".to_string(),
+            Err(e) => {
+                let status = format!(" ({e})");
+                errors.push(e);
+                status
+            }
+        };
+        println!("# Config file: {}{}", path.display(), status);
+    }
+    let settings = match Settings::resolve(&files, profile) {
+        Ok(settings) => settings,
+        Err(e) => {
+            println!("# {e}");
+            errors.push(e.into());
+            Settings::resolve(&files, None).unwrap_or_default()
+        }
+    };
+    let settings = settings.with_env(|name| std::env::var(name).ok());
+
+    // An invalid value fails the whole parse, so fall back to the raw settings
+    let matches = match apply_settings(GenerateArgs::augment_args(clap::Command::new("generate")), &settings)
+        .try_get_matches_from(["generate"])
+    {
+        Ok(matches) => Some(matches),
+        Err(e) => {
+            let message = e.to_string();
+            let message = message.lines().next().unwrap_or_default().trim_start_matches("error: ");
+            println!("# {message}");
+            errors.push(CliError::InvalidArgument(message.to_string()));
+            None
+        }
+    };
+    println!();
+
+    for key in SETTING_KEYS {
+        let values: Option<Vec<String>> = match matches {
+            Some(ref matches) => matches
+                .get_raw(&key.arg_id())
+                .map(|values| values.map(|value| value.to_string_lossy().into_owned()).collect()),
+            None => settings.get(key.name).map(|(values, _)| values.to_vec()),
+        };
+        let Some(values) = values else {
+            println!("# {} (not set)", key.name);
+            continue;
+        };
+        let source = settings.get(key.name).map_or("default".to_string(), |(_, source)| source.to_string());
+        let line = format!("{} = {}", key.name, key.to_toml(&values));
+        println!("{:<40} # {}", line, source);
+    }
+    errors.into_iter().next().map_or(Ok(()), Err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Cli, Command};
+    use clap::{CommandFactory, FromArgMatches};
+    use solana_vanity::events::ResultFormat;
+    use solana_vanity::exit::ExitCode;
+
+    #[test]
+    fn test_config_file_defaults() {
+        let text = "threads = 8\nverify = true\nexclude = [\"1111\"]\n\n[profile.ci]\nthreads = 2\nformat = \"json\"\n";
+        let files = [ConfigFile::parse(text, Path::new("config.toml")).unwrap()];
+        let parse_with = |profile: Option<&str>, argv: &[&str]| {
+            let settings = Settings::resolve(&files, profile).unwrap();
+            let matches = with_settings(Cli::command(), &settings).try_get_matches_from(argv).unwrap();
+            Cli::from_arg_matches(&matches).unwrap()
+        };
+
+        // File values are defaults for the bare invocation and `generate`
+        let cli = parse_with(None, &["solana-vanity", "--prefix", "AB"]);
+        assert_eq!(cli.generate.threads, Some(8));
+        assert!(cli.generate.verify);
+        assert_eq!(cli.generate.pattern.exclude, ["1111"]);
+        assert_eq!(cli.generate.format, ResultFormat::Text);
+
+        let cli = parse_with(Some("ci"), &["solana-vanity", "generate", "--prefix", "AB", "--profile", "ci"]);
+        let Some(Command::Generate(args)) = cli.command else { panic!("expected generate") };
+        assert_eq!(args.threads, Some(2));
+        assert_eq!(args.format, ResultFormat::Json);
+        assert!(args.verify);
+        assert_eq!(cli.profile.as_deref(), Some("ci"));
+
+        // Flags override the file
+        let cli = parse_with(Some("ci"), &["solana-vanity", "--prefix", "AB", "-t", "4", "--format", "text"]);
+        assert_eq!(cli.generate.threads, Some(4));
+        assert_eq!(cli.generate.format, ResultFormat::Text);
+        let cli = parse_with(None, &["solana-vanity", "estimate", "--prefix", "AB"]);
+        let Some(Command::Estimate(args)) = cli.command else { panic!("expected estimate") };
+        assert_eq!(args.threads, Some(8));
+
+        // A switch turned on by a file or variable can be turned off again
+        assert!(!parse_with(None, &["solana-vanity", "--prefix", "AB", "--no-verify"]).generate.verify);
+        assert!(parse_with(None, &["solana-vanity", "--prefix", "AB", "--no-verify", "--verify"]).generate.verify);
+        assert!(!parse_with(None, &["solana-vanity", "--prefix", "AB", "--verify", "--no-verify"]).generate.verify);
+        let settings = Settings::default().with_env(|name| (name == "SOLANA_VANITY_OVERWRITE").then(|| "true".into()));
+        let parse_env = |argv: &[&str]| {
+            let matches = with_settings(Cli::command(), &settings).try_get_matches_from(argv).unwrap();
+            Cli::from_arg_matches(&matches).unwrap().generate
+        };
+        assert!(parse_env(&["solana-vanity", "--prefix", "AB"]).overwrite);
+        assert!(!parse_env(&["solana-vanity", "--prefix", "AB", "--no-overwrite"]).overwrite);
+        assert!(!parse_env(&["solana-vanity", "--prefix", "AB", "--no-verify"]).verify);
+
+        // Files are read in order and missing ones are skipped
+        let dir = tempfile::tempdir().unwrap();
+        let user = dir.path().join("config.toml");
+        let project = dir.path().join("solana-vanity.toml");
+        std::fs::write(&user, "threads = 8\n").unwrap();
+        std::fs::write(&project, "threads = 3\n").unwrap();
+        let paths = [user, dir.path().join("missing.toml"), project.clone()];
+        let files = load_config_files(&paths).unwrap();
+        assert_eq!(files.len(), 2);
+        let settings = Settings::resolve(&files, None).unwrap();
+        assert_eq!(settings.get("threads").unwrap().0, ["3"]);
+
+        std::fs::write(&project, "thread = 3\n").unwrap();
+        let err = load_config_files(&paths).unwrap_err();
+        assert_eq!(err.exit_code(), ExitCode::Usage);
+        let err = CliError::from(Settings::resolve(&files, Some("mint")).unwrap_err());
+        assert_eq!(err.exit_code(), ExitCode::Usage);
+
+        // Only the user file may turn on overwriting
+        std::fs::write(&project, "overwrite = true\n").unwrap();
+        let err = load_config_files(&paths).unwrap_err();
+        assert!(err.to_string().contains("can only be set in the user config file"));
+        std::fs::write(&paths[0], "overwrite = true\n").unwrap();
+        std::fs::write(&project, "threads = 3\n").unwrap();
+        assert_eq!(load_config_files(&paths).unwrap().len(), 2);
+
+        // `config show` reports a broken file or profile instead of stopping at it
+        assert!(config_show(None, &paths).is_ok());
+        assert!(config_show(Some("mint"), &paths).is_err());
+        std::fs::write(&project, "key-format = \"bogus\"\n").unwrap();
+        let err = config_show(None, &paths).unwrap_err();
+        assert!(err.to_string().contains("bogus"));
+    }
+
+    #[test]
+    fn test_settings_for_other_modes_are_dropped() {
+        let text = "rpc-url = \"http://localhost:8899\"\nexclude = [\"1111\"]\nthreads = 2\n";
+        let files = [ConfigFile::parse(text, Path::new("config.toml")).unwrap()];
+        let parse_with = |files: &[ConfigFile], argv: &[&str]| {
+            let mut settings = Settings::resolve(files, None).unwrap();
+            let command = with_settings(Cli::command(), &settings);
+            let matches = command.clone().try_get_matches_from(argv).unwrap();
+            let warnings = drop_conflicting_settings(&command, &matches, &mut settings)?;
+            let matches = with_settings(Cli::command(), &settings).try_get_matches_from(argv).unwrap();
+            Ok::<_, CliError>((Cli::from_arg_matches(&matches).unwrap(), warnings))
+        };
+
+        // Settings every mode uses are kept
+        let (cli, warnings) = parse_with(&files, &["solana-vanity", "--prefix", "AB"]).unwrap();
+        assert!(warnings.is_empty());
+        assert_eq!(cli.generate.rpc_url.as_deref(), Some("http://localhost:8899"));
+        assert_eq!(cli.generate.pattern.exclude, ["1111"]);
+
+        // A mode that can't check accounts or exclude addresses drops them, with a warning
+        let (cli, warnings) = parse_with(&files, &["solana-vanity", "--prefix", "AB", "--best-effort"]).unwrap();
+        assert_eq!(warnings, ["'rpc-url' from config.toml doesn't apply with --best-effort; ignoring it"]);
+        assert!(cli.generate.rpc_url.is_none());
+        assert_eq!(cli.generate.pattern.exclude, ["1111"]);
+        let argv = ["solana-vanity", "generate", "--prefix", "AB", "--pda-program", "11111111111111111111111111111111"];
+        let (cli, warnings) = parse_with(&files, &argv).unwrap();
+        assert_eq!(warnings.len(), 2);
+        let Some(Command::Generate(args)) = cli.command else { panic!("expected generate") };
+        assert!(args.rpc_url.is_none());
+        assert!(args.pattern.exclude.is_empty());
+        assert_eq!(args.threads, Some(2));
+
+        // A switch turned off on the command line doesn't conflict
+        let files = [ConfigFile::parse("rpc-url = \"http://localhost:8899\"\nuse-mnemonic = true\n", Path::new("config.toml")).unwrap()];
+        let (cli, warnings) = parse_with(&files, &["solana-vanity", "--prefix", "AB", "--no-use-mnemonic"]).unwrap();
+        assert!(warnings.is_empty());
+        assert!(!cli.generate.use_mnemonic);
+
+        // Settings that conflict with each other are an error
+        let err = parse_with(&files, &["solana-vanity", "--prefix", "AB"]).unwrap_err();
+        assert_eq!(err.exit_code(), ExitCode::Usage);
+        assert!(err.to_string().contains("'use-mnemonic' from config.toml and 'rpc-url' from config.toml"));
+    }
+}
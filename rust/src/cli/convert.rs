@@ -0,0 +1,92 @@
+//! The `convert` command.
+
+use crate::cli::{CliError, Result};
+use clap::Args;
+use solana_sdk::signer::Signer;
+use solana_vanity::output::{read_keypair_file, write_keypair_as, KeypairEncoding};
+use std::path::PathBuf;
+
+/// Options for converting a keypair file
+#[derive(Args, Debug)]
+pub struct ConvertArgs {
+    /// Keypair file to read; its format is detected
+    #[arg(value_name = "INPUT")]
+    input: PathBuf,
+
+    /// Format to write: json, base58, hex or raw
+    #[arg(long, value_name = "FORMAT")]
+    to: KeypairEncoding,
+
+    /// Output file (default: <ADDRESS>.json, .b58, .hex or .bin)
+    #[arg(short, long, value_name = "FILE")]
+    output: Option<PathBuf>,
+
+    /// Overwrite the output file if it exists
+    #[arg(long)]
+    overwrite: bool,
+}
+
+pub fn convert(args: &ConvertArgs) -> Result<()> {
+    let (keypair, from) = read_keypair_file(&args.input)?;
+    let output_path = args.output.clone().unwrap_or_else(|| {
+        PathBuf::from(format!("{}.{}", keypair.pubkey(), args.to.extension()))
+    });
+    if output_path.exists() && !args.overwrite {
+        return Err(CliError::OutputExists(output_path));
+    }
+    write_keypair_as(&keypair, args.to, &output_path)?;
+    println!(
+        "Converted {} ({} -> {}) to {}",
+        keypair.pubkey(),
+        from.name(),
+        args.to.name(),
+        output_path.display()
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::inspect::{inspect, InspectArgs};
+    use crate::{Cli, Command};
+    use clap::Parser;
+    use std::path::Path;
+
+    #[test]
+    fn test_inspect_and_convert_subcommands() {
+        let dir = tempfile::tempdir().unwrap();
+        let json = dir.path().join("key.json");
+        let keypair = solana_sdk::signature::Keypair::new();
+        write_keypair_as(&keypair, KeypairEncoding::SolanaJson, &json).unwrap();
+
+        let inspect_key = |key: &str| inspect(&InspectArgs { key: key.to_string() });
+        assert!(inspect_key(json.to_str().unwrap()).is_ok());
+        assert!(inspect_key(&keypair.pubkey().to_string()).is_ok());
+        assert!(inspect_key("not-a-key").is_err());
+
+        let run_convert = |input: &Path, to: &str, output: &Path, overwrite: bool| {
+            let mut argv = vec!["solana-vanity", "convert", input.to_str().unwrap(), "--to", to];
+            argv.extend(["-o", output.to_str().unwrap()]);
+            if overwrite {
+                argv.push("--overwrite");
+            }
+            let Some(Command::Convert(args)) = Cli::try_parse_from(argv).unwrap().command else {
+                panic!("expected convert")
+            };
+            convert(&args)
+        };
+        let b58 = dir.path().join("key.b58");
+        let back = dir.path().join("back.json");
+        run_convert(&json, "base58", &b58, false).unwrap();
+        run_convert(&b58, "json", &back, false).unwrap();
+        assert_eq!(std::fs::read(&json).unwrap(), std::fs::read(&back).unwrap());
+        let (decoded, encoding) = read_keypair_file(&b58).unwrap();
+        assert_eq!(encoding, KeypairEncoding::Base58);
+        assert_eq!(decoded.pubkey(), keypair.pubkey());
+
+        // An existing output is only replaced on request
+        assert!(run_convert(&json, "json", &back, false).is_err());
+        assert!(run_convert(&json, "json", &back, true).is_ok());
+    }
+}
@@ -0,0 +1,170 @@
+//! The `estimate` command and the `--dry-run` difficulty report.
+
+use crate::cli::{format_duration, format_number, Result};
+use crate::cli::generate::build_generator;
+use crate::cli::pattern::{
+    create_derived, create_exclusions, pattern_target, require_pattern, validate_pattern,
+    PatternArgs,
+};
+use clap::Args;
+use solana_vanity::derived::DerivedConstraint;
+use solana_vanity::exclusion::ExclusionRules;
+use solana_vanity::generator::{benchmark_generation_rate, VanityGeneratorConfig};
+use solana_vanity::matcher::MatchTarget;
+use solana_vanity::mnemonic::benchmark_mnemonic_rate;
+
+/// Options for estimating a pattern's difficulty
+#[derive(Args, Debug)]
+pub struct EstimateArgs {
+    #[command(flatten)]
+    pub pattern: PatternArgs,
+
+    /// Number of threads to estimate for (default: all CPUs)
+    #[arg(short, long, value_name = "NUM")]
+    pub threads: Option<usize>,
+
+    /// Also require a derived address to match: DERIVATION=PATTERN (repeatable)
+    #[arg(long, value_name = "SPEC")]
+    pub derived: Vec<String>,
+
+    /// Estimate for keys derived from BIP39 mnemonics (much slower per attempt)
+    #[arg(long)]
+    pub use_mnemonic: bool,
+}
+
+pub fn dry_run(
+    target: &MatchTarget,
+    exclusions: &ExclusionRules,
+    derived: &[DerivedConstraint],
+    use_mnemonic: bool,
+    threads: usize,
+) -> Result<()> {
+    println!();
+    println!("Difficulty Estimation");
+    println!("=====================");
+    println!("Pattern:    {}", target.description());
+    if !exclusions.is_empty() {
+        println!(
+            "Excluding:  {} (~{:.2}% of matches rejected)",
+            exclusions.description(),
+            exclusions.rejection_probability() * 100.0
+        );
+    }
+    for constraint in derived {
+        println!("Derived:    {}", constraint.description());
+    }
+    println!("Threads:    {threads}");
+
+    // Benchmark generation rate (single-threaded measurement)
+    println!();
+    println!("Benchmarking generation rate (1 second)...");
+    // Mnemonic keys cost a PBKDF2 run each, so they are benchmarked separately
+    let (rate_per_thread, unit) = if use_mnemonic {
+        (benchmark_mnemonic_rate(1), "mnemonics")
+    } else {
+        (benchmark_generation_rate(1), "keys")
+    };
+    let total_rate = rate_per_thread * threads as u64;
+
+    println!("Rate:       {} {}/second (single thread)", format_number(rate_per_thread), unit);
+    println!(
+        "Total Rate: ~{} {}/second (estimated with {} threads)",
+        format_number(total_rate),
+        unit,
+        threads
+    );
+
+    // Calculate expected attempts
+    let generator = build_generator(target.clone(), VanityGeneratorConfig::default(), exclusions, derived, None)?;
+    let (expected_attempts, _prob) = generator.estimate_difficulty();
+
+    println!();
+    if !expected_attempts.is_finite() {
+        println!("Expected Attempts: effectively impossible");
+        eprintln!("Warning: the exclusion rules reject nearly every address; relax them before searching");
+        return Ok(());
+    }
+    println!("Expected Attempts: {}", format_number(expected_attempts as u64));
+
+    // Estimate time
+    let expected_seconds = expected_attempts / total_rate as f64;
+    println!("Estimated Time:    {}", format_duration(expected_seconds));
+
+    // Show probability table
+    println!();
+    println!("Probability of finding within:");
+    for (time, probability) in chance_table(expected_seconds) {
+        println!(
+            "  {} -> {:.1}% chance",
+            format_duration(time),
+            probability * 100.0
+        );
+    }
+
+    println!();
+
+    Ok(())
+}
+
+/// Chance of a match within a few multiples of the expected time, as
+/// (seconds, probability) pairs
+fn chance_table(expected_seconds: f64) -> Vec<(f64, f64)> {
+    [0.5, 1.0, 2.0, 5.0]
+        .iter()
+        .map(|&multiplier: &f64| (expected_seconds * multiplier, 1.0 - (-multiplier).exp()))
+        .collect()
+}
+
+pub fn estimate(args: &EstimateArgs) -> Result<()> {
+    require_pattern(&args.pattern)?;
+    validate_pattern(&args.pattern)?;
+    let target = pattern_target(&args.pattern)?;
+    let exclusions = create_exclusions(&args.pattern)?;
+    let derived = create_derived(&args.derived, args.pattern.ignore_case)?;
+    dry_run(
+        &target,
+        &exclusions,
+        &derived,
+        args.use_mnemonic,
+        args.threads.unwrap_or_else(num_cpus::get),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Cli, Command};
+    use clap::Parser;
+    use solana_vanity::exit::ExitCode;
+
+    fn parse(argv: &[&str]) -> EstimateArgs {
+        let Some(Command::Estimate(args)) = Cli::try_parse_from(argv).unwrap().command else {
+            panic!("expected estimate")
+        };
+        args
+    }
+
+    #[test]
+    fn test_chance_table() {
+        let table = chance_table(60.0);
+        let times: Vec<f64> = table.iter().map(|(time, _)| *time).collect();
+        assert_eq!(times, [30.0, 60.0, 120.0, 300.0]);
+        // The expected time gives the usual 63% chance
+        assert!((table[1].1 - 0.632).abs() < 1e-3);
+        assert!(table.windows(2).all(|pair| pair[0].1 < pair[1].1));
+    }
+
+    #[test]
+    fn test_estimate() {
+        assert!(estimate(&parse(&["solana-vanity", "estimate", "--prefix", "AB", "-t", "2"])).is_ok());
+        let args = parse(&["solana-vanity", "estimate", "--suffix", "ab", "--exclude", "1111", "-t", "1"]);
+        assert!(estimate(&args).is_ok());
+
+        let err = estimate(&parse(&["solana-vanity", "estimate", "-t", "1"])).unwrap_err();
+        assert_eq!(err.exit_code(), ExitCode::Usage);
+        let err = estimate(&parse(&["solana-vanity", "estimate", "--prefix", "A0"])).unwrap_err();
+        assert_eq!(err.exit_code(), ExitCode::InvalidPattern);
+        let err = estimate(&parse(&["solana-vanity", "estimate", "--prefix", "A", "--derived", "ata:x"])).unwrap_err();
+        assert_eq!(err.exit_code(), ExitCode::InvalidPattern);
+    }
+}
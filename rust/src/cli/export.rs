@@ -0,0 +1,148 @@
+//! The `export` and `import` commands.
+
+use crate::cli::{CliError, Result};
+use clap::Args;
+use solana_sdk::signer::Signer;
+use solana_vanity::export::{import_keypair, write_export, KeyFormat};
+use solana_vanity::output::{read_keypair_file, write_keypair_as, KeypairEncoding};
+use std::path::PathBuf;
+use zeroize::Zeroizing;
+
+/// Options for exporting a keypair to a standard key format
+#[derive(Args, Debug)]
+pub struct ExportArgs {
+    /// Keypair file to read; its format is detected
+    #[arg(value_name = "INPUT")]
+    input: PathBuf,
+
+    /// Format to write: pkcs8-pem, pkcs8-der, openssh, openssh-pub or jwk
+    #[arg(long, value_name = "FORMAT")]
+    to: KeyFormat,
+
+    /// Output file (default: <ADDRESS>.pem, .der, .pub or .jwk; <ADDRESS> for openssh)
+    #[arg(short, long, value_name = "FILE")]
+    output: Option<PathBuf>,
+
+    /// Overwrite the output file if it exists
+    #[arg(long)]
+    overwrite: bool,
+}
+
+/// Options for importing a key from a standard key format
+#[derive(Args, Debug)]
+pub struct ImportArgs {
+    /// PKCS#8 (PEM or DER), OpenSSH or JWK private key; its format is detected
+    #[arg(value_name = "INPUT")]
+    input: PathBuf,
+
+    /// Keypair format to write: json, base58, hex or raw
+    #[arg(long, value_name = "FORMAT", default_value = "json")]
+    to: KeypairEncoding,
+
+    /// Output file (default: <ADDRESS>.json, .b58, .hex or .bin)
+    #[arg(short, long, value_name = "FILE")]
+    output: Option<PathBuf>,
+
+    /// Overwrite the output file if it exists
+    #[arg(long)]
+    overwrite: bool,
+}
+
+pub fn export(args: &ExportArgs) -> Result<()> {
+    let (keypair, from) = read_keypair_file(&args.input)?;
+    let address = solana_vanity::GeneratedAddress::new(keypair, 0, 0);
+    let output_path = args
+        .output
+        .clone()
+        .unwrap_or_else(|| PathBuf::from(args.to.file_name(&address.public_key())));
+    if output_path.exists() && !args.overwrite {
+        return Err(CliError::OutputExists(output_path));
+    }
+    write_export(&address, args.to, &output_path)?;
+    println!(
+        "Exported {} ({} -> {}) to {}",
+        address.public_key(),
+        from.name(),
+        args.to,
+        output_path.display()
+    );
+    Ok(())
+}
+
+pub fn import(args: &ImportArgs) -> Result<()> {
+    let data = Zeroizing::new(std::fs::read(&args.input).map_err(|source| CliError::Read {
+        what: "key file",
+        path: args.input.clone(),
+        source,
+    })?);
+    let (keypair, from) = import_keypair(&data)?;
+    let output_path = args.output.clone().unwrap_or_else(|| {
+        PathBuf::from(format!("{}.{}", keypair.pubkey(), args.to.extension()))
+    });
+    if output_path.exists() && !args.overwrite {
+        return Err(CliError::OutputExists(output_path));
+    }
+    write_keypair_as(&keypair, args.to, &output_path)?;
+    println!(
+        "Imported {} ({} -> {}) to {}",
+        keypair.pubkey(),
+        from,
+        args.to.name(),
+        output_path.display()
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Cli, Command};
+    use clap::Parser;
+    use solana_vanity::exit::ExitCode;
+
+    #[test]
+    fn test_export_and_import_subcommands() {
+        let dir = tempfile::tempdir().unwrap();
+        let json = dir.path().join("key.json");
+        let keypair = solana_sdk::signature::Keypair::new();
+        write_keypair_as(&keypair, KeypairEncoding::SolanaJson, &json).unwrap();
+
+        let run = |argv: &[&str]| match Cli::try_parse_from(argv).unwrap().command {
+            Some(Command::Export(args)) => export(&args),
+            Some(Command::Import(args)) => import(&args),
+            _ => panic!("expected export or import"),
+        };
+        for format in KeyFormat::ALL {
+            let exported = dir.path().join(format.file_name(&keypair.pubkey().to_string()));
+            let back = dir.path().join(format!("{}.json", format.name()));
+            let (exported_str, back_str) = (exported.to_str().unwrap(), back.to_str().unwrap());
+            run(&["solana-vanity", "export", json.to_str().unwrap(), "--to", format.name(), "-o", exported_str])
+                .unwrap();
+            let imported = run(&["solana-vanity", "import", exported_str, "-o", back_str]);
+            if format.has_secret() {
+                imported.unwrap();
+                assert_eq!(std::fs::read(&json).unwrap(), std::fs::read(&back).unwrap());
+            } else {
+                assert_eq!(imported.unwrap_err().exit_code(), ExitCode::Usage);
+            }
+        }
+
+        // An existing output is only replaced on request
+        let pem = dir.path().join("key.pem");
+        let export_pem = |overwrite: bool| {
+            let mut argv = vec!["solana-vanity", "export", json.to_str().unwrap(), "--to", "pem", "-o"];
+            argv.push(pem.to_str().unwrap());
+            if overwrite {
+                argv.push("--overwrite");
+            }
+            run(&argv)
+        };
+        export_pem(false).unwrap();
+        assert!(matches!(export_pem(false), Err(CliError::OutputExists(_))));
+        assert!(export_pem(true).is_ok());
+
+        let import_json = run(&["solana-vanity", "import", json.to_str().unwrap(), "-o", "unused.json"]);
+        assert_eq!(import_json.unwrap_err().exit_code(), ExitCode::VerificationFailed);
+        assert!(Cli::try_parse_from(["solana-vanity", "export", "key.json", "--to", "pkcs12"]).is_err());
+    }
+}
@@ -0,0 +1,1029 @@
+//! The `generate` command: option parsing, the default keypair search and
+//! saving what it finds.
+
+use crate::cli::{
+    cancel_on_interrupt, format_duration, format_number, invalid, search_outcome, CliError, Result,
+};
+use crate::cli::estimate::dry_run;
+use crate::cli::mnemonic::{scan_mnemonic, search_mnemonic};
+use crate::cli::pattern::{
+    create_derived, create_exclusions, pattern_target, require_pattern, validate_pattern,
+    PatternArgs,
+};
+use crate::cli::pda::search_pda;
+use crate::cli::pump::create_tx_inputs;
+use crate::cli::report::Reporter;
+use crate::cli::seeded::search_seed;
+use crate::cli::series::search_series;
+use crate::cli::wordlist::search_wordlist;
+use clap::{ArgGroup, Args};
+use solana_vanity::derived::DerivedConstraint;
+use solana_vanity::events::{AddressResult, Event, EventFormat, ResultFormat, VerificationStatus};
+use solana_vanity::exclusion::ExclusionRules;
+use solana_vanity::generator::{
+    BestEffortConfig, ParallelSearch, RelaxSchedule, VanityGenerator, VanityGeneratorConfig,
+};
+use solana_vanity::matcher::MatchTarget;
+use solana_vanity::mnemonic::WordCount;
+use solana_vanity::output::{
+    print_quiet_result, print_result, print_verification_report, utc_date, verify_keypair_file,
+    write_keypair_file, write_report, KeypairEncoding, OutputFields, OutputTemplate,
+};
+use solana_vanity::pda::NonceEncoding;
+use solana_vanity::pump::{
+    add_pump_addresses, build_create_transaction, descriptor_path, encode_transaction,
+    pump_mint_target, write_pump_descriptor, PumpMintDescriptor,
+};
+use solana_vanity::rpc::{Preflight, PreflightPolicy, RpcClient};
+use solana_vanity::wordlist::{WordPosition, DEFAULT_MIN_WORD_LENGTH, DEFAULT_TOP_N};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant, SystemTime};
+
+/// Options for searching and saving vanity addresses
+#[derive(Args, Debug)]
+#[command(group(ArgGroup::new("timed").args(["wordlist", "best_effort"])))]
+pub struct GenerateArgs {
+    #[command(flatten)]
+    pub pattern: PatternArgs,
+
+    /// Number of threads (default: all CPUs)
+    #[arg(short, long, value_name = "NUM")]
+    pub threads: Option<usize>,
+
+    /// Output file (default: <ADDRESS>.json)
+    #[arg(short, long, value_name = "FILE")]
+    pub output: Option<PathBuf>,
+
+    /// Directory for keypair files (default: current directory)
+    #[arg(long, value_name = "DIR", conflicts_with_all = ["output", "series", "series_words", "pda_program", "seed_base", "scan_mnemonic"])]
+    pub output_dir: Option<PathBuf>,
+
+    /// Keypair file name: {pubkey}, {prefix}, {suffix}, {index} and {date} are replaced (default: {pubkey}.<EXT>)
+    #[arg(long, value_name = "TEMPLATE", conflicts_with_all = ["output", "series", "series_words", "pda_program", "seed_base", "scan_mnemonic"])]
+    pub output_template: Option<OutputTemplate>,
+
+    /// Keypair file encoding: json, base58, hex or raw
+    #[arg(long, value_name = "FORMAT", default_value = "json")]
+    pub key_format: KeypairEncoding,
+
+    /// Number of addresses to generate (default: 1)
+    #[arg(short, long, value_name = "NUM", default_value = "1")]
+    pub count: usize,
+
+    /// Verbose output
+    #[arg(short, long)]
+    pub verbose: bool,
+
+    /// Minimal output (just the public key)
+    #[arg(short, long, conflicts_with = "verbose")]
+    pub quiet: bool,
+
+    /// Verify output after generation
+    #[arg(long)]
+    pub verify: bool,
+
+    /// Estimate time without generating
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Generate a report file alongside the keypair
+    #[arg(long)]
+    pub report: bool,
+
+    /// Overwrite existing output files without prompting
+    #[arg(long)]
+    pub overwrite: bool,
+
+    /// Result format on stdout: text, or json (one object per address, never the secret)
+    #[arg(long, value_name = "FORMAT", default_value = "text", conflicts_with_all = ["dry_run", "pda_program", "seed_base", "scan_mnemonic"])]
+    pub format: ResultFormat,
+
+    /// Stream progress, match and finish events on stderr: ndjson
+    #[arg(long, value_name = "FORMAT", conflicts_with_all = ["dry_run", "pda_program", "seed_base", "scan_mnemonic"])]
+    pub events: Option<EventFormat>,
+
+    /// Find addresses starting or ending with any word from FILE
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["prefix", "suffix", "repeat_prefix", "repeat_suffix", "palindrome_prefix", "palindrome_suffix", "digit_run", "upper_run", "key_prefix", "key_shard", "key_min", "key_below"])]
+    pub wordlist: Option<PathBuf>,
+
+    /// Ignore wordlist entries shorter than NUM characters
+    #[arg(long, value_name = "NUM", default_value_t = DEFAULT_MIN_WORD_LENGTH, requires = "wordlist")]
+    pub min_word_len: usize,
+
+    /// Where wordlist words may appear: prefix, suffix or both
+    #[arg(long, value_name = "POS", default_value = "both", requires = "wordlist")]
+    pub word_position: WordPosition,
+
+    /// Time budget for wordlist and best-effort searches, in seconds
+    #[arg(long, value_name = "SECS", default_value = "60", requires = "timed")]
+    pub budget: u64,
+
+    /// Number of best wordlist or best-effort matches to keep
+    #[arg(long, value_name = "NUM", default_value_t = DEFAULT_TOP_N, requires = "timed")]
+    pub top: usize,
+
+    /// Fill a series: replace {n} in --prefix/--suffix with each number in RANGE (e.g. 1-9)
+    #[arg(long, value_name = "RANGE", conflicts_with_all = ["wordlist", "output", "count", "best_effort"])]
+    pub series: Option<String>,
+
+    /// Fill a series: replace {word} in --prefix/--suffix with each word in FILE
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["series", "wordlist", "output", "count", "best_effort"])]
+    pub series_words: Option<PathBuf>,
+
+    /// Where to write the series manifest; keypair files go in the same directory
+    #[arg(long, value_name = "FILE", default_value = "series-manifest.json")]
+    pub manifest: PathBuf,
+
+    /// Search for a vanity PDA under PROGRAM by varying a nonce seed
+    #[arg(
+        long,
+        value_name = "PROGRAM",
+        conflicts_with_all = ["wordlist", "series", "series_words", "best_effort", "count",
+                              "exclude", "exclude_at", "profanity_list", "max_run"]
+    )]
+    pub pda_program: Option<String>,
+
+    /// Fixed PDA seed before the nonce: TEXT, str:TEXT, hex:BYTES or pubkey:KEY (repeatable)
+    #[arg(long, value_name = "SEED", requires = "pda_program")]
+    pub pda_seed: Vec<String>,
+
+    /// Use find_program_address (canonical bump) instead of create_program_address
+    #[arg(long, requires = "pda_program")]
+    pub pda_find: bool,
+
+    /// How the nonce seed is encoded: le, be (8-byte u64) or str (decimal)
+    #[arg(long, value_name = "ENC", default_value = "le", requires = "pda_program")]
+    pub nonce_encoding: NonceEncoding,
+
+    /// First nonce to try (to resume or split a search)
+    #[arg(long, value_name = "NUM", default_value_t = 0, requires = "pda_program")]
+    pub nonce_start: u64,
+
+    /// Search for a vanity create_with_seed account derived from BASE
+    #[arg(
+        long,
+        value_name = "BASE",
+        requires = "seed_owner",
+        conflicts_with_all = ["pda_program", "wordlist", "series", "series_words", "best_effort", "count",
+                              "exclude", "exclude_at", "profanity_list", "max_run"]
+    )]
+    pub seed_base: Option<String>,
+
+    /// Owner program of the seed-derived account
+    #[arg(long, value_name = "PROGRAM", requires = "seed_base")]
+    pub seed_owner: Option<String>,
+
+    /// Characters seeds are built from (default: 0-9, A-Z, a-z)
+    #[arg(long, value_name = "CHARS", requires = "seed_base")]
+    pub seed_alphabet: Option<String>,
+
+    /// First seed counter to try (to resume or split a search)
+    #[arg(long, value_name = "NUM", default_value_t = 0, requires = "seed_base")]
+    pub seed_start: u64,
+
+    /// pump.fun mint mode: exact "pump" suffix, --prefix as an optional ticker,
+    /// and a <MINT>.pump.json descriptor next to the keypair
+    #[arg(
+        long,
+        conflicts_with_all = ["suffix", "suffix_case", "wordlist", "series", "series_words", "best_effort",
+                              "pda_program", "seed_base", "key_prefix", "key_shard", "key_min", "key_below",
+                              "repeat_prefix", "repeat_suffix", "palindrome_prefix", "palindrome_suffix",
+                              "digit_run", "upper_run", "lookalike", "leet", "confusable"]
+    )]
+    pub pump: bool,
+
+    /// Build a createV2 transaction for each mint, paid by CREATOR and signed by the mint
+    #[arg(long, value_name = "PUBKEY", requires_all = ["pump", "token_name", "token_symbol", "token_uri"])]
+    pub creator: Option<String>,
+
+    /// Token name for the create transaction
+    #[arg(long, value_name = "NAME", requires = "creator")]
+    pub token_name: Option<String>,
+
+    /// Token symbol for the create transaction
+    #[arg(long, value_name = "SYMBOL", requires = "creator")]
+    pub token_symbol: Option<String>,
+
+    /// Metadata URI for the create transaction
+    #[arg(long, value_name = "URI", requires = "creator")]
+    pub token_uri: Option<String>,
+
+    /// Launch the token in mayhem mode
+    #[arg(long, requires = "creator")]
+    pub mayhem_mode: bool,
+
+    /// Enable cashback for the token
+    #[arg(long, requires = "creator")]
+    pub cashback: bool,
+
+    /// Recent blockhash for the create transaction
+    #[arg(long, value_name = "HASH", requires = "creator", conflicts_with = "nonce_account")]
+    pub blockhash: Option<String>,
+
+    /// Durable nonce account for the create transaction (CREATOR is the nonce authority)
+    #[arg(long, value_name = "PUBKEY", requires_all = ["creator", "nonce_hash"])]
+    pub nonce_account: Option<String>,
+
+    /// Current value stored in --nonce-account
+    #[arg(long, value_name = "HASH", requires = "nonce_account")]
+    pub nonce_hash: Option<String>,
+
+    /// Derive keys from random BIP39 mnemonics at m/44'/501'/0'/0' so they can be
+    /// restored from a seed phrase (much slower per attempt)
+    #[arg(
+        long,
+        conflicts_with_all = ["wordlist", "series", "series_words", "best_effort", "pda_program", "seed_base",
+                              "rpc_url", "derived", "pump"]
+    )]
+    pub use_mnemonic: bool,
+
+    /// Number of mnemonic words: 12 or 24
+    #[arg(long, value_name = "NUM", default_value = "12", requires = "use_mnemonic")]
+    pub words: WordCount,
+
+    /// Scan derivation paths of an existing seed phrase read from FILE ('-' for
+    /// stdin) instead of generating keys; the phrase is never written anywhere
+    #[arg(
+        long,
+        value_name = "FILE",
+        conflicts_with_all = ["wordlist", "series", "series_words", "best_effort", "pda_program", "seed_base",
+                              "rpc_url", "derived", "pump", "use_mnemonic"]
+    )]
+    pub scan_mnemonic: Option<PathBuf>,
+
+    /// Read the BIP39 passphrase for --scan-mnemonic from FILE
+    #[arg(long, value_name = "FILE", requires = "scan_mnemonic")]
+    pub passphrase_file: Option<PathBuf>,
+
+    /// Account indices to scan, START-END inclusive
+    #[arg(long, value_name = "RANGE", default_value = "0-9999", requires = "scan_mnemonic")]
+    pub accounts: String,
+
+    /// Change indices to scan for each account, START-END inclusive
+    #[arg(long, value_name = "RANGE", default_value = "0", requires = "scan_mnemonic")]
+    pub changes: String,
+
+    /// Check each match with getAccountInfo on this JSON-RPC endpoint and skip
+    /// addresses that already exist (default: offline, no network access)
+    #[arg(
+        long,
+        value_name = "URL",
+        conflicts_with_all = ["wordlist", "series", "series_words", "best_effort", "pda_program", "seed_base"]
+    )]
+    pub rpc_url: Option<String>,
+
+    /// What to do with existing accounts: reject (skip) or flag (skip and list them)
+    #[arg(long, value_name = "POLICY", default_value = "reject", requires = "rpc_url")]
+    pub rpc_policy: PreflightPolicy,
+
+    /// Save a match whose RPC lookup failed, marked as not checked, instead of stopping
+    #[arg(long, requires = "rpc_url")]
+    pub rpc_keep_unchecked: bool,
+
+    /// Timeout for each RPC request, in seconds
+    #[arg(long, value_name = "SECS", default_value_t = 10, requires = "rpc_url")]
+    pub rpc_timeout: u64,
+
+    /// Also require a derived address to match: DERIVATION=PATTERN (repeatable)
+    ///
+    /// DERIVATION is ata-owner:OWNER[:PROGRAM], ata-mint:MINT[:PROGRAM],
+    /// pda:PROGRAM:SEED,{key},... or programdata; PATTERN is PREFIX...,
+    /// ...SUFFIX or PREFIX...SUFFIX
+    #[arg(
+        long,
+        value_name = "SPEC",
+        conflicts_with_all = ["wordlist", "series", "series_words", "best_effort", "pda_program", "seed_base"]
+    )]
+    pub derived: Vec<String>,
+
+    /// Search for --budget seconds and keep the closest partial matches
+    #[arg(long, conflicts_with = "wordlist")]
+    pub best_effort: bool,
+
+    /// In best-effort mode, accept one fewer matched character every SECS seconds
+    #[arg(long, value_name = "SECS", requires = "best_effort")]
+    pub relax_every: Option<u64>,
+
+    /// Never relax below NUM matched characters
+    #[arg(long, value_name = "NUM", default_value_t = 1, requires = "relax_every")]
+    pub min_match: usize,
+}
+
+impl GenerateArgs {
+    /// Whether text output is reduced to bare addresses (or replaced by JSON)
+    pub fn quiet(&self) -> bool {
+        self.quiet || self.format == ResultFormat::Json
+    }
+}
+
+pub fn generate(cli: &GenerateArgs) -> Result<()> {
+    let reporter = Reporter::new(cli);
+    let result = search(cli, &reporter);
+    reporter.finish(&result);
+    result
+}
+
+pub fn search(cli: &GenerateArgs, reporter: &Reporter) -> Result<()> {
+    // A bad or colliding file name should fail now, not after the search
+    check_output_paths(cli)?;
+
+    // Exclusion rules apply to every mode that generates keypairs
+    let exclusions = create_exclusions(&cli.pattern)?;
+
+    // Dictionary mode has its own target and search loop
+    if let Some(ref path) = cli.wordlist {
+        return search_wordlist(cli, reporter, path, &exclusions);
+    }
+
+    // Series templates are validated slot by slot when rendered
+    if cli.series.is_some() || cli.series_words.is_some() {
+        return search_series(cli, reporter, &exclusions);
+    }
+
+    // Validate arguments; pump mode has a built-in suffix
+    if !cli.pump {
+        require_pattern(&cli.pattern)?;
+    }
+    validate_pattern(&cli.pattern)?;
+
+    // Validate count
+    if cli.count == 0 {
+        return Err(CliError::InvalidArgument("Count must be at least 1".to_string()));
+    }
+
+    // Create match target
+    let target = create_match_target(cli)?;
+    let derived = create_derived(&cli.derived, cli.pattern.ignore_case)?;
+    let preflight = create_preflight(cli)?;
+    // Check create transaction inputs before spending time on the search
+    create_tx_inputs(cli)?;
+
+    // Path scans walk a fixed index space of an existing phrase
+    if let Some(ref source) = cli.scan_mnemonic {
+        return scan_mnemonic(cli, source, target, &exclusions);
+    }
+
+    // PDA mode searches nonce seeds instead of keypairs, with its own estimate
+    if let Some(ref program) = cli.pda_program {
+        return search_pda(cli, program, target);
+    }
+
+    // Seed mode searches create_with_seed seeds instead of keypairs, with its own estimate
+    if let (Some(ref base), Some(ref owner)) = (&cli.seed_base, &cli.seed_owner) {
+        return search_seed(cli, base, owner, target);
+    }
+
+    // Handle dry run
+    if cli.dry_run {
+        return dry_run(
+            &target,
+            &exclusions,
+            &derived,
+            cli.use_mnemonic,
+            cli.threads.unwrap_or_else(num_cpus::get),
+        );
+    }
+
+    // Mnemonic mode derives each candidate from a fresh seed phrase
+    if cli.use_mnemonic {
+        return search_mnemonic(cli, reporter, target, &exclusions);
+    }
+
+    // Best-effort mode keeps the closest matches found within the budget
+    if cli.best_effort {
+        return search_best_effort(cli, reporter, target, &exclusions);
+    }
+
+    // Run generation
+    generate_addresses(cli, reporter, target, &exclusions, &derived, preflight.as_ref())
+}
+
+fn create_preflight(cli: &GenerateArgs) -> Result<Option<Preflight>> {
+    let Some(ref url) = cli.rpc_url else {
+        return Ok(None);
+    };
+    let client = RpcClient::new(url)
+        .map_err(CliError::Config)?
+        .with_timeout(Duration::from_secs(cli.rpc_timeout));
+    Ok(Some(Preflight::new(client, cli.rpc_policy).with_keep_unchecked(cli.rpc_keep_unchecked)))
+}
+
+pub fn build_generator(
+    target: MatchTarget,
+    config: VanityGeneratorConfig,
+    exclusions: &ExclusionRules,
+    derived: &[DerivedConstraint],
+    preflight: Option<&Preflight>,
+) -> Result<VanityGenerator> {
+    let mut generator = VanityGenerator::new(target, config)?.with_exclusions(exclusions.clone());
+    generator = derived.iter().cloned().fold(generator, VanityGenerator::with_derived);
+    if let Some(preflight) = preflight {
+        generator = generator.with_preflight(preflight.clone());
+    }
+    Ok(generator)
+}
+
+pub fn create_match_target(cli: &GenerateArgs) -> Result<MatchTarget> {
+    if cli.pump {
+        return pump_mint_target(cli.pattern.prefix.as_deref(), cli.pattern.ignore_case)
+            .map_err(invalid("pattern"));
+    }
+    pattern_target(&cli.pattern)
+}
+
+fn generate_addresses(
+    cli: &GenerateArgs,
+    reporter: &Reporter,
+    target: MatchTarget,
+    exclusions: &ExclusionRules,
+    derived: &[DerivedConstraint],
+    preflight: Option<&Preflight>,
+) -> Result<()> {
+    let threads = cli.threads.unwrap_or_else(num_cpus::get);
+
+    // Print header
+    if !cli.quiet() {
+        println!();
+        println!("Solana Vanity Address Generator");
+        println!("===============================");
+        println!("Pattern:  {}", target.description());
+        if !exclusions.is_empty() {
+            println!("Exclude:  {}", exclusions.description());
+        }
+        for constraint in derived {
+            println!("Derived:  {}", constraint.description());
+        }
+        if let Some(preflight) = preflight {
+            println!("RPC:      {}", preflight.description());
+        }
+        println!("Threads:  {threads}");
+        println!("Count:    {}", cli.count);
+        println!();
+    }
+
+    // Create generator config
+    let config = VanityGeneratorConfig {
+        threads,
+        verify_keypairs: true,
+        progress_interval: 50_000,
+    };
+
+    // One Ctrl+C handler cancels whichever search is running
+    let cancelled = Arc::new(AtomicBool::new(false));
+    cancel_on_interrupt(Arc::clone(&cancelled), "cancelling");
+
+    // Generate addresses
+    for i in 0..cli.count {
+        if cancelled.load(Ordering::SeqCst) {
+            return Err(solana_vanity::GeneratorError::Cancelled.into());
+        }
+
+        if cli.count > 1 && !cli.quiet() {
+            println!("Generating address {}/{}", i + 1, cli.count);
+        }
+
+        let result = generate_single_address(
+            cli,
+            reporter,
+            &config,
+            target.clone(),
+            exclusions,
+            derived,
+            preflight,
+            &cancelled,
+        )?;
+
+        // The search only stops without an address when cancelled
+        let Some(address) = result else {
+            return Err(solana_vanity::GeneratorError::Cancelled.into());
+        };
+        let index = (cli.count > 1).then_some(i + 1);
+        save_address(cli, reporter, &address, &target.description(), index)?;
+    }
+
+    Ok(())
+}
+
+/// Write a found address and print the result, following the output flags
+///
+/// `index` is appended to an explicit `--output` path when several addresses
+/// are written in one run, so they don't overwrite each other. Otherwise the
+/// file is named by `--output-template` in `--output-dir`.
+pub fn save_address(
+    cli: &GenerateArgs,
+    reporter: &Reporter,
+    address: &solana_vanity::GeneratedAddress,
+    pattern: &str,
+    index: Option<usize>,
+) -> Result<PathBuf> {
+    let output_path = match (&cli.output, index) {
+        (Some(base), Some(index)) => {
+            let stem = base.file_stem().unwrap_or_default().to_string_lossy();
+            let ext = base.extension().map(|e| e.to_string_lossy()).unwrap_or_default();
+            if ext.is_empty() {
+                base.with_file_name(format!("{}-{}", stem, index))
+            } else {
+                base.with_file_name(format!("{}-{}.{}", stem, index, ext))
+            }
+        }
+        (Some(base), None) => base.clone(),
+        (None, index) => {
+            let template = output_template(cli);
+            let name = template.render(&address.public_key(), &output_fields(cli, index.unwrap_or(1)));
+            cli.output_dir.clone().unwrap_or_default().join(name)
+        }
+    };
+
+    save_address_to(cli, reporter, address, pattern, output_path)
+}
+
+/// The `--output-template`, or `{pubkey}.<EXT>` for the `--key-format`
+fn output_template(cli: &GenerateArgs) -> OutputTemplate {
+    cli.output_template.clone().unwrap_or_else(|| {
+        OutputTemplate::new(&format!("{{pubkey}}.{}", cli.key_format.extension())).expect("default template is valid")
+    })
+}
+
+/// Date the run started, for `{date}` in output templates
+pub fn run_date() -> &'static str {
+    static DATE: OnceLock<String> = OnceLock::new();
+    DATE.get_or_init(|| utc_date(SystemTime::now()))
+}
+
+/// Template values for the `index`th keypair file of a run
+pub fn output_fields(cli: &GenerateArgs, index: usize) -> OutputFields {
+    let suffix = cli.pattern.suffix.clone().or_else(|| cli.pump.then(|| "pump".to_string()));
+    OutputFields {
+        prefix: cli.pattern.prefix.clone().unwrap_or_default(),
+        suffix: suffix.unwrap_or_default(),
+        index,
+        date: run_date().to_string(),
+        job: None,
+    }
+}
+
+/// Check the keypair file names a run will use before it searches
+///
+/// Names that don't depend on the key must be distinct and, without
+/// `--overwrite`, must not exist yet.
+fn check_output_paths(cli: &GenerateArgs) -> Result<()> {
+    let writes_named_keypairs = cli.output.is_none()
+        && !cli.dry_run
+        && cli.series.is_none()
+        && cli.series_words.is_none()
+        && cli.pda_program.is_none()
+        && cli.seed_base.is_none()
+        && cli.scan_mnemonic.is_none();
+    if !writes_named_keypairs {
+        return Ok(());
+    }
+
+    let files = if cli.wordlist.is_some() || cli.best_effort { cli.top } else { cli.count };
+    let fields: Vec<OutputFields> = (1..=files).map(|index| output_fields(cli, index)).collect();
+    let template = output_template(cli);
+    let dir = cli.output_dir.clone().unwrap_or_default();
+    if let Some(paths) = template.planned_paths(&dir, &fields)? {
+        let mut paths = paths.into_iter().flat_map(|path| {
+            let companions = companion_paths(cli, &path);
+            std::iter::once(path).chain(companions)
+        });
+        if let Some(path) = paths.find(|path| path.exists() && !cli.overwrite) {
+            return Err(CliError::OutputExists(path));
+        }
+    }
+    Ok(())
+}
+
+/// Files written next to a keypair file, which `--overwrite` also covers
+fn companion_paths(cli: &GenerateArgs, keypair_path: &Path) -> Vec<PathBuf> {
+    let mut paths = Vec::new();
+    if cli.pump {
+        paths.push(descriptor_path(keypair_path));
+    }
+    if cli.use_mnemonic {
+        paths.push(mnemonic_path(keypair_path));
+    }
+    paths
+}
+
+/// Phrase file next to a keypair file: `<stem>.mnemonic.txt`
+pub fn mnemonic_path(keypair_path: &Path) -> PathBuf {
+    keypair_path.with_extension("mnemonic.txt")
+}
+
+/// Write a found address to `output_path` and print the result
+pub fn save_address_to(
+    cli: &GenerateArgs,
+    reporter: &Reporter,
+    address: &solana_vanity::GeneratedAddress,
+    pattern: &str,
+    output_path: PathBuf,
+) -> Result<PathBuf> {
+    // Check if the file or its companions exist and --overwrite not set
+    if !cli.overwrite {
+        let existing = std::iter::once(output_path.clone())
+            .chain(companion_paths(cli, &output_path))
+            .find(|path| path.exists());
+        if let Some(path) = existing {
+            return Err(CliError::OutputExists(path));
+        }
+    }
+
+    // Write keypair file
+    write_keypair_file(address, cli.key_format, &output_path)?;
+
+    // Describe pump.fun mints for the TypeScript SDK
+    let descriptor_file = cli.pump.then(|| descriptor_path(&output_path));
+    let mut create_transaction = None;
+    if let Some(ref path) = descriptor_file {
+        let mut descriptor = PumpMintDescriptor::new(address, cli.pattern.prefix.as_deref(), Some(&output_path));
+        if let Some((creator, args, lifetime)) = create_tx_inputs(cli)? {
+            let transaction = build_create_transaction(address.keypair(), &creator, &args, &lifetime)
+                .and_then(|tx| encode_transaction(&tx))?;
+            descriptor = descriptor.with_create_transaction(transaction.clone());
+            create_transaction = Some(transaction);
+        }
+        write_pump_descriptor(&descriptor, path)?;
+    }
+
+    // Write report if requested
+    if cli.report {
+        let report_path = output_path.with_extension("txt");
+        write_report(address, &report_path)?;
+    }
+
+    // Print result; JSON results are printed by the reporter
+    if cli.format == ResultFormat::Text && cli.quiet {
+        print_quiet_result(address);
+    } else if cli.format == ResultFormat::Text {
+        print_result(address, cli.verbose);
+        println!("Saved to: {}", output_path.display());
+        if let Some(ref path) = descriptor_file {
+            println!("Mint descriptor: {}", path.display());
+        }
+        if let Some(ref transaction) = create_transaction {
+            println!("Create transaction (signed by the mint, needs the creator's signature):");
+            println!("{transaction}");
+        }
+    }
+
+    // Verify if requested
+    let mut verification = VerificationStatus::Skipped;
+    if cli.verify {
+        let report = verify_keypair_file(&output_path)?;
+        if cli.format == ResultFormat::Text {
+            print_verification_report(&report);
+        }
+        verification = if report.signature_valid
+            && report.keypair_format_valid
+            && report.file_permissions_secure
+        {
+            VerificationStatus::Passed
+        } else {
+            VerificationStatus::Failed
+        };
+    }
+
+    if !cli.quiet() {
+        println!();
+    }
+
+    let mut result = AddressResult::new(address, pattern, &output_path).with_verification(verification);
+    if let Some(ref path) = descriptor_file {
+        result = result.with_pump(path, create_transaction);
+    }
+    reporter.found(result);
+    if verification == VerificationStatus::Failed {
+        return Err(CliError::VerificationFailed(format!(
+            "'{}' did not pass verification",
+            output_path.display()
+        )));
+    }
+    Ok(output_path)
+}
+
+fn search_best_effort(
+    cli: &GenerateArgs,
+    reporter: &Reporter,
+    target: MatchTarget,
+    exclusions: &ExclusionRules,
+) -> Result<()> {
+    if cli.top == 0 {
+        return Err(CliError::InvalidArgument("--top must be at least 1".to_string()));
+    }
+
+    let threads = cli.threads.unwrap_or_else(num_cpus::get);
+    let schedule = cli.relax_every.map(|secs| RelaxSchedule {
+        step: Duration::from_secs(secs),
+        min_score: cli.min_match,
+    });
+
+    if !cli.quiet() {
+        println!();
+        println!("Solana Vanity Address Generator (best-effort mode)");
+        println!("==================================================");
+        println!("Pattern:  {}", target.description());
+        if !exclusions.is_empty() {
+            println!("Exclude:  {}", exclusions.description());
+        }
+        println!("Threads:  {threads}");
+        println!("Budget:   {}", format_duration(cli.budget as f64));
+        println!("Keep:     top {}", cli.top);
+        if let Some(schedule) = schedule {
+            println!(
+                "Relax:    one character every {}, down to {}",
+                format_duration(schedule.step.as_secs_f64()),
+                schedule.min_score
+            );
+        }
+        println!();
+    }
+
+    let config = VanityGeneratorConfig {
+        threads,
+        verify_keypairs: true,
+        progress_interval: 50_000,
+    };
+    let pattern = target.description();
+    let generator = VanityGenerator::new(target, config)?.with_exclusions(exclusions.clone());
+
+    let interrupted = cancel_on_interrupt(generator.cancel_handle(), "stopping search");
+
+    let options = BestEffortConfig {
+        budget: Duration::from_secs(cli.budget),
+        keep: cli.top,
+        schedule,
+    };
+    let found = reporter.track(cli.quiet(), || generator.attempts(), || generator.generate_best_effort(&options))?;
+
+    if !cli.quiet() {
+        println!(
+            "Searched {} addresses, kept {} match(es)",
+            format_number(generator.attempts()),
+            found.len()
+        );
+        if generator.excluded() > 0 {
+            println!(
+                "Rejected {} candidate(s) by exclusion rules",
+                format_number(generator.excluded())
+            );
+        }
+        println!();
+    }
+
+    let multiple = found.len() > 1;
+    for (i, m) in found.iter().enumerate() {
+        if !cli.quiet() {
+            println!(
+                "#{} {} prefix + {} suffix character(s)",
+                i + 1,
+                m.score.prefix,
+                m.score.suffix
+            );
+        }
+        save_address(cli, reporter, &m.address, &pattern, multiple.then_some(i + 1))?;
+    }
+
+    search_outcome(&interrupted, found.is_empty())
+}
+
+#[allow(clippy::too_many_arguments)]
+fn generate_single_address(
+    cli: &GenerateArgs,
+    reporter: &Reporter,
+    config: &VanityGeneratorConfig,
+    target: MatchTarget,
+    exclusions: &ExclusionRules,
+    derived: &[DerivedConstraint],
+    preflight: Option<&Preflight>,
+    cancelled: &Arc<AtomicBool>,
+) -> Result<Option<solana_vanity::GeneratedAddress>> {
+    // Check if already cancelled
+    if cancelled.load(Ordering::SeqCst) {
+        return Ok(None);
+    }
+
+    let generator = build_generator(target, config.clone(), exclusions, derived, preflight)?
+        .with_cancel_handle(Arc::clone(cancelled));
+
+    // Track progress with simple output
+    let start = Instant::now();
+    let last_print = Arc::new(std::sync::Mutex::new(Instant::now()));
+    let quiet = cli.quiet();
+    // Progress events replace the progress line on stderr
+    let events = reporter.progress_events();
+    let progress_line = !quiet && events.is_none();
+
+    // Create progress callback
+    let callback: solana_vanity::generator::ProgressCallback =
+        Box::new(move |attempts, elapsed| {
+            if quiet && events.is_none() {
+                return;
+            }
+            // Use try_lock to avoid panic if mutex is poisoned
+            if let Ok(mut last) = last_print.try_lock() {
+                if last.elapsed().as_secs() >= 1 {
+                    let rate = if elapsed > 0.0 {
+                        attempts as f64 / elapsed
+                    } else {
+                        0.0
+                    };
+                    if let Some(ref events) = events {
+                        events.emit(&Event::Progress {
+                            attempts,
+                            elapsed_ms: (elapsed * 1000.0) as u64,
+                            keys_per_sec: rate.round(),
+                        });
+                    } else {
+                        eprint!(
+                            "\rSearching... {} attempts ({:.0} keys/sec)    ",
+                            format_number(attempts),
+                            rate
+                        );
+                    }
+                    *last = Instant::now();
+                }
+            }
+        });
+
+    // Generate
+    let result = generator.generate_with_callback(Some(callback));
+
+    // Clear progress line
+    if !cli.quiet() {
+        let elapsed = start.elapsed();
+        if progress_line {
+            eprint!("\r{:60}\r", ""); // Clear the line
+        }
+        if elapsed.as_secs() >= 1 {
+            println!("Search completed in {}", format_duration(elapsed.as_secs_f64()));
+        }
+        if generator.excluded() > 0 {
+            println!(
+                "Rejected {} matching address(es) by exclusion rules",
+                format_number(generator.excluded())
+            );
+        }
+        if generator.preflight_rejected() > 0 {
+            println!(
+                "Skipped {} matching address(es) that already exist on chain",
+                format_number(generator.preflight_rejected())
+            );
+        }
+    }
+    if let Some(e) = generator.preflight_error() {
+        eprintln!("Warning: pre-flight check failed ({e}); the address was saved without an on-chain check");
+    }
+
+    match result {
+        Ok(mut address) => {
+            if cli.pump {
+                add_pump_addresses(&mut address);
+            }
+            Ok(Some(address))
+        }
+        Err(solana_vanity::GeneratorError::Cancelled) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::test_support::{parse, try_parse};
+    use crate::Cli;
+    use clap::Parser;
+    use solana_vanity::exit::ExitCode;
+
+    #[test]
+    fn test_cli_preflight() {
+        // Offline unless an endpoint is given
+        let cli = try_parse(["solana-vanity", "--prefix", "A"]).unwrap();
+        assert!(create_preflight(&cli).unwrap().is_none());
+
+        let cli = try_parse([
+            "solana-vanity", "--prefix", "A", "--rpc-url", "http://127.0.0.1:8899", "--rpc-policy", "flag",
+        ])
+        .unwrap();
+        let preflight = create_preflight(&cli).unwrap().unwrap();
+        assert_eq!(preflight.policy(), PreflightPolicy::Flag);
+        assert!(!preflight.keeps_unchecked());
+
+        // Keeping keys whose lookup failed is opt-in
+        let cli = try_parse([
+            "solana-vanity", "--prefix", "A", "--rpc-url", "http://127.0.0.1:8899", "--rpc-keep-unchecked",
+        ])
+        .unwrap();
+        assert!(create_preflight(&cli).unwrap().unwrap().keeps_unchecked());
+        assert!(try_parse(["solana-vanity", "--prefix", "A", "--rpc-keep-unchecked"]).is_err());
+
+        let cli = try_parse(["solana-vanity", "--prefix", "A", "--rpc-url", "localhost:8899"]).unwrap();
+        assert!(create_preflight(&cli).is_err());
+        assert!(try_parse(["solana-vanity", "--prefix", "A", "--rpc-policy", "flag"]).is_err());
+    }
+
+    #[test]
+    fn test_mnemonic_file_not_overwritten() {
+        let dir = tempfile::tempdir().unwrap();
+        let keypair_path = dir.path().join("key.json");
+        let phrase = mnemonic_path(&keypair_path);
+        assert_eq!(phrase, dir.path().join("key.mnemonic.txt"));
+        std::fs::write(&phrase, "keep").unwrap();
+        let address = solana_vanity::GeneratedAddress::new(solana_sdk::signature::Keypair::new(), 1, 0);
+
+        // The phrase file is checked before the keypair is written
+        let cli = parse(["solana-vanity", "--prefix", "A", "--use-mnemonic", "-o", keypair_path.to_str().unwrap()]);
+        let saved = save_address_to(&cli, &Reporter::new(&cli), &address, "A", keypair_path.clone());
+        assert!(matches!(saved, Err(CliError::OutputExists(ref path)) if *path == phrase));
+        assert!(!keypair_path.exists());
+    }
+
+    #[test]
+    fn test_cli_best_effort() {
+        let cli = try_parse([
+            "solana-vanity",
+            "--prefix",
+            "ABCDEFG",
+            "--best-effort",
+            "--budget",
+            "120",
+            "--relax-every",
+            "30",
+            "--min-match",
+            "4",
+        ])
+        .unwrap();
+        assert!(cli.best_effort);
+        assert_eq!(cli.budget, 120);
+        assert_eq!(cli.relax_every, Some(30));
+        assert_eq!(cli.min_match, 4);
+
+        // The relax schedule only applies to best-effort searches
+        assert!(try_parse(["solana-vanity", "--prefix", "A", "--relax-every", "5"]).is_err());
+        assert!(try_parse(["solana-vanity", "--wordlist", "w.txt", "--best-effort"]).is_err());
+    }
+
+    #[test]
+    fn test_output_templates() {
+        let dir = tempfile::tempdir().unwrap();
+        let out = dir.path().to_str().unwrap();
+        let cli = parse([
+            "solana-vanity", "--prefix", "AB", "--suffix", "z", "--count", "2", "--output-dir", out,
+            "--output-template", "{prefix}-{suffix}-{index}-{pubkey}.json",
+        ]);
+        assert!(check_output_paths(&cli).is_ok());
+        let fields = output_fields(&cli, 2);
+        assert_eq!((fields.prefix.as_str(), fields.suffix.as_str(), fields.index), ("AB", "z", 2));
+        assert_eq!(fields.date, run_date());
+        assert_eq!(cli.output_template.unwrap().render("ABz", &fields), "AB-z-2-ABz.json");
+
+        // Names without {pubkey} or {index} collide when several files are written
+        let fixed = |count: &str, overwrite: bool| {
+            let mut argv = vec!["solana-vanity", "--prefix", "AB", "--count", count, "--output-dir", out,
+                                "--output-template", "{prefix}.json"];
+            if overwrite {
+                argv.push("--overwrite");
+            }
+            check_output_paths(&parse(argv))
+        };
+        assert_eq!(fixed("2", false).unwrap_err().exit_code(), ExitCode::OutputConflict);
+        assert!(fixed("1", false).is_ok());
+        std::fs::write(dir.path().join("AB.json"), "[]").unwrap();
+        assert!(matches!(fixed("1", false), Err(CliError::OutputExists(_))));
+        assert!(fixed("1", true).is_ok());
+
+        let job = parse(["solana-vanity", "--prefix", "AB", "--output-template", "{job}-{pubkey}.json"]);
+        assert_eq!(check_output_paths(&job).unwrap_err().exit_code(), ExitCode::Usage);
+        let system = parse(["solana-vanity", "--prefix", "AB", "--output-dir", "/etc/keys"]);
+        assert!(check_output_paths(&system).is_err());
+
+        assert!(Cli::try_parse_from(["solana-vanity", "--prefix", "A", "--output-template", "{name}"]).is_err());
+        assert!(Cli::try_parse_from(["solana-vanity", "--prefix", "A", "-o", "a.json", "--output-dir", out]).is_err());
+    }
+
+    #[test]
+    fn test_key_format() {
+        let fields = OutputFields::default();
+        let json = parse(["solana-vanity", "--prefix", "AB"]);
+        assert_eq!(json.key_format, KeypairEncoding::SolanaJson);
+        assert_eq!(output_template(&json).render("ABz", &fields), "ABz.json");
+
+        // The default file name follows the encoding; an explicit template wins
+        for (name, file) in [("base58", "ABz.b58"), ("hex", "ABz.hex"), ("raw", "ABz.bin")] {
+            let cli = parse(["solana-vanity", "--prefix", "AB", "--key-format", name]);
+            assert_eq!(output_template(&cli).render("ABz", &fields), file);
+        }
+        let cli = parse(["solana-vanity", "--prefix", "AB", "--key-format", "hex", "--output-template", "{pubkey}.key"]);
+        assert_eq!(output_template(&cli).render("ABz", &fields), "ABz.key");
+
+        assert!(Cli::try_parse_from(["solana-vanity", "--prefix", "A", "--key-format", "pem"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_with_threads() {
+        let cli = parse(["solana-vanity", "--prefix", "A", "--threads", "4"]);
+        assert_eq!(cli.threads, Some(4));
+    }
+}
@@ -0,0 +1,204 @@
+//! The `grind` command, taking `solana-keygen grind` arguments.
+
+use crate::cli::{cancel_on_interrupt, format_number, search_outcome, CliError, Result};
+use crate::cli::generate::mnemonic_path;
+use crate::cli::report::Reporter;
+use clap::{ArgGroup, Args};
+use solana_vanity::generator::{ParallelSearch, VanityGeneratorConfig};
+use solana_vanity::grind::{GrindMatch, GrindSearch, GrindSpec, DEFAULT_DERIVATION_PATH};
+use solana_vanity::mnemonic::WordCount;
+use solana_vanity::output::{default_output_path, write_keypair_file, KeypairEncoding};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+/// Arguments of `solana-keygen grind`, writing `<PUBKEY>.json` to the current directory
+#[derive(Args, Debug)]
+#[command(group(
+    ArgGroup::new("specs")
+        .args(["starts_with", "ends_with", "starts_and_ends_with"])
+        .required(true)
+        .multiple(true)
+))]
+pub struct GrindArgs {
+    /// Find COUNT keypairs whose public key starts with PREFIX (repeatable)
+    #[arg(long, value_name = "PREFIX:COUNT")]
+    starts_with: Vec<String>,
+
+    /// Find COUNT keypairs whose public key ends with SUFFIX (repeatable)
+    #[arg(long, value_name = "SUFFIX:COUNT")]
+    ends_with: Vec<String>,
+
+    /// Find COUNT keypairs whose public key starts with PREFIX and ends with SUFFIX (repeatable)
+    #[arg(long, value_name = "PREFIX:SUFFIX:COUNT")]
+    starts_and_ends_with: Vec<String>,
+
+    /// Match patterns case-insensitively
+    #[arg(long)]
+    ignore_case: bool,
+
+    /// Number of threads (default: all CPUs)
+    #[arg(long, value_name = "NUMBER")]
+    num_threads: Option<usize>,
+
+    /// Generate keypairs from BIP39 mnemonics; each seed phrase is written to
+    /// <PUBKEY>.mnemonic.txt instead of being printed
+    #[arg(long)]
+    use_mnemonic: bool,
+
+    /// Number of mnemonic words: 12 or 24
+    #[arg(long, value_name = "NUMBER", default_value = "12", requires = "use_mnemonic")]
+    word_count: WordCount,
+
+    /// Mnemonic language (only english is supported)
+    #[arg(long, value_name = "LANGUAGE", default_value = "english", value_parser = ["english"],
+          requires = "use_mnemonic")]
+    language: String,
+
+    /// Accepted for compatibility; no BIP39 passphrase is ever used
+    #[arg(long, requires = "use_mnemonic")]
+    no_bip39_passphrase: bool,
+
+    /// Derive mnemonic keys along this path (default without a value: m/44'/501'/0'/0')
+    #[arg(long, value_name = "DERIVATION_PATH", num_args = 0..=1,
+          default_missing_value = DEFAULT_DERIVATION_PATH, requires = "use_mnemonic")]
+    derivation_path: Option<String>,
+
+    /// Only print the public keys found; don't write keypair files
+    #[arg(long, conflicts_with = "use_mnemonic")]
+    no_outfile: bool,
+}
+
+/// Parse the grind specs in keygen's order: starts-with, ends-with, then both
+fn grind_specs(args: &GrindArgs) -> Result<Vec<GrindSpec>> {
+    let starts = args.starts_with.iter().map(|s| GrindSpec::starts_with(s));
+    let ends = args.ends_with.iter().map(|s| GrindSpec::ends_with(s));
+    let both = args.starts_and_ends_with.iter().map(|s| GrindSpec::starts_and_ends_with(s));
+    starts
+        .chain(ends)
+        .chain(both)
+        .map(|spec| spec.map_err(CliError::Config))
+        .collect()
+}
+
+/// Write one found key to `<PUBKEY>.json`, and its phrase next to it, unless
+/// only public keys are wanted
+fn save_grind_key(args: &GrindArgs, m: &GrindMatch) -> Result<()> {
+    let pubkey = m.address.public_key();
+    if args.no_outfile {
+        println!("Found matching key {}", pubkey);
+        return Ok(());
+    }
+    let path = default_output_path(&pubkey);
+    write_keypair_file(&m.address, KeypairEncoding::SolanaJson, &path)?;
+    println!("Wrote keypair to {}", path.display());
+    let phrase_path = mnemonic_path(&path);
+    if m.write_phrase(&phrase_path)? {
+        println!("Wrote seed phrase to {} (keep it secret)", phrase_path.display());
+    }
+    Ok(())
+}
+
+pub fn grind(args: &GrindArgs) -> Result<()> {
+    let specs = grind_specs(args)?;
+
+    let threads = args.num_threads.unwrap_or_else(num_cpus::get);
+    let config = VanityGeneratorConfig {
+        threads,
+        verify_keypairs: true,
+        progress_interval: 50_000,
+    };
+    let mut search = GrindSearch::new(specs, args.ignore_case, config)?;
+    if args.use_mnemonic {
+        search = search
+            .with_mnemonic(args.word_count, args.derivation_path.as_deref())
+            .map_err(CliError::Config)?;
+    }
+
+    println!("Searching with {} threads for:", threads);
+    for spec in search.specs() {
+        println!("\t{}", spec.description());
+    }
+
+    let interrupted = cancel_on_interrupt(search.cancel_handle(), "stopping grind");
+
+    // The first write error stops the search and is reported at the end
+    let cancel = search.cancel_handle();
+    let found = AtomicU64::new(0);
+    let error = Mutex::new(None);
+    let on_match = |m: GrindMatch| {
+        let mut error = error.lock().unwrap();
+        if error.is_some() {
+            return;
+        }
+        match save_grind_key(args, &m) {
+            Ok(()) => {
+                found.fetch_add(1, Ordering::SeqCst);
+            }
+            Err(e) => {
+                *error = Some(e);
+                cancel.store(true, Ordering::SeqCst);
+            }
+        }
+    };
+    Reporter::plain().track(false, || search.attempts(), || search.run(on_match))?;
+    if let Some(e) = error.into_inner().unwrap() {
+        return Err(e);
+    }
+
+    let found = found.into_inner();
+    let wanted = search.total_count();
+    if found < wanted {
+        println!(
+            "Grind stopped after {} attempts with {}/{} keypairs found.",
+            format_number(search.attempts()),
+            found,
+            wanted
+        );
+    }
+
+    search_outcome(&interrupted, false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Cli, Command};
+    use clap::Parser;
+
+    #[test]
+    fn test_cli_grind_subcommand() {
+        let cli = Cli::try_parse_from([
+            "solana-vanity", "grind", "--starts-with", "AB:3", "--starts-with", "C:1", "--ends-with", "pump:1",
+            "--starts-and-ends-with", "X:Y:2", "--ignore-case", "--num-threads", "4",
+        ])
+        .unwrap();
+        let Some(Command::Grind(args)) = cli.command else { panic!("expected grind") };
+        assert!(args.ignore_case);
+        assert_eq!(args.num_threads, Some(4));
+        let specs = grind_specs(&args).unwrap();
+        let counts: Vec<u64> = specs.iter().map(|s| s.count).collect();
+        assert_eq!(counts, vec![3, 1, 1, 2]);
+        assert_eq!(specs[3].suffix.as_deref(), Some("Y"));
+
+        // Mnemonic options, with keygen's optional-value --derivation-path
+        let cli = Cli::try_parse_from([
+            "solana-vanity", "grind", "--starts-with", "A:1", "--use-mnemonic", "--word-count", "24",
+            "--no-bip39-passphrase", "--derivation-path",
+        ])
+        .unwrap();
+        let Some(Command::Grind(args)) = cli.command else { panic!("expected grind") };
+        assert_eq!(args.word_count, WordCount::TwentyFour);
+        assert_eq!(args.derivation_path.as_deref(), Some(DEFAULT_DERIVATION_PATH));
+
+        // A spec is required, and top-level flags don't mix with the subcommand
+        assert!(Cli::try_parse_from(["solana-vanity", "grind"]).is_err());
+        assert!(Cli::try_parse_from(["solana-vanity", "grind", "--starts-with", "A:1", "--word-count", "24"]).is_err());
+        assert!(Cli::try_parse_from(["solana-vanity", "--prefix", "A", "grind", "--starts-with", "A:1"]).is_err());
+        let cli = Cli::try_parse_from(["solana-vanity", "grind", "--starts-with", "A0:1"]).unwrap();
+        let Some(Command::Grind(args)) = cli.command else { panic!("expected grind") };
+        assert!(grind_specs(&args).is_err());
+
+        // Plain invocations still work without a subcommand
+        assert!(Cli::try_parse_from(["solana-vanity", "--prefix", "A"]).unwrap().command.is_none());
+    }
+}
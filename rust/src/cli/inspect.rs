@@ -0,0 +1,103 @@
+//! The `inspect` command.
+
+use crate::cli::{CliError, Result};
+use clap::Args;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signer::Signer;
+use solana_vanity::output::read_keypair_file;
+use solana_vanity::raw::to_hex;
+use solana_vanity::security::{verify_file_permissions, verify_keypair_integrity};
+use std::path::Path;
+use std::str::FromStr;
+
+/// Options for inspecting a key
+#[derive(Args, Debug)]
+pub struct InspectArgs {
+    /// Keypair file (Solana JSON, Base58, hex or raw) or a Base58 public key
+    #[arg(value_name = "FILE|PUBKEY")]
+    pub key: String,
+}
+
+pub fn inspect(args: &InspectArgs) -> Result<()> {
+    println!();
+    for (label, value) in describe(&args.key)? {
+        println!("{:<13}{}", format!("{label}:"), value);
+    }
+    println!();
+    Ok(())
+}
+
+/// Labelled facts about a keypair file or a public key, in display order
+fn describe(key: &str) -> Result<Vec<(&'static str, String)>> {
+    let path = Path::new(key);
+    if path.is_file() {
+        let (keypair, encoding) = read_keypair_file(path)?;
+        let pubkey = keypair.pubkey();
+        let valid = verify_keypair_integrity(&keypair).is_ok();
+        let secure = verify_file_permissions(path).unwrap_or(false);
+        Ok(vec![
+            ("File", path.display().to_string()),
+            ("Format", encoding.name().to_string()),
+            ("Public Key", pubkey.to_string()),
+            ("Bytes (hex)", to_hex(&pubkey.to_bytes())),
+            ("Key Check", if valid { "✅ Valid" } else { "❌ Invalid" }.to_string()),
+            ("Permissions", if secure { "✅ Secure" } else { "⚠️  Not secure" }.to_string()),
+        ])
+    } else {
+        let pubkey = Pubkey::from_str(key).map_err(|_| {
+            CliError::InvalidArgument(format!(
+                "'{}' is neither a keypair file nor a Base58 public key",
+                key
+            ))
+        })?;
+        let on_curve = if pubkey.is_on_curve() { "yes (can have a keypair)" } else { "no (program derived address)" };
+        Ok(vec![
+            ("Public Key", pubkey.to_string()),
+            ("Bytes (hex)", to_hex(&pubkey.to_bytes())),
+            ("On Curve", on_curve.to_string()),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::signature::Keypair;
+    use solana_vanity::exit::ExitCode;
+    use solana_vanity::output::{write_keypair_file, KeypairEncoding};
+    use solana_vanity::GeneratedAddress;
+
+    #[test]
+    fn test_inspect_keypair_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("key.b58");
+        let address = GeneratedAddress::new(Keypair::new(), 1, 0);
+        write_keypair_file(&address, KeypairEncoding::Base58, &path).unwrap();
+
+        let rows = describe(path.to_str().unwrap()).unwrap();
+        let labels: Vec<&str> = rows.iter().map(|(label, _)| *label).collect();
+        assert_eq!(labels, ["File", "Format", "Public Key", "Bytes (hex)", "Key Check", "Permissions"]);
+        assert_eq!(rows[1].1, KeypairEncoding::Base58.name());
+        assert_eq!(rows[2].1, address.public_key());
+        assert_eq!(rows[3].1.len(), 64);
+        assert_eq!(rows[4].1, "✅ Valid");
+    }
+
+    #[test]
+    fn test_inspect_pubkey() {
+        // A PDA has no keypair, so it is off the curve
+        let program = Pubkey::new_unique();
+        let (pda, _) = Pubkey::find_program_address(&[b"vanity"], &program);
+        let rows = describe(&pda.to_string()).unwrap();
+        assert_eq!(rows[0], ("Public Key", pda.to_string()));
+        assert_eq!(rows[1].1, to_hex(&pda.to_bytes()));
+        assert_eq!(rows[2].1, "no (program derived address)");
+
+        let wallet = Keypair::new().pubkey();
+        assert_eq!(describe(&wallet.to_string()).unwrap()[2].1, "yes (can have a keypair)");
+
+        // Neither a file nor a key
+        let err = describe("not-a-key-0OIl").unwrap_err();
+        assert_eq!(err.exit_code(), ExitCode::Usage);
+    }
+}
@@ -0,0 +1,255 @@
+//! Mnemonic modes: keys derived from fresh BIP39 phrases, and path scans
+//! over an existing phrase.
+
+use crate::cli::{cancel_on_interrupt, format_duration, format_number, CliError, Result};
+use crate::cli::generate::{mnemonic_path, save_address, GenerateArgs};
+use crate::cli::report::Reporter;
+use solana_vanity::exclusion::ExclusionRules;
+use solana_vanity::generator::{ParallelSearch, VanityGeneratorConfig};
+use solana_vanity::matcher::MatchTarget;
+use solana_vanity::mnemonic::{
+    format_path, write_mnemonic_file, write_path_record, MnemonicSearch, PathScan, DEFAULT_ACCOUNT,
+    DEFAULT_CHANGE,
+};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use zeroize::Zeroizing;
+
+pub fn search_mnemonic(
+    cli: &GenerateArgs,
+    reporter: &Reporter,
+    target: MatchTarget,
+    exclusions: &ExclusionRules,
+) -> Result<()> {
+    let threads = cli.threads.unwrap_or_else(num_cpus::get);
+
+    if !cli.quiet() {
+        println!();
+        println!("Solana Vanity Address Generator (mnemonic mode)");
+        println!("===============================================");
+        println!("Pattern:  {}", target.description());
+        if !exclusions.is_empty() {
+            println!("Exclude:  {}", exclusions.description());
+        }
+        println!("Mnemonic: {} words, {}", cli.words.words(), format_path(DEFAULT_ACCOUNT, DEFAULT_CHANGE));
+        println!("Threads:  {threads}");
+        println!("Count:    {}", cli.count);
+        println!();
+    }
+
+    let config = VanityGeneratorConfig {
+        threads,
+        verify_keypairs: true,
+        progress_interval: 1_000,
+    };
+
+    // One Ctrl+C handler cancels whichever search is running
+    let cancelled = Arc::new(AtomicBool::new(false));
+    cancel_on_interrupt(Arc::clone(&cancelled), "cancelling");
+
+    for i in 0..cli.count {
+        if cancelled.load(Ordering::SeqCst) {
+            return Err(solana_vanity::GeneratorError::Cancelled.into());
+        }
+        if cli.count > 1 && !cli.quiet() {
+            println!("Generating address {}/{}", i + 1, cli.count);
+        }
+
+        let search = MnemonicSearch::new(target.clone(), config.clone())
+            .with_word_count(cli.words)
+            .with_exclusions(exclusions.clone())
+            .with_cancel_handle(Arc::clone(&cancelled));
+        let result = reporter.track(cli.quiet(), || search.attempts(), || search.run());
+
+        let found = match result {
+            Ok(found) => found,
+            Err(e @ solana_vanity::GeneratorError::Cancelled) => {
+                eprintln!("\nSearch cancelled after {} mnemonics.", format_number(search.attempts()));
+                return Err(e.into());
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        let index = (cli.count > 1).then_some(i + 1);
+        let output_path = save_address(cli, reporter, &found.address, &target.description(), index)?;
+        let mnemonic_path = mnemonic_path(&output_path);
+        write_mnemonic_file(&found, &mnemonic_path)?;
+        if !cli.quiet() {
+            println!("Mnemonic saved to: {} (keep it secret)", mnemonic_path.display());
+            println!();
+        }
+    }
+
+    Ok(())
+}
+
+/// Read a secret from FILE, or from stdin for `-`
+fn read_secret(source: &Path) -> Result<Zeroizing<String>> {
+    let mut secret = Zeroizing::new(String::new());
+    let result = if source == Path::new("-") {
+        std::io::stdin().read_to_string(&mut secret)
+    } else {
+        std::fs::File::open(source).and_then(|mut file| file.read_to_string(&mut secret))
+    };
+    result.map_err(|e| CliError::Read {
+        what: "secret from",
+        path: source.to_path_buf(),
+        source: e,
+    })?;
+    Ok(secret)
+}
+
+/// Parse an inclusive index range such as `0-9999` (or a single index)
+fn parse_index_range(flag: &str, spec: &str) -> Result<std::ops::Range<u32>> {
+    let invalid = || {
+        CliError::InvalidArgument(format!("Invalid --{} '{}'. Expected START-END, e.g. 0-9999", flag, spec))
+    };
+    let (start, end) = spec.split_once('-').unwrap_or((spec, spec));
+    let start: u32 = start.trim().parse().map_err(|_| invalid())?;
+    let end: u32 = end.trim().parse().map_err(|_| invalid())?;
+    if start > end || end == u32::MAX {
+        return Err(invalid());
+    }
+    Ok(start..end + 1)
+}
+
+pub fn scan_mnemonic(
+    cli: &GenerateArgs,
+    source: &Path,
+    target: MatchTarget,
+    exclusions: &ExclusionRules,
+) -> Result<()> {
+    let phrase = read_secret(source)?;
+    let passphrase = match cli.passphrase_file {
+        Some(ref path) => {
+            let mut passphrase = read_secret(path)?;
+            // Drop the trailing newline an editor or `echo` leaves behind
+            let len = passphrase.trim_end_matches(['\r', '\n']).len();
+            passphrase.truncate(len);
+            passphrase
+        }
+        None => Zeroizing::new(String::new()),
+    };
+    let threads = cli.threads.unwrap_or_else(num_cpus::get);
+
+    let config = VanityGeneratorConfig {
+        threads,
+        verify_keypairs: true,
+        progress_interval: 1_000,
+    };
+    let accounts = parse_index_range("accounts", &cli.accounts)?;
+    let changes = parse_index_range("changes", &cli.changes)?;
+    let scan = PathScan::new(&phrase, &passphrase, target.clone(), config)
+        .and_then(|scan| scan.with_accounts(accounts))
+        .and_then(|scan| scan.with_changes(changes))
+        .map_err(CliError::Config)?
+        .with_exclusions(exclusions.clone());
+    drop(phrase);
+
+    if !cli.quiet() {
+        println!();
+        println!("Solana Vanity Address Generator (path scan mode)");
+        println!("================================================");
+        println!("Pattern:  {}", target.description());
+        if !exclusions.is_empty() {
+            println!("Exclude:  {}", exclusions.description());
+        }
+        println!("Paths:    m/44'/501'/{{{}}}'/{{{}}}' ({})", cli.accounts, cli.changes, format_number(scan.total_paths()));
+        println!("Threads:  {threads}");
+        println!();
+    }
+
+    if cli.dry_run {
+        let expected = scan.expected_attempts();
+        if !expected.is_finite() {
+            println!("Expected Attempts: effectively impossible");
+            eprintln!("Warning: the exclusion rules reject nearly every address; relax them before searching");
+            return Ok(());
+        }
+        println!("Expected Attempts: {}", format_number(expected as u64));
+        println!(
+            "Chance in range:   {:.2}%",
+            (1.0 - (-(scan.total_paths() as f64) / expected).exp()) * 100.0
+        );
+        return Ok(());
+    }
+
+    cancel_on_interrupt(scan.cancel_handle(), "stopping path scan");
+
+    let found = match scan.run() {
+        Ok(found) => found,
+        Err(e @ solana_vanity::GeneratorError::Cancelled) => {
+            eprintln!("\nScan cancelled after {} paths.", format_number(scan.attempts()));
+            return Err(e.into());
+        }
+        Err(e @ solana_vanity::GeneratorError::Exhausted) => {
+            eprintln!(
+                "No matching address in {} paths. Widen --accounts or --changes.",
+                format_number(scan.total_paths())
+            );
+            return Err(e.into());
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    let output_path = cli
+        .output
+        .clone()
+        .unwrap_or_else(|| PathBuf::from(format!("path-{}.json", found.address)));
+    if output_path.exists() && !cli.overwrite {
+        return Err(CliError::OutputExists(output_path));
+    }
+    write_path_record(&found, &output_path)?;
+
+    if cli.quiet() {
+        println!("{} {}", found.address, found.path);
+    } else {
+        println!("Address:  {}", found.address);
+        println!("Path:     {}", found.path);
+        println!(
+            "Searched: {} paths in {}",
+            format_number(found.attempts),
+            format_duration(found.time_ms as f64 / 1000.0)
+        );
+        println!("Saved to: {} (no secret; restore from your seed phrase)", output_path.display());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::test_support::try_parse;
+    use solana_vanity::mnemonic::WordCount;
+
+    #[test]
+    fn test_cli_mnemonic_mode() {
+        let cli = try_parse(["solana-vanity", "--prefix", "A", "--use-mnemonic", "--words", "24"]).unwrap();
+        assert!(cli.use_mnemonic);
+        assert_eq!(cli.words, WordCount::TwentyFour);
+
+        assert!(try_parse(["solana-vanity", "--prefix", "A", "--use-mnemonic", "--words", "15"]).is_err());
+        assert!(try_parse(["solana-vanity", "--prefix", "A", "--words", "24"]).is_err());
+        assert!(try_parse(["solana-vanity", "--pump", "--use-mnemonic"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_scan_mnemonic() {
+        let cli = try_parse([
+            "solana-vanity", "--prefix", "A", "--scan-mnemonic", "-", "--accounts", "5-9", "--changes", "0-1",
+        ])
+        .unwrap();
+        assert_eq!(cli.scan_mnemonic.as_deref(), Some(Path::new("-")));
+        assert_eq!(parse_index_range("accounts", &cli.accounts).unwrap(), 5..10);
+        assert_eq!(parse_index_range("changes", &cli.changes).unwrap(), 0..2);
+        assert_eq!(parse_index_range("changes", "3").unwrap(), 3..4);
+        assert!(parse_index_range("accounts", "9-5").is_err());
+        assert!(parse_index_range("accounts", "a-b").is_err());
+
+        assert!(try_parse(["solana-vanity", "--prefix", "A", "--accounts", "0-5"]).is_err());
+        assert!(try_parse(["solana-vanity", "--prefix", "A", "--scan-mnemonic", "-", "--use-mnemonic"]).is_err());
+    }
+}
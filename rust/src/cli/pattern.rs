@@ -0,0 +1,471 @@
+//! Pattern options shared by `generate`, `estimate` and `verify`, and the
+//! match targets, exclusions and derived constraints built from them.
+
+use crate::cli::{invalid, CliError, Result};
+use clap::Args;
+use solana_vanity::aesthetic::PatternClass;
+use solana_vanity::config::{validate_prefix, validate_suffix};
+use solana_vanity::derived::DerivedConstraint;
+use solana_vanity::exclusion::ExclusionRules;
+use solana_vanity::lookalike::ConfusableTable;
+use solana_vanity::matcher::{CaseRule, MatchTarget};
+use solana_vanity::raw::{ByteConstraint, RawTarget};
+use std::path::PathBuf;
+
+/// What an address must (and must not) look like
+#[derive(Args, Debug)]
+pub struct PatternArgs {
+    /// Find address starting with PREFIX
+    #[arg(short, long, value_name = "PREFIX")]
+    pub prefix: Option<String>,
+
+    /// Find address ending with SUFFIX
+    #[arg(short, long, value_name = "SUFFIX")]
+    pub suffix: Option<String>,
+
+    /// Case-insensitive matching
+    #[arg(short, long)]
+    pub ignore_case: bool,
+
+    /// Case rule for the prefix: sensitive, insensitive, or a per-character mask like "iiss"
+    #[arg(long, value_name = "RULE", requires = "prefix", conflicts_with_all = ["lookalike", "leet", "confusable"])]
+    pub prefix_case: Option<CaseRule>,
+
+    /// Case rule for the suffix: sensitive, insensitive, or a per-character mask like "ssss"
+    #[arg(long, value_name = "RULE", requires = "suffix", conflicts_with_all = ["lookalike", "leet", "confusable"])]
+    pub suffix_case: Option<CaseRule>,
+
+    /// Find an address whose first NUM characters are identical
+    #[arg(long, value_name = "NUM", conflicts_with_all = ["prefix", "suffix"])]
+    pub repeat_prefix: Option<usize>,
+
+    /// Find an address whose last NUM characters are identical
+    #[arg(long, value_name = "NUM", conflicts_with_all = ["prefix", "suffix"])]
+    pub repeat_suffix: Option<usize>,
+
+    /// Find an address whose first NUM characters form a palindrome
+    #[arg(long, value_name = "NUM", conflicts_with_all = ["prefix", "suffix"])]
+    pub palindrome_prefix: Option<usize>,
+
+    /// Find an address whose last NUM characters form a palindrome
+    #[arg(long, value_name = "NUM", conflicts_with_all = ["prefix", "suffix"])]
+    pub palindrome_suffix: Option<usize>,
+
+    /// Find an address containing a run of at least NUM digits
+    #[arg(long, value_name = "NUM", conflicts_with_all = ["prefix", "suffix"])]
+    pub digit_run: Option<usize>,
+
+    /// Find an address containing a run of at least NUM uppercase letters
+    #[arg(long, value_name = "NUM", conflicts_with_all = ["prefix", "suffix"])]
+    pub upper_run: Option<usize>,
+
+    /// Raw key must start with these bytes, as HEX or HEX/MASK (repeatable)
+    #[arg(long, value_name = "HEX[/MASK]", conflicts_with_all = ["prefix", "suffix"])]
+    pub key_prefix: Vec<String>,
+
+    /// Top BITS bits of the raw key must equal ID, e.g. 12:0x5a3
+    #[arg(long, value_name = "BITS:ID", conflicts_with_all = ["prefix", "suffix"])]
+    pub key_shard: Option<String>,
+
+    /// Raw key must sort at or above KEY (Base58 or 64 hex digits)
+    #[arg(long, value_name = "KEY", conflicts_with_all = ["prefix", "suffix"])]
+    pub key_min: Option<String>,
+
+    /// Raw key must sort strictly below KEY (Base58 or 64 hex digits)
+    #[arg(long, value_name = "KEY", conflicts_with_all = ["prefix", "suffix"])]
+    pub key_below: Option<String>,
+
+    /// Reject matches containing SUBSTR anywhere (repeatable)
+    #[arg(long, value_name = "SUBSTR")]
+    pub exclude: Vec<String>,
+
+    /// Reject matches containing SUBSTR at zero-based position POS (repeatable)
+    #[arg(long, value_name = "SUBSTR@POS")]
+    pub exclude_at: Vec<String>,
+
+    /// Compare --exclude and --exclude-at substrings ignoring case
+    #[arg(long)]
+    pub exclude_ignore_case: bool,
+
+    /// Reject matches containing any word from FILE (case-insensitive)
+    #[arg(long, value_name = "FILE")]
+    pub profanity_list: Option<PathBuf>,
+
+    /// Reject matches where a character repeats more than NUM times in a row
+    #[arg(long, value_name = "NUM", value_parser = clap::value_parser!(usize))]
+    pub max_run: Option<usize>,
+
+    /// Accept Base58 lookalikes for each pattern character (0->o/Q/D, I->i/1/L/J, l->L/1)
+    #[arg(long)]
+    pub lookalike: bool,
+
+    /// Also accept leet digits as lookalikes (E->3, A->4, ...); implies --lookalike
+    #[arg(long)]
+    pub leet: bool,
+
+    /// Extra lookalike mapping X=abc (repeatable); implies --lookalike
+    #[arg(long, value_name = "X=abc")]
+    pub confusable: Vec<String>,
+}
+
+pub fn create_derived(specs: &[String], ignore_case: bool) -> Result<Vec<DerivedConstraint>> {
+    let derived: Vec<DerivedConstraint> = specs
+        .iter()
+        .map(|spec| {
+            DerivedConstraint::parse(spec, ignore_case)
+                .map_err(CliError::Config)
+        })
+        .collect::<Result<_>>()?;
+
+    // Each derived address is reported under its label, so one derivation
+    // can't carry two patterns (use PREFIX...SUFFIX instead)
+    for (i, constraint) in derived.iter().enumerate() {
+        if derived[..i].iter().any(|other| other.label() == constraint.label()) {
+            return Err(CliError::Config(solana_vanity::ConfigError::InvalidDerived(format!(
+                "'{}' is given more than once",
+                constraint.label()
+            ))));
+        }
+    }
+    Ok(derived)
+}
+
+pub fn create_exclusions(pattern: &PatternArgs) -> Result<ExclusionRules> {
+    let mut rules = ExclusionRules::new();
+
+    for text in &pattern.exclude {
+        rules = rules.forbid(text, pattern.exclude_ignore_case);
+    }
+
+    for spec in &pattern.exclude_at {
+        let (text, position) = spec
+            .rsplit_once('@')
+            .and_then(|(text, pos)| Some((text, pos.parse::<usize>().ok()?)))
+            .filter(|(text, _)| !text.is_empty())
+            .ok_or_else(|| {
+                CliError::InvalidArgument(format!(
+                    "Invalid --exclude-at '{}'. Expected SUBSTR@POS, e.g. 1111@0",
+                    spec
+                ))
+            })?;
+        rules = rules.forbid_at(text, position, pattern.exclude_ignore_case);
+    }
+
+    if let Some(ref path) = pattern.profanity_list {
+        rules = rules.with_profanity_file(path).map_err(|source| CliError::Read {
+            what: "profanity list",
+            path: path.clone(),
+            source,
+        })?;
+    }
+
+    if let Some(max) = pattern.max_run {
+        if max == 0 {
+            return Err(CliError::InvalidArgument("--max-run must be at least 1".to_string()));
+        }
+        rules = rules.with_max_repeat_run(max);
+    }
+
+    Ok(rules)
+}
+
+/// Structural pattern classes requested on the command line
+fn pattern_classes(pattern: &PatternArgs) -> Vec<PatternClass> {
+    [
+        pattern.repeat_prefix.map(PatternClass::RepeatPrefix),
+        pattern.repeat_suffix.map(PatternClass::RepeatSuffix),
+        pattern.palindrome_prefix.map(PatternClass::PalindromePrefix),
+        pattern.palindrome_suffix.map(PatternClass::PalindromeSuffix),
+        pattern.digit_run.map(PatternClass::DigitRun),
+        pattern.upper_run.map(PatternClass::UppercaseRun),
+    ]
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+fn raw_key_enabled(pattern: &PatternArgs) -> bool {
+    !pattern.key_prefix.is_empty()
+        || pattern.key_shard.is_some()
+        || pattern.key_min.is_some()
+        || pattern.key_below.is_some()
+}
+
+/// Raw key constraints requested on the command line
+fn raw_key_target(pattern: &PatternArgs) -> Result<RawTarget> {
+    let mut constraints = Vec::new();
+    for spec in &pattern.key_prefix {
+        constraints.push(ByteConstraint::masked_prefix(spec));
+    }
+    if let Some(ref spec) = pattern.key_shard {
+        constraints.push(ByteConstraint::shard(spec));
+    }
+    if let Some(ref spec) = pattern.key_min {
+        constraints.push(ByteConstraint::at_least(spec));
+    }
+    if let Some(ref spec) = pattern.key_below {
+        constraints.push(ByteConstraint::below(spec));
+    }
+
+    constraints
+        .into_iter()
+        .try_fold(RawTarget::new(), |target, constraint| target.with(constraint?))
+        .map_err(invalid("pattern"))
+}
+
+fn lookalike_enabled(pattern: &PatternArgs) -> bool {
+    pattern.lookalike || pattern.leet || !pattern.confusable.is_empty()
+}
+
+/// Fail unless some pattern was given
+pub fn require_pattern(pattern: &PatternArgs) -> Result<()> {
+    if pattern.prefix.is_none()
+        && pattern.suffix.is_none()
+        && pattern_classes(pattern).is_empty()
+        && !raw_key_enabled(pattern)
+    {
+        return Err(CliError::InvalidArgument(
+            "No pattern specified. Use --prefix and/or --suffix (or --wordlist, --repeat-prefix, --key-prefix, ...) to specify a pattern.".to_string()
+        ));
+    }
+    Ok(())
+}
+
+/// Check the prefix and suffix are valid Base58
+pub fn validate_pattern(pattern: &PatternArgs) -> Result<()> {
+    // Lookalike patterns may contain non-Base58 characters; they are
+    // validated against the confusable table when the target is built
+    if lookalike_enabled(pattern) {
+        return Ok(());
+    }
+    if let Some(ref prefix) = pattern.prefix {
+        validate_prefix(prefix).map_err(invalid("prefix"))?;
+    }
+    if let Some(ref suffix) = pattern.suffix {
+        validate_suffix(suffix).map_err(invalid("suffix"))?;
+    }
+    Ok(())
+}
+
+pub fn pattern_target(pattern: &PatternArgs) -> Result<MatchTarget> {
+    let classes = pattern_classes(pattern);
+    if raw_key_enabled(pattern) {
+        if !classes.is_empty() {
+            return Err(CliError::InvalidArgument(
+                "Raw key constraints can't be combined with structural patterns".to_string(),
+            ));
+        }
+        return MatchTarget::raw(raw_key_target(pattern)?)
+            .map_err(invalid("pattern"));
+    }
+
+    if !classes.is_empty() {
+        return MatchTarget::aesthetic(classes)
+            .map_err(invalid("pattern"));
+    }
+
+    if lookalike_enabled(pattern) {
+        let mut table = ConfusableTable::builtin();
+        if pattern.leet {
+            table = table.with_leet();
+        }
+        for spec in &pattern.confusable {
+            table
+                .insert_spec(spec)
+                .map_err(CliError::Config)?;
+        }
+        return MatchTarget::lookalike(
+            pattern.prefix.as_deref(),
+            pattern.suffix.as_deref(),
+            table,
+            pattern.ignore_case,
+        )
+        .map_err(invalid("pattern"));
+    }
+
+    // Per-segment rules; a segment without its own rule follows --ignore-case
+    if pattern.prefix_case.is_some() || pattern.suffix_case.is_some() {
+        let rule = |r: &Option<CaseRule>| r.clone().unwrap_or(CaseRule::from_flag(pattern.ignore_case));
+        return MatchTarget::segments(
+            pattern.prefix.as_deref().map(|p| (p, rule(&pattern.prefix_case))),
+            pattern.suffix.as_deref().map(|s| (s, rule(&pattern.suffix_case))),
+        )
+        .map_err(invalid("pattern"));
+    }
+
+    match (&pattern.prefix, &pattern.suffix) {
+        (Some(prefix), Some(suffix)) => {
+            MatchTarget::both(prefix, suffix, pattern.ignore_case)
+                .map_err(invalid("pattern"))
+        }
+        (Some(prefix), None) => {
+            MatchTarget::prefix(prefix, pattern.ignore_case)
+                .map_err(invalid("prefix"))
+        }
+        (None, Some(suffix)) => {
+            MatchTarget::suffix(suffix, pattern.ignore_case)
+                .map_err(invalid("suffix"))
+        }
+        (None, None) => unreachable!(), // Already validated above
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::generate::create_match_target;
+    use crate::cli::test_support::{parse, try_parse};
+
+    #[test]
+    fn test_cli_parsing() {
+        let cli = parse(["solana-vanity", "--prefix", "ABC"]);
+        assert_eq!(cli.pattern.prefix, Some("ABC".to_string()));
+        assert!(cli.pattern.suffix.is_none());
+        assert!(!cli.pattern.ignore_case);
+    }
+
+    #[test]
+    fn test_cli_with_suffix() {
+        let cli = parse(["solana-vanity", "--suffix", "XYZ", "--ignore-case"]);
+        assert!(cli.pattern.prefix.is_none());
+        assert_eq!(cli.pattern.suffix, Some("XYZ".to_string()));
+        assert!(cli.pattern.ignore_case);
+    }
+
+    #[test]
+    fn test_cli_lookalike_target() {
+        let cli = parse([
+            "solana-vanity",
+            "--prefix",
+            "C0IN",
+            "--leet",
+            "--confusable",
+            "N=M",
+        ]);
+        assert!(lookalike_enabled(&cli.pattern));
+        let target = create_match_target(&cli).unwrap();
+        assert!(target.matches("CoiMdefghijkmnopqrstuvwxyz123456789"));
+        assert!(target.description().contains("lookalikes"));
+
+        let plain = parse(["solana-vanity", "--prefix", "C0IN"]);
+        assert!(!lookalike_enabled(&plain.pattern));
+        assert!(create_match_target(&plain).is_err());
+    }
+
+    #[test]
+    fn test_cli_per_segment_case() {
+        let cli = parse([
+            "solana-vanity",
+            "--prefix",
+            "moon",
+            "--suffix",
+            "pump",
+            "--ignore-case",
+            "--suffix-case",
+            "sensitive",
+        ]);
+        let target = create_match_target(&cli).unwrap();
+        assert!(target.matches("MooN23456789abcdefghijkmnopqrstuvpump"));
+        assert!(!target.matches("MooN23456789abcdefghijkmnopqrstuvPump"));
+
+        let masked = parse(["solana-vanity", "--prefix", "AB", "--prefix-case", "si"]);
+        assert_eq!(masked.pattern.prefix_case, Some(CaseRule::PerChar(vec![false, true])));
+        assert!(try_parse(["solana-vanity", "--prefix", "AB", "--prefix-case", "x"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_exclusions() {
+        let cli = parse([
+            "solana-vanity",
+            "--prefix",
+            "A",
+            "--exclude",
+            "1111",
+            "--exclude-at",
+            "xx@3",
+            "--max-run",
+            "3",
+        ]);
+        let rules = create_exclusions(&cli.pattern).unwrap();
+        assert_eq!(rules.rule_count(), 3);
+        assert!(rules.check("A1111bcdefghijkmnopqrstuvwxyz2345678").is_some());
+
+        let bad = parse(["solana-vanity", "--prefix", "A", "--exclude-at", "xx"]);
+        assert!(create_exclusions(&bad.pattern).is_err());
+
+        // A huge position is accepted and never matches
+        let far = parse(["solana-vanity", "--prefix", "A", "--exclude-at", "x@18446744073709551615"]);
+        assert!(create_exclusions(&far.pattern).unwrap().check("Axbcdefghijkmnopqrstuvwxyz").is_none());
+    }
+
+    #[test]
+    fn test_cli_pattern_classes() {
+        let cli = parse(["solana-vanity", "--repeat-prefix", "5", "--digit-run", "4"]);
+        assert_eq!(
+            pattern_classes(&cli.pattern),
+            vec![PatternClass::RepeatPrefix(5), PatternClass::DigitRun(4)]
+        );
+        let target = create_match_target(&cli).unwrap();
+        assert!(target.description().contains("5 identical leading characters"));
+
+        let too_short = parse(["solana-vanity", "--repeat-prefix", "1"]);
+        assert!(create_match_target(&too_short).is_err());
+
+        // Structural targets replace fixed patterns
+        assert!(try_parse(["solana-vanity", "--prefix", "A", "--repeat-prefix", "3"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_raw_key_target() {
+        let cli = try_parse([
+            "solana-vanity",
+            "--key-prefix",
+            "00",
+            "--key-shard",
+            "12:5",
+            "--key-below",
+            "0x0100000000000000000000000000000000000000000000000000000000000000",
+        ])
+        .unwrap();
+        // "00" fixes the first 8 bits, the 12-bit shard 5 fixes 4 more, and
+        // every such key sorts below the bound
+        let target = create_match_target(&cli).unwrap();
+        assert_eq!(target.probability(), 2f64.powi(-12));
+
+        let cli = try_parse(["solana-vanity", "--key-prefix", "ff", "--key-shard", "4:0"]).unwrap();
+        assert!(create_match_target(&cli).is_err());
+
+        assert!(try_parse(["solana-vanity", "--key-prefix", "00", "--prefix", "A"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_derived_constraints() {
+        let cli = try_parse([
+            "solana-vanity",
+            "--prefix",
+            "A",
+            "--derived",
+            "programdata=...z",
+            "--derived",
+            "ata-owner:11111111111111111111111111111111=Pay...",
+        ])
+        .unwrap();
+        let derived = create_derived(&cli.derived, cli.pattern.ignore_case).unwrap();
+        assert_eq!(derived.len(), 2);
+        assert_eq!(derived[0].label(), "programdata");
+
+        let bad = try_parse(["solana-vanity", "--prefix", "A", "--derived", "programdata"]).unwrap();
+        assert!(create_derived(&bad.derived, false).is_err());
+        let twice = try_parse([
+            "solana-vanity", "--prefix", "A", "--derived", "programdata=AB", "--derived", "programdata=...z",
+        ])
+        .unwrap();
+        assert!(create_derived(&twice.derived, false).is_err());
+
+        // Derived constraints apply to keypair searches only
+        assert!(try_parse([
+            "solana-vanity", "--prefix", "A", "--derived", "programdata=z", "--best-effort"
+        ])
+        .is_err());
+    }
+}
@@ -0,0 +1,147 @@
+//! PDA mode: searching nonce seeds for a vanity program-derived address.
+
+use crate::cli::{cancel_on_interrupt, format_duration, format_number, CliError, Result};
+use crate::cli::generate::GenerateArgs;
+use solana_sdk::pubkey::Pubkey;
+use solana_vanity::generator::{ParallelSearch, VanityGeneratorConfig};
+use solana_vanity::matcher::MatchTarget;
+use solana_vanity::pda::{parse_seed, write_pda_record, PdaDerivation, PdaSearch};
+use std::path::PathBuf;
+use std::str::FromStr;
+
+pub fn search_pda(cli: &GenerateArgs, program: &str, target: MatchTarget) -> Result<()> {
+    let program_id = Pubkey::from_str(program)
+        .map_err(|_| CliError::InvalidArgument(format!("Invalid program id '{}'", program)))?;
+    let seeds = cli
+        .pda_seed
+        .iter()
+        .map(|spec| parse_seed(spec))
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .map_err(CliError::Config)?;
+    let derivation = if cli.pda_find {
+        PdaDerivation::FindProgramAddress
+    } else {
+        PdaDerivation::CreateProgramAddress
+    };
+    let threads = cli.threads.unwrap_or_else(num_cpus::get);
+
+    // A PDA has no keypair to verify
+    let config = VanityGeneratorConfig {
+        threads,
+        verify_keypairs: false,
+        progress_interval: 50_000,
+    };
+    let search = PdaSearch::new(program_id, seeds, target.clone(), derivation, config)
+        .map_err(CliError::Config)?
+        .with_encoding(cli.nonce_encoding);
+
+    if !cli.quiet() {
+        println!();
+        println!("Solana Vanity Address Generator (PDA mode)");
+        println!("==========================================");
+        println!("Pattern:  {}", target.description());
+        println!("Program:  {}", program_id);
+        println!("Seeds:    {} fixed + nonce ({})", cli.pda_seed.len(), cli.nonce_encoding);
+        println!("Derive:   {}", derivation);
+        println!("Threads:  {threads}");
+        println!();
+    }
+
+    if cli.dry_run {
+        println!(
+            "Expected Attempts: {}",
+            format_number(search.expected_attempts() as u64)
+        );
+        return Ok(());
+    }
+
+    cancel_on_interrupt(search.cancel_handle(), "stopping PDA search");
+
+    let found = match search.run(cli.nonce_start) {
+        Ok(found) => found,
+        Err(e @ solana_vanity::GeneratorError::Cancelled) => {
+            eprintln!("\nSearch cancelled after {} nonces.", format_number(search.attempts()));
+            return Err(e.into());
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    let output_path = cli
+        .output
+        .clone()
+        .unwrap_or_else(|| PathBuf::from(format!("pda-{}.json", found.address)));
+    if output_path.exists() && !cli.overwrite {
+        return Err(CliError::OutputExists(output_path));
+    }
+    write_pda_record(&found, &output_path)?;
+
+    if cli.quiet() {
+        println!("{}", found.address);
+    } else {
+        println!("Address:  {}", found.address);
+        println!("Nonce:    {}", found.nonce);
+        if let Some(bump) = found.bump {
+            println!("Bump:     {}", bump);
+        }
+        println!(
+            "Searched: {} nonces in {}",
+            format_number(found.attempts),
+            format_duration(found.time_ms as f64 / 1000.0)
+        );
+        println!("Saved to: {}", output_path.display());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::generate::search;
+    use crate::cli::report::Reporter;
+    use crate::cli::test_support::{parse, try_parse};
+    use solana_vanity::pda::NonceEncoding;
+
+    #[test]
+    fn test_cli_pda_mode() {
+        let cli = try_parse([
+            "solana-vanity",
+            "--prefix",
+            "AB",
+            "--pda-program",
+            "11111111111111111111111111111111",
+            "--pda-seed",
+            "vault",
+            "--pda-seed",
+            "hex:01",
+            "--pda-find",
+            "--nonce-encoding",
+            "str",
+        ])
+        .unwrap();
+        assert_eq!(cli.pda_seed, vec!["vault", "hex:01"]);
+        assert!(cli.pda_find);
+        assert_eq!(cli.nonce_encoding, NonceEncoding::Decimal);
+
+        // PDA options only make sense with a program
+        assert!(try_parse(["solana-vanity", "--prefix", "A", "--pda-find"]).is_err());
+    }
+
+    #[test]
+    fn test_pda_dry_run() {
+        let dir = tempfile::tempdir().unwrap();
+        let output = dir.path().join("pda.json");
+        let dry_run = |program: &str| {
+            let cli = parse([
+                "solana-vanity", "--prefix", "AB", "--pda-program", program, "--pda-seed", "vault", "--dry-run",
+                "-o", output.to_str().unwrap(),
+            ]);
+            search(&cli, &Reporter::new(&cli))
+        };
+
+        // The PDA estimate runs, so the program id is checked and nothing is written
+        assert!(dry_run("11111111111111111111111111111111").is_ok());
+        assert!(!output.exists());
+        assert!(matches!(dry_run("not-a-program"), Err(CliError::InvalidArgument(_))));
+    }
+}
@@ -0,0 +1,122 @@
+//! Inputs for the pump.fun create transaction built in `--pump` mode.
+
+use crate::cli::generate::GenerateArgs;
+use crate::cli::{CliError, Result};
+use solana_sdk::hash::Hash;
+use solana_sdk::pubkey::Pubkey;
+use solana_vanity::pump::{CreateTokenArgs, TxLifetime};
+use std::str::FromStr;
+
+/// Creator, metadata and lifetime for the pump.fun create transaction, if requested
+pub fn create_tx_inputs(cli: &GenerateArgs) -> Result<Option<(Pubkey, CreateTokenArgs, TxLifetime)>> {
+    let Some(ref creator) = cli.creator else {
+        return Ok(None);
+    };
+    let creator = Pubkey::from_str(creator)
+        .map_err(|_| CliError::InvalidArgument(format!("Invalid creator pubkey '{}'", creator)))?;
+    let hash = |flag: &str, text: &str| {
+        Hash::from_str(text)
+            .map_err(|_| CliError::InvalidArgument(format!("Invalid {} '{}'", flag, text)))
+    };
+    let lifetime = match (&cli.blockhash, &cli.nonce_account, &cli.nonce_hash) {
+        (Some(blockhash), None, None) => TxLifetime::Blockhash(hash("blockhash", blockhash)?),
+        (None, Some(account), Some(nonce)) => TxLifetime::DurableNonce {
+            account: Pubkey::from_str(account).map_err(|_| {
+                CliError::InvalidArgument(format!("Invalid nonce account '{}'", account))
+            })?,
+            authority: creator,
+            nonce: hash("nonce hash", nonce)?,
+        },
+        _ => {
+            return Err(CliError::InvalidArgument(
+                "--creator needs --blockhash or --nonce-account with --nonce-hash".to_string(),
+            ))
+        }
+    };
+    // clap guarantees the metadata flags accompany --creator
+    let args = CreateTokenArgs::new(
+        cli.token_name.as_deref().unwrap_or_default(),
+        cli.token_symbol.as_deref().unwrap_or_default(),
+        cli.token_uri.as_deref().unwrap_or_default(),
+    )
+    .map_err(CliError::Config)?
+    .with_mayhem_mode(cli.mayhem_mode)
+    .with_cashback(cli.cashback);
+    Ok(Some((creator, args, lifetime)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::generate::{create_match_target, save_address_to};
+    use crate::cli::report::Reporter;
+    use crate::cli::test_support::{parse, try_parse};
+    use solana_vanity::pump::descriptor_path;
+
+    #[test]
+    fn test_cli_pump_mode() {
+        let cli = try_parse(["solana-vanity", "--pump", "--prefix", "dog", "--ignore-case"]).unwrap();
+        let target = create_match_target(&cli).unwrap();
+        assert!(target.matches("DoGxxxpump"));
+        assert!(!target.matches("DoGxxxPUMP"));
+
+        let cli = try_parse(["solana-vanity", "--pump"]).unwrap();
+        assert!(create_match_target(&cli).unwrap().matches("xxxpump"));
+
+        // The suffix is fixed in pump mode
+        assert!(try_parse(["solana-vanity", "--pump", "--suffix", "moon"]).is_err());
+    }
+
+    #[test]
+    fn test_pump_descriptor_not_overwritten() {
+        let dir = tempfile::tempdir().unwrap();
+        let keypair_path = dir.path().join("mint.json");
+        let descriptor = descriptor_path(&keypair_path);
+        std::fs::write(&descriptor, "keep").unwrap();
+        let address = solana_vanity::GeneratedAddress::new(solana_sdk::signature::Keypair::new(), 1, 0);
+
+        // An existing descriptor stops the save before the keypair is written
+        let cli = parse(["solana-vanity", "--pump", "-o", keypair_path.to_str().unwrap()]);
+        let saved = save_address_to(&cli, &Reporter::new(&cli), &address, "pump", keypair_path.clone());
+        assert!(matches!(saved, Err(CliError::OutputExists(ref path)) if *path == descriptor));
+        assert!(!keypair_path.exists());
+        assert_eq!(std::fs::read_to_string(&descriptor).unwrap(), "keep");
+
+        let cli = parse(["solana-vanity", "--pump", "-o", keypair_path.to_str().unwrap(), "--overwrite", "-q"]);
+        save_address_to(&cli, &Reporter::new(&cli), &address, "pump", keypair_path.clone()).unwrap();
+        assert_ne!(std::fs::read_to_string(&descriptor).unwrap(), "keep");
+    }
+
+    #[test]
+    fn test_cli_create_transaction_inputs() {
+        let wallet = "11111111111111111111111111111111";
+        let hash = Hash::new_unique().to_string();
+        let base = ["solana-vanity", "--pump", "--creator", wallet, "--token-name", "Meme",
+                    "--token-symbol", "MEME", "--token-uri", "https://example.com/m.json"];
+
+        let cli = try_parse(base.iter().copied().chain(["--blockhash", hash.as_str()])).unwrap();
+        let (creator, _, lifetime) = create_tx_inputs(&cli).unwrap().unwrap();
+        assert_eq!(creator.to_string(), wallet);
+        assert!(matches!(lifetime, TxLifetime::Blockhash(_)));
+
+        let cli = try_parse(
+            base.iter().copied().chain(["--nonce-account", wallet, "--nonce-hash", hash.as_str()]),
+        )
+        .unwrap();
+        let (_, _, lifetime) = create_tx_inputs(&cli).unwrap().unwrap();
+        assert!(matches!(lifetime, TxLifetime::DurableNonce { .. }));
+
+        let cli = try_parse(base.iter().copied().chain(["--blockhash", hash.as_str(), "--cashback"])).unwrap();
+        let (_, args, _) = create_tx_inputs(&cli).unwrap().unwrap();
+        let expected = CreateTokenArgs::new("Meme", "MEME", "https://example.com/m.json")
+            .unwrap()
+            .with_cashback(true);
+        assert_eq!(args, expected);
+        assert!(try_parse(["solana-vanity", "--pump", "--cashback"]).is_err());
+
+        // A lifetime is required, and metadata must accompany --creator
+        let cli = try_parse(base).unwrap();
+        assert!(create_tx_inputs(&cli).is_err());
+        assert!(try_parse(["solana-vanity", "--pump", "--creator", wallet]).is_err());
+    }
+}
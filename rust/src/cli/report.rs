@@ -0,0 +1,157 @@
+//! Structured reporting for `generate`: JSON results and the event stream.
+
+use crate::cli::{format_number, CliError, Result};
+use crate::cli::generate::GenerateArgs;
+use solana_vanity::events::{AddressResult, Event, EventFormat, EventWriter, FinishStatus, ResultFormat};
+use solana_vanity::exit::ExitCode;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+/// Structured reporting for a generate run: JSON results and the event stream
+pub struct Reporter {
+    format: ResultFormat,
+    events: Option<EventWriter>,
+    start: Instant,
+    found: AtomicU64,
+}
+
+impl Reporter {
+    pub fn new(cli: &GenerateArgs) -> Self {
+        Self {
+            format: cli.format,
+            events: cli.events.map(|EventFormat::Ndjson| EventWriter::stderr()),
+            start: Instant::now(),
+            found: AtomicU64::new(0),
+        }
+    }
+
+    /// Reporter for commands without structured output: progress lines only
+    pub fn plain() -> Self {
+        Self {
+            format: ResultFormat::Text,
+            events: None,
+            start: Instant::now(),
+            found: AtomicU64::new(0),
+        }
+    }
+
+    /// Event writer for progress callbacks, if events were requested
+    pub fn progress_events(&self) -> Option<EventWriter> {
+        self.events.clone()
+    }
+
+    /// Run `search`, reporting `attempts` about once a second: as progress
+    /// events if requested, otherwise as a progress line unless `quiet`
+    pub fn track<T>(&self, quiet: bool, attempts: impl Fn() -> u64 + Sync, search: impl FnOnce() -> T) -> T {
+        if quiet && self.events.is_none() {
+            return search();
+        }
+        let done = AtomicBool::new(false);
+        std::thread::scope(|scope| {
+            scope.spawn(|| {
+                let start = Instant::now();
+                let mut last_print = Instant::now();
+                let mut printed = false;
+                while !done.load(Ordering::Relaxed) {
+                    std::thread::sleep(Duration::from_millis(50));
+                    if last_print.elapsed().as_secs() < 1 {
+                        continue;
+                    }
+                    let attempts = attempts();
+                    let elapsed = start.elapsed().as_secs_f64();
+                    let rate = attempts as f64 / elapsed;
+                    if let Some(ref events) = self.events {
+                        events.emit(&Event::Progress {
+                            attempts,
+                            elapsed_ms: (elapsed * 1000.0) as u64,
+                            keys_per_sec: rate.round(),
+                        });
+                    } else {
+                        eprint!("\rSearching... {} attempts ({:.0} keys/sec)    ", format_number(attempts), rate);
+                        printed = true;
+                    }
+                    last_print = Instant::now();
+                }
+                if printed {
+                    eprint!("\r{:60}\r", ""); // Clear the line
+                }
+            });
+            let result = search();
+            done.store(true, Ordering::Relaxed);
+            result
+        })
+    }
+
+    /// Report a written address: a JSON line on stdout and a match event
+    pub fn found(&self, result: AddressResult) {
+        self.found.fetch_add(1, Ordering::Relaxed);
+        if let Some(ref events) = self.events {
+            events.emit(&Event::Match {
+                pubkey: result.pubkey.clone(),
+                path: result.path.clone(),
+                attempts: result.attempts,
+                time_ms: result.time_ms,
+            });
+        }
+        if self.format == ResultFormat::Json {
+            println!("{}", result.to_json());
+        }
+    }
+
+    /// Close the event stream with the run's outcome
+    pub fn finish(&self, result: &Result<()>) {
+        if let Some(ref events) = self.events {
+            let exit_code = result.as_ref().err().map_or(ExitCode::Success, CliError::exit_code);
+            events.emit(&Event::Finish {
+                status: match exit_code {
+                    ExitCode::Success => FinishStatus::Completed,
+                    ExitCode::Cancelled => FinishStatus::Cancelled,
+                    _ => FinishStatus::Failed,
+                },
+                exit_code: exit_code.code(),
+                found: self.found.load(Ordering::Relaxed),
+                elapsed_ms: self.start.elapsed().as_millis() as u64,
+                error: result.as_ref().err().map(ToString::to_string),
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::generate::save_address_to;
+    use crate::cli::test_support::{parse, try_parse, Buffer};
+
+    #[test]
+    fn test_cli_structured_output() {
+        let cli = parse(["solana-vanity", "--prefix", "A", "--format", "json", "--events", "ndjson"]);
+        assert_eq!(cli.format, ResultFormat::Json);
+        assert_eq!(cli.events, Some(EventFormat::Ndjson));
+        assert!(cli.quiet());
+        assert!(!parse(["solana-vanity", "--prefix", "A"]).quiet());
+        assert!(try_parse(["solana-vanity", "--prefix", "A", "--format", "yaml"]).is_err());
+        assert!(try_parse(["solana-vanity", "--prefix", "A", "--events", "json"]).is_err());
+        assert!(try_parse(["solana-vanity", "--prefix", "A", "--dry-run", "--format", "json"]).is_err());
+
+        // A saved address produces a match event, and the run ends with a finish event
+        let buffer = Buffer::default();
+        let reporter = Reporter {
+            format: ResultFormat::Json,
+            events: Some(EventWriter::new(buffer.clone())),
+            start: Instant::now(),
+            found: AtomicU64::new(0),
+        };
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("key.json");
+        let address = solana_vanity::GeneratedAddress::new(solana_sdk::signature::Keypair::new(), 3, 1);
+        save_address_to(&cli, &reporter, &address, "prefix 'A'", path.clone()).unwrap();
+        reporter.finish(&Ok(()));
+
+        let text = buffer.text();
+        let events: Vec<_> = text.lines().map(|line| Event::from_json(line).unwrap().1).collect();
+        assert_eq!(events.len(), 2);
+        assert!(matches!(events[0], Event::Match { ref pubkey, .. } if *pubkey == address.public_key()));
+        assert!(matches!(events[1], Event::Finish { status: FinishStatus::Completed, found: 1, .. }));
+    }
+}
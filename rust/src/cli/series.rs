@@ -0,0 +1,167 @@
+//! Series mode: one address per numbered or named slot.
+
+use crate::cli::{cancel_on_interrupt, format_number, invalid, search_outcome, CliError, Result};
+use crate::cli::generate::{save_address_to, GenerateArgs};
+use crate::cli::report::Reporter;
+use solana_vanity::exclusion::ExclusionRules;
+use solana_vanity::generator::{ParallelSearch, VanityGeneratorConfig};
+use solana_vanity::series::{write_manifest, ManifestEntry, Series, SeriesManifest, SeriesSearch};
+use std::path::Path;
+
+/// Parse a series range such as `1-9` (or a single number)
+fn parse_series_range(spec: &str) -> Result<std::ops::RangeInclusive<u64>> {
+    let invalid = || {
+        CliError::InvalidArgument(format!("Invalid --series '{}'. Expected START-END, e.g. 1-9", spec))
+    };
+    let (start, end) = spec.split_once('-').unwrap_or((spec, spec));
+    let start: u64 = start.trim().parse().map_err(|_| invalid())?;
+    let end: u64 = end.trim().parse().map_err(|_| invalid())?;
+    if start > end {
+        return Err(invalid());
+    }
+    Ok(start..=end)
+}
+
+pub fn search_series(cli: &GenerateArgs, reporter: &Reporter, exclusions: &ExclusionRules) -> Result<()> {
+    let series = match (&cli.series, &cli.series_words) {
+        (Some(range), _) => Series::numbered(
+            cli.pattern.prefix.as_deref(),
+            cli.pattern.suffix.as_deref(),
+            parse_series_range(range)?,
+            cli.pattern.ignore_case,
+        ),
+        (None, Some(path)) => {
+            let content = std::fs::read_to_string(path).map_err(|source| CliError::Read {
+                what: "series words",
+                path: path.clone(),
+                source,
+            })?;
+            Series::from_words(
+                cli.pattern.prefix.as_deref(),
+                cli.pattern.suffix.as_deref(),
+                content.lines(),
+                cli.pattern.ignore_case,
+            )
+        }
+        (None, None) => unreachable!(), // Only called for series runs
+    }
+    .map_err(invalid("pattern"))?;
+
+    let threads = cli.threads.unwrap_or_else(num_cpus::get);
+    let directory = cli.manifest.parent().unwrap_or(Path::new("")).to_path_buf();
+
+    if !cli.quiet() {
+        println!();
+        println!("Solana Vanity Address Generator (series mode)");
+        println!("=============================================");
+        println!("Pattern:  {}", series.description());
+        if !exclusions.is_empty() {
+            println!("Exclude:  {}", exclusions.description());
+        }
+        println!("Threads:  {threads}");
+        println!("Manifest: {}", cli.manifest.display());
+        println!();
+    }
+
+    if cli.dry_run {
+        println!(
+            "Expected Attempts for all slots: {}",
+            format_number(series.expected_attempts() as u64)
+        );
+        return Ok(());
+    }
+
+    if cli.manifest.exists() && !cli.overwrite {
+        return Err(CliError::OutputExists(cli.manifest.clone()));
+    }
+
+    let config = VanityGeneratorConfig {
+        threads,
+        verify_keypairs: true,
+        progress_interval: 50_000,
+    };
+    let search = SeriesSearch::new(series, config)?.with_exclusions(exclusions.clone());
+
+    let interrupted = cancel_on_interrupt(search.cancel_handle(), "stopping series search");
+
+    let found = reporter.track(cli.quiet(), || search.attempts(), || search.run())?;
+
+    if !cli.quiet() {
+        println!(
+            "Searched {} addresses, filled {}/{} slot(s)",
+            format_number(search.attempts()),
+            found.len(),
+            search.series().len()
+        );
+        println!();
+    }
+
+    let mut entries = Vec::with_capacity(found.len());
+    for m in &found {
+        let pubkey = m.address.public_key();
+        let path = directory.join(format!("{}-{}.json", m.slot, pubkey));
+        if !cli.quiet() {
+            println!("Slot {}", m.slot);
+        }
+        let pattern = search
+            .series()
+            .slots()
+            .iter()
+            .find(|slot| slot.name == m.slot)
+            .map_or_else(|| m.slot.clone(), |slot| slot.target.description());
+        let path = save_address_to(cli, reporter, &m.address, &pattern, path)?;
+        entries.push(ManifestEntry {
+            slot: m.slot.clone(),
+            pubkey,
+            file: path.file_name().unwrap_or_default().to_string_lossy().into_owned(),
+        });
+    }
+
+    let missing = search
+        .series()
+        .slots()
+        .iter()
+        .filter(|slot| !entries.iter().any(|e| e.slot == slot.name))
+        .map(|slot| slot.name.clone())
+        .collect::<Vec<_>>();
+    let manifest = SeriesManifest {
+        template: search.series().template().to_string(),
+        slots: entries,
+        missing,
+    };
+    write_manifest(&manifest, &cli.manifest)?;
+
+    if !cli.quiet() {
+        if !manifest.missing.is_empty() {
+            println!("Unfilled slots: {}", manifest.missing.join(", "));
+        }
+        println!("Manifest saved to: {}", cli.manifest.display());
+    }
+
+    search_outcome(&interrupted, false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::test_support::try_parse;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_cli_series() {
+        let cli = try_parse(["solana-vanity", "--prefix", "TEAM{n}", "--series", "1-9"]).unwrap();
+        assert_eq!(cli.series.as_deref(), Some("1-9"));
+        assert_eq!(cli.manifest, PathBuf::from("series-manifest.json"));
+
+        assert_eq!(parse_series_range("1-9").unwrap(), 1..=9);
+        assert_eq!(parse_series_range("4").unwrap(), 4..=4);
+        assert!(parse_series_range("9-1").is_err());
+        assert!(parse_series_range("a-b").is_err());
+
+        // Every slot gets its own file, so a single --output makes no sense
+        assert!(try_parse([
+            "solana-vanity", "--prefix", "A{n}", "--series", "1-3", "--output", "k.json"
+        ])
+        .is_err());
+    }
+}
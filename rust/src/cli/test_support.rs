@@ -0,0 +1,16 @@
+//! Helpers shared by the CLI tests.
+
+use clap::Parser;
+
+use crate::cli::generate::GenerateArgs;
+use crate::Cli;
+pub use crate::buffer::Buffer;
+
+/// Parse generate options given without a subcommand
+pub fn parse<'a>(args: impl IntoIterator<Item = &'a str>) -> GenerateArgs {
+    Cli::parse_from(args).generate
+}
+
+pub fn try_parse<'a>(args: impl IntoIterator<Item = &'a str>) -> std::result::Result<GenerateArgs, clap::Error> {
+    Cli::try_parse_from(args).map(|cli| cli.generate)
+}
@@ -0,0 +1,93 @@
+//! The `verify` command.
+
+use crate::cli::pattern::{create_exclusions, pattern_target, require_pattern, validate_pattern, PatternArgs};
+use crate::cli::{CliError, Result};
+use clap::Args;
+use solana_vanity::output::{print_verification_report, verify_keypair_file};
+use std::path::PathBuf;
+
+/// Options for checking a keypair file
+#[derive(Args, Debug)]
+pub struct VerifyArgs {
+    /// Keypair file (Solana JSON or Base58)
+    #[arg(value_name = "FILE")]
+    file: PathBuf,
+
+    /// Fail unless the file holds the key for PUBKEY
+    #[arg(long, value_name = "PUBKEY")]
+    pubkey: Option<String>,
+
+    #[command(flatten)]
+    pattern: PatternArgs,
+}
+
+pub fn verify(args: &VerifyArgs) -> Result<()> {
+    let report = verify_keypair_file(&args.file)?;
+    print_verification_report(&report);
+
+    let mut failures = Vec::new();
+    if !report.signature_valid {
+        failures.push("the key fails a sign/verify round trip".to_string());
+    }
+    if let Some(ref expected) = args.pubkey {
+        if report.public_key != *expected {
+            failures.push(format!("public key is {}, not {}", report.public_key, expected));
+        }
+    }
+    if require_pattern(&args.pattern).is_ok() {
+        validate_pattern(&args.pattern)?;
+        let target = pattern_target(&args.pattern)?;
+        if !target.matches(&report.public_key) {
+            failures.push(format!("address does not match {}", target.description()));
+        }
+    }
+    if let Some(violation) = create_exclusions(&args.pattern)?.check(&report.public_key) {
+        failures.push(format!("address is excluded: {}", violation));
+    }
+
+    if !failures.is_empty() {
+        return Err(CliError::VerificationFailed(failures.join("; ")));
+    }
+    println!("   Result:         ✅ OK");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Cli, Command};
+    use clap::Parser;
+    use solana_sdk::signer::Signer;
+    use solana_vanity::output::{write_keypair_as, KeypairEncoding};
+
+    #[test]
+    fn test_verify_subcommand() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("key.json");
+        let keypair = solana_sdk::signature::Keypair::new();
+        write_keypair_as(&keypair, KeypairEncoding::SolanaJson, &path).unwrap();
+        let address = keypair.pubkey().to_string();
+        let file = path.to_str().unwrap();
+
+        let verify_with = |extra: &[&str]| {
+            let mut argv = vec!["solana-vanity", "verify", file];
+            argv.extend_from_slice(extra);
+            let Some(Command::Verify(args)) = Cli::try_parse_from(argv).unwrap().command else {
+                panic!("expected verify")
+            };
+            verify(&args)
+        };
+        assert!(verify_with(&[]).is_ok());
+        assert!(verify_with(&["--prefix", &address[..2], "--pubkey", &address]).is_ok());
+        assert!(matches!(
+            verify_with(&["--pubkey", "11111111111111111111111111111111"]),
+            Err(CliError::VerificationFailed(_))
+        ));
+        let wrong = if address.starts_with('Z') { "Y" } else { "Z" };
+        assert!(matches!(verify_with(&["--prefix", wrong]), Err(CliError::VerificationFailed(_))));
+        assert!(matches!(
+            verify_with(&["--exclude", &address[..3]]),
+            Err(CliError::VerificationFailed(_))
+        ));
+    }
+}
@@ -0,0 +1,147 @@
+//! Dictionary mode: matching addresses against a wordlist.
+
+use crate::cli::{cancel_on_interrupt, format_duration, format_number, search_outcome, CliError, Result};
+use crate::cli::generate::{save_address, GenerateArgs};
+use crate::cli::report::Reporter;
+use solana_vanity::exclusion::ExclusionRules;
+use solana_vanity::generator::{ParallelSearch, VanityGeneratorConfig};
+use solana_vanity::wordlist::{DictionarySearch, WordPosition, Wordlist, WordlistMatcher};
+use std::path::Path;
+use std::time::Duration;
+
+pub fn search_wordlist(
+    cli: &GenerateArgs,
+    reporter: &Reporter,
+    path: &Path,
+    exclusions: &ExclusionRules,
+) -> Result<()> {
+    if cli.top == 0 {
+        return Err(CliError::InvalidArgument("--top must be at least 1".to_string()));
+    }
+
+    let wordlist = Wordlist::load(path, cli.min_word_len)?;
+    let matcher = WordlistMatcher::new(&wordlist, cli.word_position, cli.pattern.ignore_case);
+    let threads = cli.threads.unwrap_or_else(num_cpus::get);
+    let stats = wordlist.stats();
+
+    if !cli.quiet() {
+        println!();
+        println!("Solana Vanity Address Generator (dictionary mode)");
+        println!("=================================================");
+        println!("Pattern:  {}", matcher.description());
+        println!(
+            "Words:    {} accepted, {} repaired, {} dropped (invalid), {} dropped (short)",
+            stats.accepted, stats.repaired, stats.dropped_invalid, stats.dropped_short
+        );
+        if !exclusions.is_empty() {
+            println!("Exclude:  {}", exclusions.description());
+        }
+        println!("Threads:  {threads}");
+        println!("Budget:   {}", format_duration(cli.budget as f64));
+        println!("Keep:     top {}", cli.top);
+        if cli.verbose {
+            for word in wordlist.words() {
+                if let Some(ref original) = word.original {
+                    println!("  repaired '{}' -> '{}'", original, word.text);
+                }
+            }
+        }
+        println!();
+    }
+
+    if cli.dry_run {
+        let probability = matcher.match_probability();
+        println!("Expected Attempts per Match: {}", format_number((1.0 / probability) as u64));
+        return Ok(());
+    }
+
+    let config = VanityGeneratorConfig {
+        threads,
+        verify_keypairs: true,
+        progress_interval: 50_000,
+    };
+    let pattern = matcher.description();
+    let search = DictionarySearch::new(matcher, config)?.with_exclusions(exclusions.clone());
+
+    let interrupted = cancel_on_interrupt(search.cancel_handle(), "stopping search");
+
+    let found = reporter.track(cli.quiet(), || search.attempts(), || {
+        search.run(Duration::from_secs(cli.budget), cli.top)
+    })?;
+
+    if !cli.quiet() {
+        println!(
+            "Searched {} addresses, kept {} match(es)",
+            format_number(search.attempts()),
+            found.len()
+        );
+        if search.excluded() > 0 {
+            println!(
+                "Rejected {} candidate(s) by exclusion rules",
+                format_number(search.excluded())
+            );
+        }
+        println!();
+    }
+
+    let multiple = found.len() > 1;
+    for (i, m) in found.iter().enumerate() {
+        if !cli.quiet() {
+            let position = match m.word.position {
+                WordPosition::Suffix => "suffix",
+                _ => "prefix",
+            };
+            println!("#{} '{}' ({position}, score {})", i + 1, m.word.word, m.word.score);
+        }
+        save_address(cli, reporter, &m.address, &pattern, multiple.then_some(i + 1))?;
+    }
+
+    search_outcome(&interrupted, found.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::generate::search;
+    use crate::cli::test_support::{parse, try_parse};
+    use solana_vanity::wordlist::DEFAULT_MIN_WORD_LENGTH;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_cli_wordlist_mode() {
+        let cli = parse([
+            "solana-vanity",
+            "--wordlist",
+            "words.txt",
+            "--word-position",
+            "suffix",
+            "--top",
+            "3",
+        ]);
+        assert_eq!(cli.wordlist, Some(PathBuf::from("words.txt")));
+        assert_eq!(cli.word_position, WordPosition::Suffix);
+        assert_eq!(cli.min_word_len, DEFAULT_MIN_WORD_LENGTH);
+        assert_eq!(cli.top, 3);
+
+        // A wordlist replaces the fixed prefix/suffix target
+        assert!(try_parse(["solana-vanity", "--wordlist", "w.txt", "--prefix", "A"]).is_err());
+
+        // Exclusion rules are checked in dictionary mode too
+        let dir = tempfile::tempdir().unwrap();
+        let words = dir.path().join("words.txt");
+        std::fs::write(&words, "moon\n").unwrap();
+        let dry_run = |exclude_at: &str| {
+            let cli = parse([
+                "solana-vanity", "--wordlist", words.to_str().unwrap(), "--exclude-at", exclude_at, "--dry-run",
+            ]);
+            search(&cli, &Reporter::new(&cli))
+        };
+        assert!(dry_run("xx@3").is_ok());
+        assert!(matches!(dry_run("xx"), Err(CliError::InvalidArgument(_))));
+
+        // --budget and --top only apply to wordlist and best-effort searches
+        assert!(try_parse(["solana-vanity", "--prefix", "A", "--budget", "10"]).is_err());
+        assert!(try_parse(["solana-vanity", "--prefix", "A", "--top", "3"]).is_err());
+        assert!(try_parse(["solana-vanity", "--prefix", "A", "--best-effort", "--budget", "10", "--top", "3"]).is_ok());
+    }
+}
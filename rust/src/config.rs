@@ -8,6 +8,9 @@ use thiserror::Error;
 /// Base58 alphabet used by Solana (excludes 0, O, I, l)
 pub const BASE58_ALPHABET: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
 
+/// Typical length of a Base58-encoded Solana address, used for estimates
+pub const TYPICAL_ADDRESS_LENGTH: usize = 44;
+
 /// Maximum reasonable prefix length (longer would take astronomical time)
 pub const MAX_PREFIX_LENGTH: usize = 8;
 
@@ -32,17 +35,95 @@ pub enum ConfigError {
     #[error("Suffix length {0} exceeds maximum of {MAX_SUFFIX_LENGTH}. Longer suffixes would take impractical time.")]
     SuffixTooLong(usize),
 
+    #[error("Pattern can never match: {0}")]
+    Unsatisfiable(String),
+
     #[error("Thread count must be at least 1, got {0}")]
     InvalidThreadCount(usize),
 
     #[error("Count must be at least 1, got {0}")]
     InvalidCount(usize),
 
+    #[error("Count {count} exceeds the maximum of {max}")]
+    CountTooLarge { count: u64, max: u64 },
+
     #[error("No pattern specified. Use --prefix and/or --suffix.")]
     NoPatternSpecified,
 
     #[error("Output path is not writable: {0}")]
     OutputNotWritable(String),
+
+    #[error("Invalid output template {0}")]
+    InvalidOutputTemplate(String),
+
+    #[error("Output template gives two files the same path: {0}. Add {{index}} or {{pubkey}}.")]
+    OutputCollision(String),
+
+    #[error("Invalid lookalike mapping: {0}")]
+    InvalidConfusable(String),
+
+    #[error("Invalid pattern class: {0}")]
+    InvalidPatternClass(String),
+
+    #[error("Invalid raw key constraint: {0}")]
+    InvalidRawConstraint(String),
+
+    #[error("Invalid series template: {0}")]
+    InvalidTemplate(String),
+
+    #[error("Invalid seed: {0}")]
+    InvalidSeed(String),
+
+    #[error("Invalid derived address constraint: {0}")]
+    InvalidDerived(String),
+
+    #[error("Invalid token metadata: {0}")]
+    InvalidTokenMetadata(String),
+
+    #[error("Invalid mnemonic: {0}")]
+    InvalidMnemonic(String),
+
+    #[error("Invalid RPC URL '{0}'. Use an http:// or https:// endpoint.")]
+    InvalidRpcUrl(String),
+
+    #[error("Invalid pre-flight policy '{0}'. Use 'reject' or 'flag'.")]
+    InvalidPreflightPolicy(String),
+
+    #[error("Invalid key format '{0}'. Use 'json', 'base58', 'hex' or 'raw'.")]
+    InvalidKeyFormat(String),
+
+    #[error("Invalid export format '{0}'. Use 'pkcs8-pem', 'pkcs8-der', 'openssh', 'openssh-pub' or 'jwk'.")]
+    InvalidExportFormat(String),
+
+    #[error("Invalid grind spec: {0}")]
+    InvalidGrindSpec(String),
+
+    #[error("Invalid output format '{0}'. Use 'text' or 'json'.")]
+    InvalidResultFormat(String),
+
+    #[error("Invalid event format '{0}'. Use 'ndjson'.")]
+    InvalidEventFormat(String),
+
+    #[error("Invalid config file {0}")]
+    InvalidConfigFile(String),
+
+    #[error("Unknown profile '{0}'. Define it as [profile.{0}] in a config file.")]
+    UnknownProfile(String),
+
+    #[error("Invalid job file {0}")]
+    InvalidJobFile(String),
+
+    #[error("Invalid job '{job}': {source}")]
+    InvalidJob {
+        job: String,
+        source: Box<ConfigError>,
+    },
+
+    #[error("Invalid case rule '{0}'. Use 'sensitive', 'insensitive', or a mask of 's'/'i' per character.")]
+    InvalidCaseRule(String),
+
+    #[error("Case mask has {mask_len} entries but pattern '{pattern}' has {} characters", pattern.chars().count())]
+    CaseMaskLength { pattern: String, mask_len: usize },
 }
 
 /// Configuration for the vanity address generator
@@ -151,30 +232,15 @@ impl GeneratorConfig {
     ///
     /// For a prefix of length n, the probability of a random address matching is:
     /// - Case-sensitive: (1/58)^n
-    /// - Case-insensitive: approximately (1/34)^n (depends on pattern)
+    /// - Case-insensitive: (2/58) for each letter valid in both cases, (1/58) for the rest
+    /// - Mixed case rules and lookalikes: the product of (accepted characters / 58) over all positions
     ///
     /// Returns the expected number of attempts (50% probability of success)
     pub fn estimate_attempts(&self) -> u64 {
-        let (prefix_len, suffix_len, case_insensitive) = match &self.match_target {
-            MatchTarget::Prefix { pattern, case_insensitive } => {
-                (pattern.len(), 0, *case_insensitive)
-            }
-            MatchTarget::Suffix { pattern, case_insensitive } => {
-                (0, pattern.len(), *case_insensitive)
-            }
-            MatchTarget::Both { prefix, suffix, case_insensitive } => {
-                (prefix.len(), suffix.len(), *case_insensitive)
-            }
-        };
-
-        // Base probability for each position
-        let base: f64 = if case_insensitive { 34.0 } else { 58.0 };
-
-        // Total pattern length
-        let total_len = prefix_len + suffix_len;
-
-        // Expected attempts = base^total_len * ln(2) for 50% success probability
-        let expected = base.powi(total_len as i32) * 0.693;
+        let probability = self.match_target.probability();
+
+        // Expected attempts = ln(2) / p for 50% success probability
+        let expected = 0.693 / probability;
 
         expected as u64
     }
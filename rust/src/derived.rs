@@ -0,0 +1,361 @@
+//! Constraints on addresses derived from the generated public key.
+//!
+//! A mint keypair is rarely used alone: its associated token accounts and
+//! program PDAs show up in explorers too. A [`DerivedConstraint`] derives
+//! one such address from a candidate key and checks it against its own
+//! target. The generator only evaluates derived constraints for candidates
+//! that already match the main target, so they cost little per attempt.
+
+use crate::config::ConfigError;
+use crate::matcher::{MatchTarget, OptimizedMatcher};
+use crate::pda::parse_seed;
+use crate::raw::to_hex;
+use solana_sdk::bpf_loader_upgradeable;
+use solana_sdk::pubkey::{Pubkey, MAX_SEEDS};
+use std::fmt;
+use std::str::FromStr;
+
+/// SPL Token program id
+pub const TOKEN_PROGRAM_ID: Pubkey =
+    solana_sdk::pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+
+/// SPL Token-2022 program id
+pub const TOKEN_2022_PROGRAM_ID: Pubkey =
+    solana_sdk::pubkey!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+
+/// SPL Associated Token Account program id
+pub const ASSOCIATED_TOKEN_PROGRAM_ID: Pubkey =
+    solana_sdk::pubkey!("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
+
+/// Seed placeholder replaced by the generated public key
+pub const KEY_PLACEHOLDER: &str = "{key}";
+
+/// Derive the associated token account of `owner` for `mint`
+pub fn associated_token_address(owner: &Pubkey, mint: &Pubkey, token_program: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[owner.as_ref(), token_program.as_ref(), mint.as_ref()],
+        &ASSOCIATED_TOKEN_PROGRAM_ID,
+    )
+    .0
+}
+
+/// A seed of a derived PDA: fixed bytes or the generated key
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DerivedSeed {
+    /// Fixed seed bytes
+    Bytes(Vec<u8>),
+    /// The generated public key
+    Key,
+}
+
+impl fmt::Display for DerivedSeed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Key => write!(f, "{KEY_PLACEHOLDER}"),
+            Self::Bytes(bytes) => match std::str::from_utf8(bytes) {
+                Ok(text) if text.chars().all(|c| c.is_ascii_graphic()) => write!(f, "{text}"),
+                _ => write!(f, "hex:{}", to_hex(bytes)),
+            },
+        }
+    }
+}
+
+/// How an address is derived from the generated public key
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Derivation {
+    /// The associated token account of `owner`, with the generated key as mint
+    AtaOfMint { owner: Pubkey, token_program: Pubkey },
+    /// The associated token account for `mint`, with the generated key as owner
+    AtaForMint { mint: Pubkey, token_program: Pubkey },
+    /// A PDA under `program_id` whose seeds include the generated key
+    ProgramAddress { program_id: Pubkey, seeds: Vec<DerivedSeed> },
+    /// The programdata account, with the generated key as an upgradeable program id
+    ProgramData,
+}
+
+impl Derivation {
+    /// Derive the address for a generated public key
+    pub fn derive(&self, key: &Pubkey) -> Pubkey {
+        match self {
+            Self::AtaOfMint { owner, token_program } => {
+                associated_token_address(owner, key, token_program)
+            }
+            Self::AtaForMint { mint, token_program } => {
+                associated_token_address(key, mint, token_program)
+            }
+            Self::ProgramAddress { program_id, seeds } => {
+                let key_bytes = key.to_bytes();
+                let seeds: Vec<&[u8]> = seeds
+                    .iter()
+                    .map(|seed| match seed {
+                        DerivedSeed::Bytes(bytes) => bytes.as_slice(),
+                        DerivedSeed::Key => key_bytes.as_slice(),
+                    })
+                    .collect();
+                Pubkey::find_program_address(&seeds, program_id).0
+            }
+            Self::ProgramData => {
+                Pubkey::find_program_address(&[key.as_ref()], &bpf_loader_upgradeable::id()).0
+            }
+        }
+    }
+
+    /// Parse `ata-owner:OWNER[:TOKEN]`, `ata-mint:MINT[:TOKEN]`,
+    /// `pda:PROGRAM:SEED,SEED,...` (with `{key}` for the generated key) or `programdata`
+    ///
+    /// TOKEN is `token`, `token2022` or a program id (default: `token`).
+    pub fn parse(spec: &str) -> Result<Self, ConfigError> {
+        let invalid = |msg: String| ConfigError::InvalidDerived(format!("{msg} in '{spec}'"));
+        let pubkey = |text: &str| {
+            Pubkey::from_str(text).map_err(|_| invalid(format!("'{text}' is not a valid pubkey")))
+        };
+        let token_program = |text: Option<&str>| match text {
+            None | Some("token") => Ok(TOKEN_PROGRAM_ID),
+            Some("token2022") => Ok(TOKEN_2022_PROGRAM_ID),
+            Some(other) => pubkey(other),
+        };
+
+        let mut parts = spec.splitn(3, ':');
+        match (parts.next(), parts.next(), parts.next()) {
+            (Some("ata-owner"), Some(owner), token) => Ok(Self::AtaOfMint {
+                owner: pubkey(owner)?,
+                token_program: token_program(token)?,
+            }),
+            (Some("ata-mint"), Some(mint), token) => Ok(Self::AtaForMint {
+                mint: pubkey(mint)?,
+                token_program: token_program(token)?,
+            }),
+            (Some("pda"), Some(program), Some(seeds)) => {
+                let seeds = seeds
+                    .split(',')
+                    .map(|seed| match seed {
+                        KEY_PLACEHOLDER => Ok(DerivedSeed::Key),
+                        other => parse_seed(other).map(DerivedSeed::Bytes),
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                Self::program_address(pubkey(program)?, seeds)
+            }
+            (Some("pda"), Some(_), None) => {
+                Err(invalid("PDA needs seeds, e.g. pda:PROGRAM:seed,{key}".to_string()))
+            }
+            (Some("programdata"), None, None) => Ok(Self::ProgramData),
+            _ => Err(invalid(
+                "expected ata-owner:, ata-mint:, pda: or programdata".to_string(),
+            )),
+        }
+    }
+
+    /// Build a PDA derivation, checking that the seeds include the generated key
+    pub fn program_address(program_id: Pubkey, seeds: Vec<DerivedSeed>) -> Result<Self, ConfigError> {
+        if !seeds.contains(&DerivedSeed::Key) {
+            return Err(ConfigError::InvalidDerived(format!(
+                "PDA seeds must include {KEY_PLACEHOLDER}"
+            )));
+        }
+        // find_program_address appends the bump seed
+        if seeds.len() >= MAX_SEEDS {
+            return Err(ConfigError::InvalidDerived(format!(
+                "PDA has {} seeds, the maximum is {}",
+                seeds.len(),
+                MAX_SEEDS - 1
+            )));
+        }
+        Ok(Self::ProgramAddress { program_id, seeds })
+    }
+}
+
+impl fmt::Display for Derivation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Labels key the derived addresses in reports, so every field that
+        // changes the address shows up in the label
+        let token = |token_program: &Pubkey| match *token_program {
+            TOKEN_PROGRAM_ID => String::new(),
+            TOKEN_2022_PROGRAM_ID => " (token2022)".to_string(),
+            other => format!(" (token program {other})"),
+        };
+        match self {
+            Self::AtaOfMint { owner, token_program } => write!(f, "ATA of {owner}{}", token(token_program)),
+            Self::AtaForMint { mint, token_program } => write!(f, "ATA for mint {mint}{}", token(token_program)),
+            Self::ProgramAddress { program_id, seeds } => {
+                let seeds = seeds.iter().map(ToString::to_string).collect::<Vec<_>>();
+                write!(f, "PDA under {program_id} [{}]", seeds.join(","))
+            }
+            Self::ProgramData => write!(f, "programdata"),
+        }
+    }
+}
+
+/// A derived address that must match its own target
+#[derive(Debug, Clone)]
+pub struct DerivedConstraint {
+    label: String,
+    derivation: Derivation,
+    matcher: OptimizedMatcher,
+}
+
+impl DerivedConstraint {
+    /// Create a constraint; `label` names the derived address in reports
+    pub fn new(label: impl Into<String>, derivation: Derivation, target: MatchTarget) -> Self {
+        Self {
+            label: label.into(),
+            derivation,
+            matcher: OptimizedMatcher::new(target),
+        }
+    }
+
+    /// Parse `DERIVATION=PATTERN`, where PATTERN is `PREFIX...`, `...SUFFIX`
+    /// or `PREFIX...SUFFIX` (a bare pattern is a prefix)
+    pub fn parse(spec: &str, case_insensitive: bool) -> Result<Self, ConfigError> {
+        let (derivation, pattern) = spec.rsplit_once('=').ok_or_else(|| {
+            ConfigError::InvalidDerived(format!("expected DERIVATION=PATTERN, got '{spec}'"))
+        })?;
+        let derivation = Derivation::parse(derivation)?;
+
+        let target = match pattern.split_once("...") {
+            Some(("", suffix)) => MatchTarget::suffix(suffix, case_insensitive),
+            Some((prefix, "")) => MatchTarget::prefix(prefix, case_insensitive),
+            Some((prefix, suffix)) => MatchTarget::both(prefix, suffix, case_insensitive),
+            None => MatchTarget::prefix(pattern, case_insensitive),
+        }?;
+
+        Ok(Self::new(derivation.to_string(), derivation, target))
+    }
+
+    /// Label used for the derived address in reports
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// How the address is derived
+    pub fn derivation(&self) -> &Derivation {
+        &self.derivation
+    }
+
+    /// The target the derived address must match
+    pub fn target(&self) -> &MatchTarget {
+        self.matcher.target()
+    }
+
+    /// Derive the address for `key`, returning it only if it matches
+    pub fn check(&self, key: &Pubkey) -> Option<Pubkey> {
+        let derived = self.derivation.derive(key);
+        (self.matcher.matches_key(&derived.to_bytes()) && self.matcher.matches(&derived.to_string()))
+            .then_some(derived)
+    }
+
+    /// Human-readable description of the constraint
+    pub fn description(&self) -> String {
+        format!("{} with {}", self.label, self.target().description())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PUMP_PROGRAM: &str = "6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P";
+
+    #[test]
+    fn test_associated_token_address() {
+        let owner = Pubkey::from_str("7xKXtg2CW87d97TXJSDpbD5jBkheTqA83TZRuJosgAsU").unwrap();
+        let mint = Pubkey::from_str("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v").unwrap();
+        let ata = associated_token_address(&owner, &mint, &TOKEN_PROGRAM_ID);
+
+        let expected = Pubkey::find_program_address(
+            &[owner.as_ref(), TOKEN_PROGRAM_ID.as_ref(), mint.as_ref()],
+            &ASSOCIATED_TOKEN_PROGRAM_ID,
+        )
+        .0;
+        assert_eq!(ata, expected);
+        assert_ne!(
+            ata,
+            associated_token_address(&owner, &mint, &TOKEN_2022_PROGRAM_ID)
+        );
+    }
+
+    #[test]
+    fn test_parse_derivations() {
+        let owner = Pubkey::new_unique();
+        assert_eq!(
+            Derivation::parse(&format!("ata-owner:{owner}")).unwrap(),
+            Derivation::AtaOfMint { owner, token_program: TOKEN_PROGRAM_ID }
+        );
+        assert_eq!(
+            Derivation::parse(&format!("ata-mint:{owner}:token2022")).unwrap(),
+            Derivation::AtaForMint { mint: owner, token_program: TOKEN_2022_PROGRAM_ID }
+        );
+        assert_eq!(Derivation::parse("programdata").unwrap(), Derivation::ProgramData);
+
+        let pda = Derivation::parse(&format!("pda:{PUMP_PROGRAM}:bonding-curve,{{key}}")).unwrap();
+        assert!(matches!(
+            pda,
+            Derivation::ProgramAddress { ref seeds, .. }
+                if seeds == &[DerivedSeed::Bytes(b"bonding-curve".to_vec()), DerivedSeed::Key]
+        ));
+
+        assert!(Derivation::parse(&format!("pda:{PUMP_PROGRAM}:bonding-curve")).is_err());
+        assert!(Derivation::parse(&format!("pda:{PUMP_PROGRAM}")).is_err());
+        assert!(Derivation::parse("ata-owner:nope").is_err());
+        assert!(Derivation::parse("vault").is_err());
+    }
+
+    #[test]
+    fn test_derivation_labels_are_unique() {
+        let owner = Pubkey::new_unique();
+        let labels = [
+            format!("ata-owner:{owner}"),
+            format!("ata-owner:{owner}:token2022"),
+            format!("pda:{PUMP_PROGRAM}:bonding-curve,{{key}}"),
+            format!("pda:{PUMP_PROGRAM}:{{key}},hex:00ff"),
+        ]
+        .map(|spec| Derivation::parse(&spec).unwrap().to_string());
+
+        assert_eq!(labels[1], format!("ATA of {owner} (token2022)"));
+        assert_eq!(labels[2], format!("PDA under {PUMP_PROGRAM} [bonding-curve,{{key}}]"));
+        assert_eq!(labels[3], format!("PDA under {PUMP_PROGRAM} [{{key}},hex:00ff]"));
+        assert!(labels.iter().enumerate().all(|(i, a)| labels[i + 1..].iter().all(|b| a != b)));
+    }
+
+    #[test]
+    fn test_pda_derivation() {
+        let program = Pubkey::from_str(PUMP_PROGRAM).unwrap();
+        let key = Pubkey::new_unique();
+        let derivation = Derivation::parse(&format!("pda:{PUMP_PROGRAM}:bonding-curve,{{key}}")).unwrap();
+        let expected = Pubkey::find_program_address(&[b"bonding-curve", key.as_ref()], &program).0;
+        assert_eq!(derivation.derive(&key), expected);
+    }
+
+    #[test]
+    fn test_programdata_derivation() {
+        let key = Pubkey::new_unique();
+        let expected =
+            Pubkey::find_program_address(&[key.as_ref()], &bpf_loader_upgradeable::id()).0;
+        assert_eq!(Derivation::ProgramData.derive(&key), expected);
+    }
+
+    #[test]
+    fn test_constraint_parse_and_check() {
+        let constraint = DerivedConstraint::parse("programdata=...xyz", false).unwrap();
+        assert_eq!(constraint.label(), "programdata");
+        assert!(matches!(constraint.target(), MatchTarget::Suffix { .. }));
+
+        let prefix = DerivedConstraint::parse("programdata=AB", false).unwrap();
+        assert!(matches!(prefix.target(), MatchTarget::Prefix { .. }));
+        let both = DerivedConstraint::parse("programdata=AB...z", false).unwrap();
+        assert!(matches!(both.target(), MatchTarget::Both { .. }));
+
+        assert!(DerivedConstraint::parse("programdata", false).is_err());
+        assert!(DerivedConstraint::parse("programdata=0", false).is_err());
+
+        // An empty pattern is rejected
+        assert!(DerivedConstraint::parse("programdata=...", false).is_err());
+
+        // A one-character target matches a derived address often enough to test
+        let key = (0..)
+            .map(|_| Pubkey::new_unique())
+            .find(|k| Derivation::ProgramData.derive(k).to_string().ends_with('z'))
+            .unwrap();
+        let constraint = DerivedConstraint::parse("programdata=...z", false).unwrap();
+        assert_eq!(constraint.check(&key), Some(Derivation::ProgramData.derive(&key)));
+    }
+}
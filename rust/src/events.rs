@@ -0,0 +1,405 @@
+//! Machine-readable results and progress events.
+//!
+//! `--format json` prints one [`AddressResult`] per found address on stdout,
+//! and `--events ndjson` streams [`Event`]s on stderr, one JSON object per
+//! line. Both carry [`SCHEMA_VERSION`]; fields are only ever added within a
+//! version, so consumers should ignore fields they don't know. Neither ever
+//! contains secret key material.
+
+use crate::config::ConfigError;
+use crate::output::GeneratedAddress;
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+
+/// Version of the result and event schema
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// How found addresses are reported on stdout
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ResultFormat {
+    /// Human-readable lines
+    #[default]
+    Text,
+    /// One JSON object per address
+    Json,
+}
+
+impl FromStr for ResultFormat {
+    type Err = ConfigError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            _ => Err(ConfigError::InvalidResultFormat(s.to_string())),
+        }
+    }
+}
+
+/// Encoding of the event stream
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventFormat {
+    /// Newline-delimited JSON
+    Ndjson,
+}
+
+impl FromStr for EventFormat {
+    type Err = ConfigError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "ndjson" => Ok(Self::Ndjson),
+            _ => Err(ConfigError::InvalidEventFormat(s.to_string())),
+        }
+    }
+}
+
+/// Outcome of `--verify` for a written keypair file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VerificationStatus {
+    /// The file was not re-read
+    Skipped,
+    /// The file holds a valid keypair with secure permissions
+    Passed,
+    /// The file failed a signature, format or permission check
+    Failed,
+}
+
+/// A labelled note about a match
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MatchDetail {
+    pub label: String,
+    pub value: String,
+}
+
+/// A labelled address derived from the found key
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DerivedAddress {
+    pub label: String,
+    pub address: String,
+}
+
+/// Structured result for one found address (no secret key)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AddressResult {
+    pub schema_version: u32,
+    pub pubkey: String,
+    /// Keypair file the secret key was written to
+    pub path: String,
+    pub attempts: u64,
+    pub time_ms: u64,
+    /// Description of the pattern the address matched
+    pub pattern: String,
+    pub verification: VerificationStatus,
+    /// Labelled notes about the match, as shown in text output
+    #[serde(default)]
+    pub details: Vec<MatchDetail>,
+    #[serde(default)]
+    pub derived: Vec<DerivedAddress>,
+    /// Mint descriptor file written with `--pump`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pump_descriptor: Option<String>,
+    /// Base64 create transaction, signed by the mint only
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub create_transaction: Option<String>,
+}
+
+impl AddressResult {
+    /// Describe a found address written to `path`
+    pub fn new(address: &GeneratedAddress, pattern: impl Into<String>, path: &Path) -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            pubkey: address.public_key(),
+            path: path.display().to_string(),
+            attempts: address.attempts,
+            time_ms: address.time_ms,
+            pattern: pattern.into(),
+            verification: VerificationStatus::Skipped,
+            details: address
+                .details()
+                .iter()
+                .map(|(label, value)| MatchDetail {
+                    label: label.clone(),
+                    value: value.clone(),
+                })
+                .collect(),
+            derived: address
+                .derived()
+                .iter()
+                .map(|(label, address)| DerivedAddress {
+                    label: label.clone(),
+                    address: address.clone(),
+                })
+                .collect(),
+            pump_descriptor: None,
+            create_transaction: None,
+        }
+    }
+
+    /// Record the pump.fun descriptor file and the create transaction in it
+    pub fn with_pump(mut self, descriptor: &Path, create_transaction: Option<String>) -> Self {
+        self.pump_descriptor = Some(descriptor.display().to_string());
+        self.create_transaction = create_transaction;
+        self
+    }
+
+    /// Record the outcome of re-reading the keypair file
+    pub fn with_verification(mut self, verification: VerificationStatus) -> Self {
+        self.verification = verification;
+        self
+    }
+
+    /// Single-line JSON form
+    pub fn to_json(&self) -> String {
+        // Plain strings and numbers always serialize
+        serde_json::to_string(self).expect("result serializes")
+    }
+}
+
+/// How a run ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FinishStatus {
+    /// The search ran to completion (it may still have found nothing)
+    Completed,
+    /// The user interrupted the run
+    Cancelled,
+    /// The run stopped with an error
+    Failed,
+}
+
+/// A progress, match or finish event
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum Event {
+    /// Periodic search progress
+    Progress {
+        attempts: u64,
+        elapsed_ms: u64,
+        keys_per_sec: f64,
+    },
+    /// An address was found and written
+    Match {
+        pubkey: String,
+        path: String,
+        attempts: u64,
+        time_ms: u64,
+    },
+    /// The run ended; always the last event
+    Finish {
+        status: FinishStatus,
+        /// The process exit code the run ends with
+        exit_code: i32,
+        found: u64,
+        elapsed_ms: u64,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        error: Option<String>,
+    },
+}
+
+/// One line of the event stream
+#[derive(Serialize, Deserialize)]
+struct EventLine {
+    schema_version: u32,
+    #[serde(flatten)]
+    event: Event,
+}
+
+impl Event {
+    /// Single-line JSON form, tagged with the schema version
+    pub fn to_json(&self) -> String {
+        let line = EventLine {
+            schema_version: SCHEMA_VERSION,
+            event: self.clone(),
+        };
+        serde_json::to_string(&line).expect("event serializes")
+    }
+
+    /// Parse a line written by [`Event::to_json`], returning its schema version
+    pub fn from_json(line: &str) -> serde_json::Result<(u32, Self)> {
+        let line: EventLine = serde_json::from_str(line)?;
+        Ok((line.schema_version, line.event))
+    }
+}
+
+/// Shared writer for the event stream, usable from generator threads
+#[derive(Clone)]
+pub struct EventWriter {
+    out: Arc<Mutex<Box<dyn Write + Send>>>,
+}
+
+impl EventWriter {
+    /// Write events to `out`
+    pub fn new(out: impl Write + Send + 'static) -> Self {
+        Self {
+            out: Arc::new(Mutex::new(Box::new(out))),
+        }
+    }
+
+    /// Write events to stderr
+    pub fn stderr() -> Self {
+        Self::new(std::io::stderr())
+    }
+
+    /// Write one event as a line
+    ///
+    /// Events are best-effort: a closed or full stream never stops the search.
+    pub fn emit(&self, event: &Event) {
+        if let Ok(mut out) = self.out.lock() {
+            let _ = writeln!(out, "{}", event.to_json());
+            let _ = out.flush();
+        }
+    }
+}
+
+impl std::fmt::Debug for EventWriter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EventWriter").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::Buffer;
+    use solana_sdk::signature::Keypair;
+
+    #[test]
+    fn test_formats_parse() {
+        assert_eq!("json".parse::<ResultFormat>().unwrap(), ResultFormat::Json);
+        assert_eq!("TEXT".parse::<ResultFormat>().unwrap(), ResultFormat::Text);
+        assert!("yaml".parse::<ResultFormat>().is_err());
+        assert_eq!("ndjson".parse::<EventFormat>().unwrap(), EventFormat::Ndjson);
+        assert!("json".parse::<EventFormat>().is_err());
+    }
+
+    #[test]
+    fn test_event_schema() {
+        // These lines are the documented v1 schema; changing them breaks consumers
+        let progress = Event::Progress {
+            attempts: 50_000,
+            elapsed_ms: 1_250,
+            keys_per_sec: 40_000.0,
+        };
+        assert_eq!(
+            progress.to_json(),
+            r#"{"schema_version":1,"event":"progress","attempts":50000,"elapsed_ms":1250,"keys_per_sec":40000.0}"#
+        );
+        let found = Event::Match {
+            pubkey: "ABC".to_string(),
+            path: "ABC.json".to_string(),
+            attempts: 7,
+            time_ms: 3,
+        };
+        assert_eq!(
+            found.to_json(),
+            r#"{"schema_version":1,"event":"match","pubkey":"ABC","path":"ABC.json","attempts":7,"time_ms":3}"#
+        );
+        let finish = Event::Finish {
+            status: FinishStatus::Completed,
+            exit_code: 0,
+            found: 1,
+            elapsed_ms: 5,
+            error: None,
+        };
+        assert_eq!(
+            finish.to_json(),
+            r#"{"schema_version":1,"event":"finish","status":"completed","exit_code":0,"found":1,"elapsed_ms":5}"#
+        );
+
+        for event in [progress, found, finish] {
+            assert_eq!(Event::from_json(&event.to_json()).unwrap(), (SCHEMA_VERSION, event));
+        }
+
+        // Details and derived addresses are lists of labelled objects
+        let result = AddressResult {
+            schema_version: SCHEMA_VERSION,
+            pubkey: "ABC".to_string(),
+            path: "ABC.json".to_string(),
+            attempts: 7,
+            time_ms: 3,
+            pattern: "prefix 'A'".to_string(),
+            verification: VerificationStatus::Skipped,
+            details: vec![MatchDetail {
+                label: "Series slot".to_string(),
+                value: "TEAM1".to_string(),
+            }],
+            derived: vec![DerivedAddress {
+                label: "ATA".to_string(),
+                address: "Derived111".to_string(),
+            }],
+            pump_descriptor: None,
+            create_transaction: None,
+        };
+        assert_eq!(
+            result.to_json(),
+            concat!(
+                r#"{"schema_version":1,"pubkey":"ABC","path":"ABC.json","attempts":7,"time_ms":3,"#,
+                r#""pattern":"prefix 'A'","verification":"skipped","#,
+                r#""details":[{"label":"Series slot","value":"TEAM1"}],"#,
+                r#""derived":[{"label":"ATA","address":"Derived111"}]}"#
+            )
+        );
+    }
+
+    #[test]
+    fn test_event_writer_emits_lines() {
+        let buffer = Buffer::default();
+        let writer = EventWriter::new(buffer.clone());
+        writer.emit(&Event::Progress {
+            attempts: 1,
+            elapsed_ms: 1,
+            keys_per_sec: 1000.0,
+        });
+        writer.clone().emit(&Event::Finish {
+            status: FinishStatus::Failed,
+            exit_code: 1,
+            found: 0,
+            elapsed_ms: 2,
+            error: Some("boom".to_string()),
+        });
+
+        let text = buffer.text();
+        let events: Vec<_> = text.lines().map(|line| Event::from_json(line).unwrap().1).collect();
+        assert_eq!(events.len(), 2);
+        assert!(matches!(events[0], Event::Progress { attempts: 1, .. }));
+        assert!(matches!(events[1], Event::Finish { ref error, .. } if error.as_deref() == Some("boom")));
+    }
+
+    #[test]
+    fn test_address_result_has_no_secret() {
+        let keypair = Keypair::new();
+        let secret = keypair.to_base58_string();
+        let bytes = format!("{:?}", keypair.to_bytes().to_vec()).replace(' ', "");
+        let mut address = GeneratedAddress::new(keypair, 42, 9);
+        address.add_detail("Match", "prefix");
+        address.add_derived("ATA", "Derived111");
+
+        let result = AddressResult::new(&address, "prefix 'A'", Path::new("out.json"))
+            .with_verification(VerificationStatus::Passed);
+        let json = result.to_json();
+        assert!(!json.contains(&secret));
+        assert!(!json.contains(&bytes));
+
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["schema_version"], SCHEMA_VERSION);
+        assert_eq!(value["pubkey"], address.public_key());
+        assert_eq!(value["path"], "out.json");
+        assert_eq!(value["attempts"], 42);
+        assert_eq!(value["pattern"], "prefix 'A'");
+        assert_eq!(value["verification"], "passed");
+        assert_eq!(value["details"][0]["value"], "prefix");
+        assert_eq!(value["derived"][0]["label"], "ATA");
+        assert!(value.get("pump_descriptor").is_none());
+        assert_eq!(serde_json::from_str::<AddressResult>(&json).unwrap(), result);
+
+        let result = result.with_pump(Path::new("out.mint.json"), Some("AQID".to_string()));
+        let value: serde_json::Value = serde_json::from_str(&result.to_json()).unwrap();
+        assert_eq!(value["pump_descriptor"], "out.mint.json");
+        assert_eq!(value["create_transaction"], "AQID");
+    }
+}
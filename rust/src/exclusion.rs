@@ -0,0 +1,342 @@
+//! Exclusion rules for rejecting matched addresses.
+//!
+//! A candidate that matches the target can still be unwanted: it may contain
+//! an offensive word or a long run like `1111`. [`ExclusionRules`] describes
+//! what to reject; the generator drops violating candidates and counts them
+//! separately from ordinary misses.
+
+use crate::config::{BASE58_ALPHABET, TYPICAL_ADDRESS_LENGTH};
+use crate::matcher::CharSet;
+use std::fmt;
+use std::path::Path;
+
+/// Rejection probability from which a rule set counts as rejecting everything
+pub const NEARLY_ALL_REJECTED: f64 = 0.999_999;
+
+/// A substring that must not appear in the address
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ForbiddenSubstring {
+    /// The forbidden text
+    pub text: String,
+    /// Zero-based position it is forbidden at, or anywhere if `None`
+    pub position: Option<usize>,
+    /// Whether the comparison ignores case
+    pub case_insensitive: bool,
+}
+
+impl ForbiddenSubstring {
+    fn found_in(&self, address: &str) -> bool {
+        let (haystack, needle) = if self.case_insensitive {
+            (address.to_ascii_lowercase(), self.text.to_ascii_lowercase())
+        } else {
+            (address.to_string(), self.text.clone())
+        };
+
+        match self.position {
+            // A position past any address can't hold the substring
+            Some(position) => position
+                .checked_add(needle.len())
+                .and_then(|end| haystack.get(position..end))
+                .is_some_and(|s| s == needle),
+            None => haystack.contains(&needle),
+        }
+    }
+}
+
+/// Why an address was rejected
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExclusionViolation {
+    /// A forbidden substring appears in the address
+    Forbidden(String),
+    /// A forbidden substring appears at a specific position
+    ForbiddenAt(String, usize),
+    /// A word from the profanity list appears in the address
+    Profanity(String),
+    /// A character repeats more times in a row than allowed
+    RepeatedRun(char, usize),
+}
+
+impl fmt::Display for ExclusionViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Forbidden(text) => write!(f, "contains forbidden '{text}'"),
+            Self::ForbiddenAt(text, position) => {
+                write!(f, "contains forbidden '{text}' at position {position}")
+            }
+            Self::Profanity(_) => write!(f, "contains a word from the profanity list"),
+            Self::RepeatedRun(c, len) => write!(f, "repeats '{c}' {len} times in a row"),
+        }
+    }
+}
+
+/// Rules for rejecting addresses that match the target but are unwanted
+#[derive(Debug, Clone, Default)]
+pub struct ExclusionRules {
+    forbidden: Vec<ForbiddenSubstring>,
+    profanity: Vec<String>,
+    max_repeat_run: Option<usize>,
+}
+
+impl ExclusionRules {
+    /// Create an empty rule set that rejects nothing
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Forbid a substring anywhere in the address
+    pub fn forbid(mut self, text: &str, case_insensitive: bool) -> Self {
+        if !text.is_empty() {
+            self.forbidden.push(ForbiddenSubstring {
+                text: text.to_string(),
+                position: None,
+                case_insensitive,
+            });
+        }
+        self
+    }
+
+    /// Forbid a substring starting at a specific zero-based position
+    pub fn forbid_at(mut self, text: &str, position: usize, case_insensitive: bool) -> Self {
+        if !text.is_empty() {
+            self.forbidden.push(ForbiddenSubstring {
+                text: text.to_string(),
+                position: Some(position),
+                case_insensitive,
+            });
+        }
+        self
+    }
+
+    /// Reject addresses containing any of these words, ignoring case
+    pub fn with_profanity<I, S>(mut self, words: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        for word in words {
+            let word = word.as_ref().trim().to_ascii_lowercase();
+            if !word.is_empty() && !word.starts_with('#') && !self.profanity.contains(&word) {
+                self.profanity.push(word);
+            }
+        }
+        self
+    }
+
+    /// Load a profanity list (one word per line, `#` comments)
+    pub fn with_profanity_file(self, path: &Path) -> std::io::Result<Self> {
+        let content = std::fs::read_to_string(path)?;
+        Ok(self.with_profanity(content.lines()))
+    }
+
+    /// Reject addresses where any character repeats more than `max` times in a row
+    pub fn with_max_repeat_run(mut self, max: usize) -> Self {
+        self.max_repeat_run = Some(max.max(1));
+        self
+    }
+
+    /// Check if no rules are configured
+    pub fn is_empty(&self) -> bool {
+        self.forbidden.is_empty() && self.profanity.is_empty() && self.max_repeat_run.is_none()
+    }
+
+    /// Number of configured rules (each profanity word counts once)
+    pub fn rule_count(&self) -> usize {
+        self.forbidden.len() + self.profanity.len() + usize::from(self.max_repeat_run.is_some())
+    }
+
+    /// Return the first rule the address violates, if any
+    pub fn check(&self, address: &str) -> Option<ExclusionViolation> {
+        for rule in &self.forbidden {
+            if rule.found_in(address) {
+                return Some(match rule.position {
+                    Some(position) => ExclusionViolation::ForbiddenAt(rule.text.clone(), position),
+                    None => ExclusionViolation::Forbidden(rule.text.clone()),
+                });
+            }
+        }
+
+        if !self.profanity.is_empty() {
+            let lowered = address.to_ascii_lowercase();
+            if let Some(word) = self.profanity.iter().find(|w| lowered.contains(w.as_str())) {
+                return Some(ExclusionViolation::Profanity(word.clone()));
+            }
+        }
+
+        if let Some(max) = self.max_repeat_run {
+            if let Some((c, len)) = longest_run(address) {
+                if len > max {
+                    return Some(ExclusionViolation::RepeatedRun(c, len));
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Approximate probability that a random address violates these rules
+    ///
+    /// Uses a union bound over all rules, so it slightly overestimates when
+    /// rules overlap. The result is capped at 1.
+    pub fn rejection_probability(&self) -> f64 {
+        let alphabet = BASE58_ALPHABET.len() as f64;
+        let per_position = |text: &str, case_insensitive: bool| -> f64 {
+            text.chars()
+                .map(|c| {
+                    let variants = if case_insensitive {
+                        CharSet::from_chars([c.to_ascii_lowercase(), c.to_ascii_uppercase()])
+                            .len()
+                            .max(1)
+                    } else {
+                        1
+                    };
+                    variants as f64 / alphabet
+                })
+                .product()
+        };
+        let placements =
+            |len: usize| TYPICAL_ADDRESS_LENGTH.saturating_sub(len).saturating_add(1) as f64;
+
+        let mut probability = 0.0;
+        for rule in &self.forbidden {
+            let p = per_position(&rule.text, rule.case_insensitive);
+            probability += match rule.position {
+                Some(_) => p,
+                None => p * placements(rule.text.len()),
+            };
+        }
+        for word in &self.profanity {
+            probability += per_position(word, true) * placements(word.len());
+        }
+        if let Some(max) = self.max_repeat_run {
+            // A run longer than `max` needs `max` repeats of the previous character
+            probability += placements(max.saturating_add(1)) * (1.0 / alphabet).powf(max as f64);
+        }
+
+        probability.min(1.0)
+    }
+
+    /// Approximate probability that a random address passes these rules
+    ///
+    /// Returns 0 once [`rejection_probability`](Self::rejection_probability)
+    /// is within [`NEARLY_ALL_REJECTED`] of its cap, so difficulty estimates
+    /// report such rule sets as effectively impossible rather than as a huge
+    /// but finite number of attempts.
+    pub fn acceptance_probability(&self) -> f64 {
+        let rejection = self.rejection_probability();
+        if rejection >= NEARLY_ALL_REJECTED {
+            0.0
+        } else {
+            1.0 - rejection
+        }
+    }
+
+    /// Human-readable summary of the rules
+    pub fn description(&self) -> String {
+        let mut parts = Vec::new();
+        for rule in &self.forbidden {
+            let case_str = if rule.case_insensitive { ", any case" } else { "" };
+            match rule.position {
+                Some(position) => {
+                    parts.push(format!("no '{}' at position {position}{case_str}", rule.text))
+                }
+                None => parts.push(format!("no '{}'{case_str}", rule.text)),
+            }
+        }
+        if !self.profanity.is_empty() {
+            parts.push(format!("no profanity ({} words)", self.profanity.len()));
+        }
+        if let Some(max) = self.max_repeat_run {
+            parts.push(format!("runs of at most {max}"));
+        }
+        parts.join(", ")
+    }
+}
+
+/// Longest run of one repeated character, as `(char, length)`
+fn longest_run(address: &str) -> Option<(char, usize)> {
+    let mut best: Option<(char, usize)> = None;
+    let mut current: Option<(char, usize)> = None;
+
+    for c in address.chars() {
+        current = match current {
+            Some((prev, len)) if prev == c => Some((c, len + 1)),
+            _ => Some((c, 1)),
+        };
+        if let Some((c, len)) = current {
+            if best.is_none_or(|(_, best_len)| len > best_len) {
+                best = Some((c, len));
+            }
+        }
+    }
+
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ADDRESS: &str = "AB1111cdefghijkmnopqrstuvwxyz23456789XYZ";
+
+    #[test]
+    fn test_empty_rules_accept_everything() {
+        let rules = ExclusionRules::new();
+        assert!(rules.is_empty());
+        assert!(rules.check(ADDRESS).is_none());
+        assert_eq!(rules.rejection_probability(), 0.0);
+    }
+
+    #[test]
+    fn test_forbidden_anywhere() {
+        let rules = ExclusionRules::new().forbid("111", false);
+        assert_eq!(rules.check(ADDRESS), Some(ExclusionViolation::Forbidden("111".into())));
+
+        let rules = ExclusionRules::new().forbid("Xyz", false);
+        assert!(rules.check(ADDRESS).is_none());
+        let rules = ExclusionRules::new().forbid("Xyz", true);
+        assert!(rules.check(ADDRESS).is_some());
+    }
+
+    #[test]
+    fn test_forbidden_at_position() {
+        let rules = ExclusionRules::new().forbid_at("11", 2, false);
+        assert_eq!(rules.check(ADDRESS), Some(ExclusionViolation::ForbiddenAt("11".into(), 2)));
+
+        let rules = ExclusionRules::new().forbid_at("11", 0, false);
+        assert!(rules.check(ADDRESS).is_none());
+
+        let rules = ExclusionRules::new().forbid_at("11", usize::MAX, false);
+        assert!(rules.check(ADDRESS).is_none());
+    }
+
+    #[test]
+    fn test_profanity_ignores_case() {
+        let rules = ExclusionRules::new().with_profanity(["# comment", "CDEF", ""]);
+        assert_eq!(rules.rule_count(), 1);
+        assert!(matches!(rules.check(ADDRESS), Some(ExclusionViolation::Profanity(_))));
+    }
+
+    #[test]
+    fn test_max_repeat_run() {
+        let rules = ExclusionRules::new().with_max_repeat_run(3);
+        assert_eq!(rules.check(ADDRESS), Some(ExclusionViolation::RepeatedRun('1', 4)));
+
+        let rules = ExclusionRules::new().with_max_repeat_run(4);
+        assert!(rules.check(ADDRESS).is_none());
+    }
+
+    #[test]
+    fn test_rejection_probability() {
+        let strict = ExclusionRules::new().with_max_repeat_run(1);
+        let loose = ExclusionRules::new().with_max_repeat_run(4);
+        assert!(strict.rejection_probability() > 0.5);
+        assert!(loose.rejection_probability() < 1e-3);
+        assert!(strict.rejection_probability() <= 1.0);
+    }
+
+    #[test]
+    fn test_violation_display_hides_profanity() {
+        let violation = ExclusionViolation::Profanity("badword".into());
+        assert!(!violation.to_string().contains("badword"));
+    }
+}
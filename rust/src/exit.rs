@@ -0,0 +1,252 @@
+//! Process exit codes.
+//!
+//! Every error the library returns falls into one of these categories, so
+//! scripts and orchestrators can tell a bad pattern (fix the input) from an
+//! exhausted budget (retry with more time) or an I/O failure (check the disk).
+
+use crate::config::ConfigError;
+use crate::export::ExportError;
+use crate::generator::GeneratorError;
+use crate::output::OutputError;
+use crate::pump::PumpError;
+use crate::security::SecurityError;
+use crate::wordlist::WordlistError;
+use std::io;
+
+/// Exit code of the `solana-vanity` binary
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitCode {
+    /// Everything requested was done
+    Success = 0,
+    /// An error outside the categories below (e.g. RNG or signing failure)
+    Failure = 1,
+    /// Invalid command-line usage or option value (as used by clap)
+    Usage = 2,
+    /// The pattern is malformed, e.g. contains non-Base58 characters
+    InvalidPattern = 3,
+    /// The pattern is valid but can't match in practice, or at all
+    ImpossiblePattern = 4,
+    /// The time budget or search range ran out without a match
+    BudgetExhausted = 5,
+    /// An output file already exists or its path can't be used
+    OutputConflict = 6,
+    /// Reading or writing a file failed
+    Io = 7,
+    /// A keypair or keypair file failed verification
+    VerificationFailed = 8,
+    /// The RPC pre-flight check couldn't be completed, so no match was checked
+    PreflightFailed = 9,
+    /// Interrupted by Ctrl+C (128 + SIGINT)
+    Cancelled = 130,
+}
+
+impl ExitCode {
+    /// The numeric process exit code
+    pub fn code(self) -> i32 {
+        self as i32
+    }
+}
+
+impl From<&io::Error> for ExitCode {
+    fn from(error: &io::Error) -> Self {
+        match error.kind() {
+            io::ErrorKind::AlreadyExists => Self::OutputConflict,
+            _ => Self::Io,
+        }
+    }
+}
+
+impl From<&ConfigError> for ExitCode {
+    fn from(error: &ConfigError) -> Self {
+        match error {
+            ConfigError::InvalidBase58Character(_)
+            | ConfigError::EmptyPattern
+            | ConfigError::NoPatternSpecified
+            | ConfigError::InvalidConfusable(_)
+            | ConfigError::InvalidPatternClass(_)
+            | ConfigError::InvalidRawConstraint(_)
+            | ConfigError::InvalidTemplate(_)
+            | ConfigError::InvalidDerived(_)
+            | ConfigError::InvalidGrindSpec(_)
+            | ConfigError::InvalidCaseRule(_)
+            | ConfigError::CaseMaskLength { .. } => Self::InvalidPattern,
+            ConfigError::PrefixTooLong(_)
+            | ConfigError::SuffixTooLong(_)
+            | ConfigError::Unsatisfiable(_) => Self::ImpossiblePattern,
+            ConfigError::OutputNotWritable(_) => Self::Io,
+            ConfigError::OutputCollision(_) => Self::OutputConflict,
+            ConfigError::InvalidThreadCount(_)
+            | ConfigError::InvalidCount(_)
+            | ConfigError::CountTooLarge { .. }
+            | ConfigError::InvalidSeed(_)
+            | ConfigError::InvalidTokenMetadata(_)
+            | ConfigError::InvalidMnemonic(_)
+            | ConfigError::InvalidRpcUrl(_)
+            | ConfigError::InvalidPreflightPolicy(_)
+            | ConfigError::InvalidKeyFormat(_)
+            | ConfigError::InvalidExportFormat(_)
+            | ConfigError::InvalidResultFormat(_)
+            | ConfigError::InvalidEventFormat(_)
+            | ConfigError::InvalidConfigFile(_)
+            | ConfigError::UnknownProfile(_)
+            | ConfigError::InvalidJobFile(_)
+            | ConfigError::InvalidOutputTemplate(_) => Self::Usage,
+            ConfigError::InvalidJob { source, .. } => source.as_ref().into(),
+        }
+    }
+}
+
+impl From<&GeneratorError> for ExitCode {
+    fn from(error: &GeneratorError) -> Self {
+        match error {
+            GeneratorError::RngError(_) => Self::Failure,
+            GeneratorError::VerificationError(_) => Self::VerificationFailed,
+            GeneratorError::Cancelled => Self::Cancelled,
+            GeneratorError::ConfigError(_) => Self::InvalidPattern,
+            GeneratorError::Exhausted => Self::BudgetExhausted,
+            GeneratorError::PreflightFailed(_) => Self::PreflightFailed,
+        }
+    }
+}
+
+impl From<&SecurityError> for ExitCode {
+    fn from(error: &SecurityError) -> Self {
+        match error {
+            SecurityError::PermissionError(e) => e.into(),
+            SecurityError::RngQualityError(_) => Self::Failure,
+            SecurityError::IntegrityError { .. } | SecurityError::KeypairVerificationError(_) => {
+                Self::VerificationFailed
+            }
+            SecurityError::UnsafePathError(_) => Self::Io,
+        }
+    }
+}
+
+impl From<&OutputError> for ExitCode {
+    fn from(error: &OutputError) -> Self {
+        match error {
+            OutputError::SerializationError(_) => Self::Failure,
+            OutputError::SecurityError(e) => e.into(),
+            OutputError::IoError(e) => e.into(),
+            OutputError::InvalidKeypairData => Self::VerificationFailed,
+            OutputError::ConfigError(e) => e.into(),
+        }
+    }
+}
+
+impl From<&ExportError> for ExitCode {
+    fn from(error: &ExportError) -> Self {
+        match error {
+            ExportError::Malformed { .. } | ExportError::UnknownFormat | ExportError::PublicKeyMismatch(_) => {
+                Self::VerificationFailed
+            }
+            ExportError::Unsupported { .. } | ExportError::PublicKeyOnly(_) => Self::Usage,
+            ExportError::OutputError(e) => e.into(),
+            ExportError::SecurityError(e) => e.into(),
+        }
+    }
+}
+
+impl From<&WordlistError> for ExitCode {
+    fn from(error: &WordlistError) -> Self {
+        match error {
+            WordlistError::IoError(e) => e.into(),
+            WordlistError::InvalidScore { .. } | WordlistError::NoUsableWords(_) => {
+                Self::InvalidPattern
+            }
+        }
+    }
+}
+
+impl From<&PumpError> for ExitCode {
+    fn from(error: &PumpError) -> Self {
+        match error {
+            PumpError::ConfigError(e) => e.into(),
+            PumpError::SigningFailed(_) | PumpError::SerializationFailed(_) => Self::Failure,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matcher::MatchTarget;
+    use crate::raw::{ByteConstraint, RawTarget};
+
+    #[test]
+    fn test_codes_are_distinct() {
+        let codes = [
+            ExitCode::Success,
+            ExitCode::Failure,
+            ExitCode::Usage,
+            ExitCode::InvalidPattern,
+            ExitCode::ImpossiblePattern,
+            ExitCode::BudgetExhausted,
+            ExitCode::OutputConflict,
+            ExitCode::Io,
+            ExitCode::VerificationFailed,
+            ExitCode::Cancelled,
+        ];
+        let mut numbers: Vec<i32> = codes.iter().map(|c| c.code()).collect();
+        numbers.sort_unstable();
+        numbers.dedup();
+        assert_eq!(numbers.len(), codes.len());
+        assert_eq!(ExitCode::Cancelled.code(), 130);
+        assert_eq!(ExitCode::Usage.code(), 2);
+    }
+
+    #[test]
+    fn test_pattern_errors() {
+        let code = |e: ConfigError| ExitCode::from(&e);
+        let invalid = MatchTarget::prefix("A0", false).unwrap_err();
+        assert_eq!(code(invalid), ExitCode::InvalidPattern);
+        let long = MatchTarget::prefix("ABCDEFGHJ", false).unwrap_err();
+        assert_eq!(code(long), ExitCode::ImpossiblePattern);
+
+        let contradiction = RawTarget::new()
+            .with(ByteConstraint::masked_prefix("00").unwrap())
+            .unwrap()
+            .with(ByteConstraint::masked_prefix("01").unwrap())
+            .unwrap_err();
+        assert_eq!(code(contradiction), ExitCode::ImpossiblePattern);
+        assert_eq!(code(ByteConstraint::shard("0:0").unwrap_err()), ExitCode::InvalidPattern);
+        assert_eq!(code(ConfigError::InvalidKeyFormat("pem".into())), ExitCode::Usage);
+        assert_eq!(code(ConfigError::InvalidExportFormat("pkcs12".into())), ExitCode::Usage);
+        let job = ConfigError::InvalidJob {
+            job: "treasury".into(),
+            source: Box::new(MatchTarget::prefix("A0", false).unwrap_err()),
+        };
+        assert_eq!(code(job), ExitCode::InvalidPattern);
+    }
+
+    #[test]
+    fn test_runtime_errors() {
+        assert_eq!(ExitCode::from(&GeneratorError::Cancelled), ExitCode::Cancelled);
+        assert_eq!(ExitCode::from(&GeneratorError::Exhausted), ExitCode::BudgetExhausted);
+        let preflight = GeneratorError::PreflightFailed("connection refused".into());
+        assert_eq!(ExitCode::from(&preflight).code(), 9);
+        assert_eq!(
+            ExitCode::from(&GeneratorError::VerificationError("bad".into())),
+            ExitCode::VerificationFailed
+        );
+
+        let exists = io::Error::new(io::ErrorKind::AlreadyExists, "exists");
+        assert_eq!(ExitCode::from(&OutputError::IoError(exists)), ExitCode::OutputConflict);
+        let denied = io::Error::new(io::ErrorKind::PermissionDenied, "denied");
+        let denied = OutputError::SecurityError(SecurityError::PermissionError(denied));
+        assert_eq!(ExitCode::from(&denied), ExitCode::Io);
+        let unsafe_path = SecurityError::UnsafePathError("/etc/key.json".into());
+        assert_eq!(ExitCode::from(&unsafe_path), ExitCode::Io);
+        let collision = OutputError::ConfigError(ConfigError::OutputCollision("key.json".into()));
+        assert_eq!(ExitCode::from(&collision), ExitCode::OutputConflict);
+        assert_eq!(ExitCode::from(&OutputError::InvalidKeypairData), ExitCode::VerificationFailed);
+        assert_eq!(
+            ExitCode::from(&SecurityError::IntegrityError { expected: 64, actual: 0 }),
+            ExitCode::VerificationFailed
+        );
+
+        use crate::export::KeyFormat;
+        assert_eq!(ExitCode::from(&ExportError::PublicKeyMismatch(KeyFormat::Jwk)), ExitCode::VerificationFailed);
+        assert_eq!(ExitCode::from(&ExportError::PublicKeyOnly(KeyFormat::OpenSshPublic)), ExitCode::Usage);
+    }
+}
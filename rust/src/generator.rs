@@ -3,15 +3,19 @@
 //! This module provides multi-threaded vanity address generation
 //! using the official Solana SDK for cryptographic operations.
 
-use crate::matcher::{MatchTarget, OptimizedMatcher};
+use crate::derived::DerivedConstraint;
+use crate::exclusion::ExclusionRules;
+use crate::matcher::{MatchScore, MatchTarget, OptimizedMatcher};
 use crate::output::GeneratedAddress;
+use crate::rpc::{Preflight, PreflightPolicy};
 use crate::security::{verify_keypair_integrity, verify_rng_quality};
 use rayon::prelude::*;
 use solana_sdk::signature::Signer;
 use solana_sdk::signer::keypair::Keypair;
+use std::ops::Range;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use thiserror::Error;
 
 /// Generator errors
@@ -28,6 +32,12 @@ pub enum GeneratorError {
 
     #[error("Invalid configuration: {0}")]
     ConfigError(String),
+
+    #[error("Search space exhausted without a match")]
+    Exhausted,
+
+    #[error("Pre-flight check failed: {0}")]
+    PreflightFailed(String),
 }
 
 /// Result type for generator operations
@@ -36,6 +46,47 @@ pub type GeneratorResult<T> = Result<T, GeneratorError>;
 /// Progress callback type
 pub type ProgressCallback = Box<dyn Fn(u64, f64) + Send + Sync>;
 
+/// Schedule that lowers the score accepted by a best-effort search over time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RelaxSchedule {
+    /// How long to wait before accepting one fewer matched character
+    pub step: Duration,
+    /// The lowest score the schedule will accept
+    pub min_score: usize,
+}
+
+impl RelaxSchedule {
+    /// Score required to stop early after `elapsed`, starting from `full`
+    pub fn required_score(&self, full: usize, elapsed: Duration) -> usize {
+        let steps = if self.step.is_zero() {
+            usize::MAX
+        } else {
+            (elapsed.as_nanos() / self.step.as_nanos()) as usize
+        };
+        full.saturating_sub(steps).max(self.min_score.min(full))
+    }
+}
+
+/// Options for a best-effort search
+#[derive(Debug, Clone)]
+pub struct BestEffortConfig {
+    /// Maximum time to search
+    pub budget: Duration,
+    /// Number of best candidates to keep
+    pub keep: usize,
+    /// Optional schedule for stopping early on a partial match
+    pub schedule: Option<RelaxSchedule>,
+}
+
+/// An address kept by a best-effort search, with how much of the pattern it matched
+#[derive(Debug)]
+pub struct PartialMatch {
+    /// The generated keypair and its statistics
+    pub address: GeneratedAddress,
+    /// How many pattern characters the address satisfies
+    pub score: MatchScore,
+}
+
 /// Configuration for the vanity generator
 #[derive(Clone)]
 pub struct VanityGeneratorConfig {
@@ -57,12 +108,57 @@ impl Default for VanityGeneratorConfig {
     }
 }
 
+/// Cancellation flag and attempt counter shared by a search and its caller
+#[derive(Debug, Clone, Default)]
+pub struct SearchProgress {
+    cancelled: Arc<AtomicBool>,
+    attempts: Arc<AtomicU64>,
+}
+
+impl SearchProgress {
+    /// Progress whose cancellation flag is `cancel`, shared with other searches
+    pub fn with_cancel(cancel: Arc<AtomicBool>) -> Self {
+        Self { cancelled: cancel, attempts: Arc::default() }
+    }
+
+    /// Whether the search has been cancelled
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// Count one attempt, returning the new total
+    pub fn record_attempt(&self) -> u64 {
+        self.attempts.fetch_add(1, Ordering::Relaxed) + 1
+    }
+}
+
+/// Cancellation and attempt reporting common to every search
+pub trait ParallelSearch {
+    /// The search's shared progress
+    fn progress(&self) -> &SearchProgress;
+
+    /// Get a cancellation handle
+    fn cancel_handle(&self) -> Arc<AtomicBool> {
+        Arc::clone(&self.progress().cancelled)
+    }
+
+    /// Get the current attempt count
+    fn attempts(&self) -> u64 {
+        self.progress().attempts.load(Ordering::Relaxed)
+    }
+}
+
 /// The main vanity address generator
 pub struct VanityGenerator {
     config: VanityGeneratorConfig,
     matcher: OptimizedMatcher,
-    cancelled: Arc<AtomicBool>,
-    attempts: Arc<AtomicU64>,
+    exclusions: ExclusionRules,
+    derived: Vec<DerivedConstraint>,
+    preflight: Option<Preflight>,
+    progress: SearchProgress,
+    excluded: Arc<AtomicU64>,
+    preflight_rejected: Arc<AtomicU64>,
+    preflight_error: Arc<std::sync::Mutex<Option<String>>>,
 }
 
 impl VanityGenerator {
@@ -76,34 +172,92 @@ impl VanityGenerator {
         Ok(Self {
             config,
             matcher,
-            cancelled: Arc::new(AtomicBool::new(false)),
-            attempts: Arc::new(AtomicU64::new(0)),
+            exclusions: ExclusionRules::default(),
+            derived: Vec::new(),
+            preflight: None,
+            progress: SearchProgress::default(),
+            excluded: Arc::new(AtomicU64::new(0)),
+            preflight_rejected: Arc::new(AtomicU64::new(0)),
+            preflight_error: Arc::new(std::sync::Mutex::new(None)),
         })
     }
 
+    /// Reject matching addresses that violate these exclusion rules
+    pub fn with_exclusions(mut self, exclusions: ExclusionRules) -> Self {
+        self.exclusions = exclusions;
+        self
+    }
+
+    /// Get the configured exclusion rules
+    pub fn exclusions(&self) -> &ExclusionRules {
+        &self.exclusions
+    }
+
+    /// Also require an address derived from the key to match its own target
+    ///
+    /// Every derived constraint must hold; the derived addresses are recorded
+    /// on the result for reports.
+    pub fn with_derived(mut self, constraint: DerivedConstraint) -> Self {
+        self.derived.push(constraint);
+        self
+    }
+
+    /// Get the configured derived-address constraints
+    pub fn derived_constraints(&self) -> &[DerivedConstraint] {
+        &self.derived
+    }
+
+    /// Check each verified match over RPC and skip addresses already in use
+    ///
+    /// Without this the search never touches the network. A failed lookup
+    /// stops the search with [`GeneratorError::PreflightFailed`] unless the
+    /// check keeps unchecked matches.
+    pub fn with_preflight(mut self, preflight: Preflight) -> Self {
+        self.preflight = Some(preflight);
+        self
+    }
+
+    /// Stop when `cancel` is set, e.g. by a Ctrl+C handler covering several searches
+    pub fn with_cancel_handle(mut self, cancel: Arc<AtomicBool>) -> Self {
+        self.progress = SearchProgress::with_cancel(cancel);
+        self
+    }
+
+    /// Get the configured pre-flight check
+    pub fn preflight(&self) -> Option<&Preflight> {
+        self.preflight.as_ref()
+    }
+
+    /// Number of matches skipped because their account already exists
+    pub fn preflight_rejected(&self) -> u64 {
+        self.preflight_rejected.load(Ordering::Relaxed)
+    }
+
+    /// The RPC failure that left the last result unchecked, if any
+    ///
+    /// Only set when the pre-flight check keeps unchecked matches.
+    pub fn preflight_error(&self) -> Option<String> {
+        self.preflight_error.lock().unwrap().clone()
+    }
+
     /// Create a generator with default configuration
     pub fn with_target(target: MatchTarget) -> GeneratorResult<Self> {
         Self::new(target, VanityGeneratorConfig::default())
     }
 
-    /// Get a cancellation handle
-    pub fn cancel_handle(&self) -> Arc<AtomicBool> {
-        Arc::clone(&self.cancelled)
-    }
-
-    /// Get the current attempt count
-    pub fn attempts(&self) -> u64 {
-        self.attempts.load(Ordering::Relaxed)
+    /// Get the number of matching addresses dropped by exclusion rules
+    pub fn excluded(&self) -> u64 {
+        self.excluded.load(Ordering::Relaxed)
     }
 
     /// Cancel the generation
     pub fn cancel(&self) {
-        self.cancelled.store(true, Ordering::SeqCst);
+        self.progress.cancelled.store(true, Ordering::SeqCst);
     }
 
     /// Check if generation has been cancelled
     pub fn is_cancelled(&self) -> bool {
-        self.cancelled.load(Ordering::SeqCst)
+        self.progress.cancelled.load(Ordering::SeqCst)
     }
 
     /// Generate a single vanity address
@@ -120,9 +274,16 @@ impl VanityGenerator {
         progress: Option<ProgressCallback>,
     ) -> GeneratorResult<GeneratedAddress> {
         let start_time = Instant::now();
-        let cancelled = Arc::clone(&self.cancelled);
-        let attempts = Arc::clone(&self.attempts);
+        let cancelled = Arc::clone(&self.progress.cancelled);
+        let attempts = Arc::clone(&self.progress.attempts);
+        let excluded = Arc::clone(&self.excluded);
         let matcher = self.matcher.clone();
+        let exclusions = &self.exclusions;
+        let derived = &self.derived;
+        let preflight = self.preflight.as_ref();
+        let preflight_rejected = Arc::clone(&self.preflight_rejected);
+        let preflight_error = &self.preflight_error;
+        *preflight_error.lock().unwrap() = None;
         let verify = self.config.verify_keypairs;
         let progress_interval = self.config.progress_interval;
 
@@ -135,7 +296,8 @@ impl VanityGenerator {
         // Result storage
         let result: Arc<std::sync::Mutex<Option<GeneratedAddress>>> =
             Arc::new(std::sync::Mutex::new(None));
-
+        // Addresses flagged by the pre-flight check
+        let flagged = std::sync::Mutex::new(Vec::new());
         // Progress tracking
         let last_progress_report = Arc::new(AtomicU64::new(0));
 
@@ -154,7 +316,7 @@ impl VanityGenerator {
 
                 // Generate a new keypair
                 let keypair = Keypair::new();
-                let pubkey_str = keypair.pubkey().to_string();
+                let pubkey = keypair.pubkey();
 
                 // Increment attempt counter
                 let current_attempts = attempts.fetch_add(1, Ordering::Relaxed) + 1;
@@ -169,8 +331,30 @@ impl VanityGenerator {
                     }
                 }
 
+                // Raw key targets are checked before Base58 encoding
+                if !matcher.matches_key(&pubkey.to_bytes()) {
+                    return false;
+                }
+                let pubkey_str = pubkey.to_string();
+
                 // Check for match
                 if matcher.matches(&pubkey_str) {
+                    // Drop matches that violate an exclusion rule
+                    if let Some(violation) = exclusions.check(&pubkey_str) {
+                        log::debug!("Excluded {}: {}", pubkey_str, violation);
+                        excluded.fetch_add(1, Ordering::Relaxed);
+                        return false; // Continue searching
+                    }
+
+                    // Every derived address must match its own target
+                    let mut derived_addresses = Vec::with_capacity(derived.len());
+                    for constraint in derived {
+                        match constraint.check(&pubkey) {
+                            Some(address) => derived_addresses.push((constraint.label(), address)),
+                            None => return false, // Continue searching
+                        }
+                    }
+
                     // Verify keypair if configured
                     if verify {
                         if let Err(e) = verify_keypair_integrity(&keypair) {
@@ -179,8 +363,42 @@ impl VanityGenerator {
                         }
                     }
 
+                    // Skip addresses that already hold an account on chain
+                    let mut preflight_note = None;
+                    if let Some(preflight) = preflight {
+                        match preflight.check(&pubkey) {
+                            Ok(None) => {}
+                            Ok(Some(account)) => {
+                                log::info!("Skipping {}: account exists ({})", pubkey_str, account.summary());
+                                preflight_rejected.fetch_add(1, Ordering::Relaxed);
+                                if preflight.policy() == PreflightPolicy::Flag {
+                                    flagged.lock().unwrap().push((pubkey_str, account));
+                                }
+                                return false; // Continue searching
+                            }
+                            Err(e) if preflight.keeps_unchecked() => {
+                                log::warn!("Pre-flight check of {} failed: {}", pubkey_str, e);
+                                preflight_note = Some(("Pre-flight", format!("not checked ({e})")));
+                                preflight_error.lock().unwrap().get_or_insert(e.to_string());
+                            }
+                            Err(e) => {
+                                preflight_error.lock().unwrap().get_or_insert(e.to_string());
+                                return true; // Stop: the check can't be trusted
+                            }
+                        }
+                    }
+
                     let elapsed_ms = start_time.elapsed().as_millis() as u64;
-                    let address = GeneratedAddress::new(keypair, current_attempts, elapsed_ms);
+                    let mut address = GeneratedAddress::new(keypair, current_attempts, elapsed_ms);
+                    if let Some((label, value)) = preflight_note {
+                        address.add_detail(label, value);
+                    }
+                    for (label, value) in matcher.match_details(&pubkey_str) {
+                        address.add_detail(label, value);
+                    }
+                    for (label, derived_address) in derived_addresses {
+                        address.add_derived(label, derived_address.to_string());
+                    }
 
                     let mut guard = result.lock().unwrap();
                     if guard.is_none() {
@@ -198,13 +416,22 @@ impl VanityGenerator {
             return Err(GeneratorError::Cancelled);
         }
 
+        let mut final_result = result.lock().unwrap().take();
+        if final_result.is_none() {
+            if let Some(e) = preflight_error.lock().unwrap().clone() {
+                return Err(GeneratorError::PreflightFailed(e));
+            }
+        }
+
+        // List flagged addresses on the result
+        if let Some(ref mut address) = final_result {
+            for (pubkey, account) in flagged.into_inner().unwrap() {
+                address.add_detail("Skipped (account exists)", format!("{} ({})", pubkey, account.summary()));
+            }
+        }
+
         // Return result
-        let final_result = result
-            .lock()
-            .unwrap()
-            .take()
-            .ok_or(GeneratorError::Cancelled);
-        final_result
+        final_result.ok_or(GeneratorError::Cancelled)
     }
 
     /// Generate multiple vanity addresses
@@ -224,18 +451,92 @@ impl VanityGenerator {
         Ok(results)
     }
 
+    /// Search for the closest matches within a time budget
+    ///
+    /// Candidates are scored by how many leading prefix characters and
+    /// trailing suffix characters they satisfy, and the best `keep` are held
+    /// in memory (evicted keypairs are zeroized as they are dropped). The
+    /// search stops early on a full match, or on a partial match that meets
+    /// the optional relax schedule. Results are ordered best first.
+    pub fn generate_best_effort(
+        &self,
+        options: &BestEffortConfig,
+    ) -> GeneratorResult<Vec<PartialMatch>> {
+        let full = self.matcher.max_score();
+        if full == 0 {
+            return Err(GeneratorError::ConfigError(format!(
+                "best-effort mode needs a prefix or suffix pattern, not {}",
+                self.matcher.target().description()
+            )));
+        }
+        if options.keep == 0 {
+            return Err(GeneratorError::ConfigError(
+                "best-effort mode must keep at least one result".to_string(),
+            ));
+        }
+        if !self.derived.is_empty() {
+            return Err(GeneratorError::ConfigError(
+                "best-effort mode does not support derived-address constraints".to_string(),
+            ));
+        }
+        if self.preflight.is_some() {
+            return Err(GeneratorError::ConfigError(
+                "best-effort mode does not support on-chain pre-flight checks".to_string(),
+            ));
+        }
+
+        let matcher = &self.matcher;
+        let exclusions = &self.exclusions;
+        let excluded = &self.excluded;
+        let found = ranked_search(
+            &self.config,
+            &self.progress,
+            options.budget,
+            options.keep,
+            |address: &str| {
+                let score = matcher.score(address);
+                if score.total() == 0 {
+                    return None;
+                }
+                if exclusions.check(address).is_some() {
+                    excluded.fetch_add(1, Ordering::Relaxed);
+                    return None;
+                }
+                Some(score)
+            },
+            |score, elapsed| {
+                let required = options
+                    .schedule
+                    .map_or(full, |s| s.required_score(full, elapsed));
+                score.total() >= required
+            },
+        )?;
+
+        Ok(found
+            .into_iter()
+            .map(|(mut address, score)| {
+                address.add_detail(
+                    "Partial match",
+                    format!("{} of {} pattern characters", score.total(), full),
+                );
+                PartialMatch { address, score }
+            })
+            .collect())
+    }
+
     /// Estimate the difficulty of finding a match
     ///
+    /// Matches rejected by exclusion rules are discounted, which only
+    /// matters when the rules reject a noticeable share of addresses. Rules
+    /// that reject nearly every address give infinite expected attempts.
+    ///
     /// Returns (expected_attempts, probability_per_attempt)
     pub fn estimate_difficulty(&self) -> (f64, f64) {
-        let target = self.matcher.target();
-        let case_insensitive = target.is_case_insensitive();
-        let pattern_len = target.pattern_length();
-
-        // Base probability for each character position
-        let base: f64 = if case_insensitive { 34.0 } else { 58.0 };
-
-        let prob_per_attempt = 1.0 / base.powi(pattern_len as i32);
+        let derived_probability: f64 =
+            self.derived.iter().map(|c| c.target().probability()).product();
+        let prob_per_attempt = self.matcher.target().probability()
+            * derived_probability
+            * self.exclusions.acceptance_probability();
         let expected_attempts = 1.0 / prob_per_attempt;
 
         (expected_attempts, prob_per_attempt)
@@ -244,10 +545,244 @@ impl VanityGenerator {
     /// Get generation rate (keys per second) from current stats
     pub fn current_rate(&self) -> f64 {
         // This is a snapshot and may not be perfectly accurate
-        self.attempts.load(Ordering::Relaxed) as f64
+        self.progress.attempts.load(Ordering::Relaxed) as f64
+    }
+}
+
+impl ParallelSearch for VanityGenerator {
+    fn progress(&self) -> &SearchProgress {
+        &self.progress
+    }
+}
+
+/// Bounded, best-first collection of ranked search results
+///
+/// Evicted entries are dropped immediately, which zeroizes their secret keys.
+pub(crate) struct TopK<M> {
+    keep: usize,
+    entries: Vec<(GeneratedAddress, M)>,
+}
+
+impl<M: Ord> TopK<M> {
+    /// Create an empty collection holding at most `keep` entries
+    pub(crate) fn new(keep: usize) -> Self {
+        Self {
+            keep,
+            entries: Vec::with_capacity(keep + 1),
+        }
+    }
+
+    /// Check whether a candidate with this rank would be kept
+    pub(crate) fn accepts(&self, rank: &M) -> bool {
+        self.entries.len() < self.keep
+            || self.entries.last().is_none_or(|(_, worst)| rank > worst)
+    }
+
+    /// Insert a candidate, evicting the worst entry if over capacity
+    pub(crate) fn insert(&mut self, address: GeneratedAddress, rank: M) {
+        let position = self.entries.partition_point(|(_, m)| *m >= rank);
+        self.entries.insert(position, (address, rank));
+        if self.entries.len() > self.keep {
+            drop(self.entries.pop());
+        }
+    }
+
+    /// Consume the collection, returning entries best first
+    pub(crate) fn into_sorted(self) -> Vec<(GeneratedAddress, M)> {
+        self.entries
+    }
+}
+
+/// Time-boxed parallel search that keeps the `keep` best-ranked candidates
+///
+/// `evaluate` returns a rank for addresses worth keeping, or `None`. The
+/// search runs until `budget` elapses, `progress` is cancelled, or `done` accepts
+/// a newly kept rank (given the elapsed time), and always returns what it
+/// has found so far.
+pub(crate) fn ranked_search<M, F, D>(
+    config: &VanityGeneratorConfig,
+    progress: &SearchProgress,
+    budget: Duration,
+    keep: usize,
+    evaluate: F,
+    done: D,
+) -> GeneratorResult<Vec<(GeneratedAddress, M)>>
+where
+    M: Ord + Send,
+    F: Fn(&str) -> Option<M> + Sync,
+    D: Fn(&M, Duration) -> bool + Sync,
+{
+    let start_time = Instant::now();
+    let verify = config.verify_keypairs;
+
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(config.threads)
+        .build()
+        .map_err(|e| GeneratorError::ConfigError(e.to_string()))?;
+
+    let best = std::sync::Mutex::new(TopK::new(keep));
+    let finished = AtomicBool::new(false);
+
+    pool.install(|| {
+        (0..usize::MAX).into_par_iter().find_any(|_| {
+            if progress.is_cancelled() || finished.load(Ordering::Relaxed) || start_time.elapsed() >= budget
+            {
+                return true;
+            }
+
+            let keypair = Keypair::new();
+            let pubkey_str = keypair.pubkey().to_string();
+            let current_attempts = progress.record_attempt();
+
+            let Some(rank) = evaluate(&pubkey_str) else {
+                return false;
+            };
+
+            if !best.lock().unwrap().accepts(&rank) {
+                return false;
+            }
+
+            if verify {
+                if let Err(e) = verify_keypair_integrity(&keypair) {
+                    log::error!("Keypair verification failed: {}", e);
+                    return false;
+                }
+            }
+
+            let elapsed = start_time.elapsed();
+            let address = GeneratedAddress::new(keypair, current_attempts, elapsed.as_millis() as u64);
+            if done(&rank, elapsed) {
+                finished.store(true, Ordering::Relaxed);
+            }
+            let mut guard = best.lock().unwrap();
+            if guard.accepts(&rank) {
+                guard.insert(address, rank);
+            }
+            false
+        });
+    });
+
+    Ok(best.into_inner().unwrap().into_sorted())
+}
+
+/// Parallel search over a counter, for candidates that aren't plain keypairs
+///
+/// `check` maps each counter value in `range` to a result, or `None` to keep
+/// going. Threads take counter values in any order, so the value found is
+/// not necessarily the smallest match.
+pub(crate) fn counter_search<T, F>(
+    config: &VanityGeneratorConfig,
+    progress: &SearchProgress,
+    range: Range<u64>,
+    check: F,
+) -> GeneratorResult<(u64, T)>
+where
+    T: Send,
+    F: Fn(u64) -> Option<T> + Sync,
+{
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(config.threads)
+        .build()
+        .map_err(|e| GeneratorError::ConfigError(e.to_string()))?;
+
+    let found = pool.install(|| {
+        range.into_par_iter().find_map_any(|counter| {
+            if progress.is_cancelled() {
+                // Ends the search; the caller reports cancellation
+                return Some(None);
+            }
+            progress.record_attempt();
+            check(counter).map(|result| Some((counter, result)))
+        })
+    });
+
+    match found {
+        Some(Some(found)) => Ok(found),
+        Some(None) => Err(GeneratorError::Cancelled),
+        None => Err(GeneratorError::Exhausted),
     }
 }
 
+/// Parallel search that fills a count of keys for each of several slots
+///
+/// `candidate` produces each key along with data passed on with it, or
+/// `None` to skip the attempt. A key that matches a slot still short of its
+/// count, and that `admit` accepts, is assigned to the first such slot and
+/// passed to `on_match` from the thread that found it. The search ends when
+/// every count is filled or `progress` is cancelled.
+pub(crate) fn slot_search<X, C, A, F>(
+    config: &VanityGeneratorConfig,
+    progress: &SearchProgress,
+    matchers: &[OptimizedMatcher],
+    counts: &[u64],
+    candidate: C,
+    admit: A,
+    on_match: F,
+) -> GeneratorResult<()>
+where
+    C: Fn() -> Option<(Keypair, X)> + Sync,
+    A: Fn(&str) -> bool + Sync,
+    F: Fn(usize, GeneratedAddress, X) + Sync,
+{
+    let start_time = Instant::now();
+    let remaining: Vec<AtomicU64> = counts.iter().map(|&count| AtomicU64::new(count)).collect();
+    let outstanding = AtomicU64::new(counts.iter().sum());
+
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(config.threads)
+        .build()
+        .map_err(|e| GeneratorError::ConfigError(e.to_string()))?;
+
+    pool.install(|| {
+        (0..usize::MAX).into_par_iter().find_any(|_| {
+            if progress.is_cancelled() || outstanding.load(Ordering::Relaxed) == 0 {
+                return true;
+            }
+
+            let Some((keypair, extra)) = candidate() else {
+                return false;
+            };
+            let pubkey = keypair.pubkey();
+            let current_attempts = progress.record_attempt();
+            let pubkey_str = pubkey.to_string();
+
+            let mut matching = (0..matchers.len()).filter(|&i| {
+                remaining[i].load(Ordering::Relaxed) > 0
+                    && matchers[i].matches_key(&pubkey.to_bytes())
+                    && matchers[i].matches(&pubkey_str)
+            });
+            let Some(first) = matching.next() else {
+                return false;
+            };
+
+            if !admit(&pubkey_str) {
+                return false;
+            }
+            if config.verify_keypairs {
+                if let Err(e) = verify_keypair_integrity(&keypair) {
+                    log::error!("Keypair verification failed: {}", e);
+                    return false;
+                }
+            }
+            // Another thread may have taken the last match since the check,
+            // in which case the key goes to the next slot it matches
+            let Some(slot) = std::iter::once(first).chain(matching).find(|&i| {
+                remaining[i]
+                    .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |r| r.checked_sub(1))
+                    .is_ok()
+            }) else {
+                return false;
+            };
+
+            let elapsed_ms = start_time.elapsed().as_millis() as u64;
+            on_match(slot, GeneratedAddress::new(keypair, current_attempts, elapsed_ms), extra);
+            outstanding.fetch_sub(1, Ordering::SeqCst) == 1
+        });
+    });
+
+    Ok(())
+}
+
 /// A simpler, single-threaded generator for testing
 pub fn generate_single(target: &MatchTarget) -> GeneratedAddress {
     let matcher = OptimizedMatcher::new(target.clone());
@@ -283,6 +818,7 @@ pub fn benchmark_generation_rate(duration_secs: u64) -> u64 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_support::config;
 
     #[test]
     fn test_generate_single_char_prefix() {
@@ -312,16 +848,25 @@ mod tests {
         assert!(matches!(result, Err(GeneratorError::Cancelled)));
     }
 
+    #[test]
+    fn test_shared_cancel_handle() {
+        let cancel = Arc::new(AtomicBool::new(false));
+        let target = MatchTarget::prefix("ZZZZZ", false).unwrap();
+        let first = VanityGenerator::with_target(target.clone()).unwrap().with_cancel_handle(Arc::clone(&cancel));
+        let second = VanityGenerator::with_target(target).unwrap().with_cancel_handle(Arc::clone(&cancel));
+
+        // One flag stops every generator built with it
+        cancel.store(true, Ordering::SeqCst);
+        assert!(first.is_cancelled() && second.is_cancelled());
+        assert!(matches!(second.generate(), Err(GeneratorError::Cancelled)));
+    }
+
     #[test]
     fn test_generator_with_callback() {
         use std::sync::atomic::AtomicBool;
 
         let target = MatchTarget::prefix("A", false).unwrap();
-        let config = VanityGeneratorConfig {
-            threads: 1,
-            verify_keypairs: true,
-            progress_interval: 10,
-        };
+        let config = VanityGeneratorConfig { threads: 1, progress_interval: 10, ..config() };
         let generator = VanityGenerator::new(target, config).unwrap();
 
         let callback_called = Arc::new(AtomicBool::new(false));
@@ -350,11 +895,7 @@ mod tests {
     #[test]
     fn test_keypair_verification() {
         let target = MatchTarget::prefix("A", false).unwrap();
-        let config = VanityGeneratorConfig {
-            threads: 1,
-            verify_keypairs: true,
-            progress_interval: 100_000,
-        };
+        let config = VanityGeneratorConfig { threads: 1, ..config() };
         let generator = VanityGenerator::new(target, config).unwrap();
 
         let result = generator.generate();
@@ -368,11 +909,7 @@ mod tests {
     #[test]
     fn test_multi_threaded_generation() {
         let target = MatchTarget::prefix("A", false).unwrap();
-        let config = VanityGeneratorConfig {
-            threads: 4,
-            verify_keypairs: true,
-            progress_interval: 100_000,
-        };
+        let config = VanityGeneratorConfig { threads: 4, ..config() };
         let generator = VanityGenerator::new(target, config).unwrap();
 
         let result = generator.generate();
@@ -380,6 +917,340 @@ mod tests {
         assert!(result.unwrap().public_key().starts_with('A'));
     }
 
+    #[test]
+    fn test_exclusions_drop_matches() {
+        let target = MatchTarget::prefix("A", false).unwrap();
+        // Forbid the second character being any digit, so some matches are dropped
+        let mut rules = ExclusionRules::new();
+        for digit in "123456789".chars() {
+            rules = rules.forbid_at(&digit.to_string(), 1, false);
+        }
+        let generator = VanityGenerator::new(target, config()).unwrap().with_exclusions(rules);
+
+        for _ in 0..5 {
+            let address = generator.generate().unwrap();
+            let second = address.public_key().chars().nth(1).unwrap();
+            assert!(!second.is_ascii_digit());
+        }
+        assert!(generator.exclusions().rule_count() == 9);
+    }
+
+    #[test]
+    fn test_estimate_difficulty_with_exclusions() {
+        let target = MatchTarget::prefix("AB", false).unwrap();
+        let generator = VanityGenerator::with_target(target.clone()).unwrap();
+        let strict = VanityGenerator::with_target(target)
+            .unwrap()
+            .with_exclusions(ExclusionRules::new().with_max_repeat_run(1));
+
+        assert!(strict.estimate_difficulty().0 > generator.estimate_difficulty().0 * 2.0);
+    }
+
+    #[test]
+    fn test_estimate_difficulty_nearly_all_rejected() {
+        let exclusions = "abcdefghijk"
+            .chars()
+            .fold(ExclusionRules::new().with_max_repeat_run(1), |rules, c| {
+                rules.forbid(&c.to_string(), true)
+            });
+        assert_eq!(exclusions.rejection_probability(), 1.0);
+
+        let generator = VanityGenerator::with_target(MatchTarget::prefix("AB", false).unwrap())
+            .unwrap()
+            .with_exclusions(exclusions);
+        let (attempts, probability) = generator.estimate_difficulty();
+        assert_eq!(probability, 0.0);
+        assert!(attempts.is_infinite());
+    }
+
+    #[test]
+    fn test_generate_raw_target() {
+        use crate::raw::{ByteConstraint, RawTarget};
+
+        let raw = RawTarget::new()
+            .with(ByteConstraint::shard("4:0x3").unwrap())
+            .unwrap();
+        let target = MatchTarget::raw(raw).unwrap();
+        let generator = VanityGenerator::with_target(target).unwrap();
+
+        let (attempts, probability) = generator.estimate_difficulty();
+        assert_eq!(probability, 1.0 / 16.0);
+        assert_eq!(attempts, 16.0);
+
+        let address = generator.generate().unwrap();
+        let key: solana_sdk::pubkey::Pubkey = address.public_key().parse().unwrap();
+        assert_eq!(key.to_bytes()[0] >> 4, 0x3);
+        assert!(address.details().iter().any(|(label, _)| label == "Key bytes"));
+    }
+
+    #[test]
+    fn test_derived_constraints_must_hold() {
+        use crate::derived::{Derivation, DerivedConstraint};
+
+        let target = MatchTarget::prefix("A", false).unwrap();
+        let constraint = DerivedConstraint::new(
+            "programdata",
+            Derivation::ProgramData,
+            MatchTarget::suffix("z", false).unwrap(),
+        );
+        let generator = VanityGenerator::with_target(target).unwrap().with_derived(constraint);
+
+        let (_, probability) = generator.estimate_difficulty();
+        assert!((probability - 1.0 / (58.0 * 58.0)).abs() < 1e-12);
+
+        let address = generator.generate().unwrap();
+        assert!(address.public_key().starts_with('A'));
+        let (label, derived) = &address.derived()[0];
+        assert_eq!(label, "programdata");
+        assert!(derived.ends_with('z'));
+
+        let key: solana_sdk::pubkey::Pubkey = address.public_key().parse().unwrap();
+        assert_eq!(derived, &Derivation::ProgramData.derive(&key).to_string());
+    }
+
+    #[test]
+    fn test_preflight_skips_existing_accounts() {
+        use crate::rpc::{mock, RpcClient};
+
+        let (url, served) = mock::serve(2);
+        let preflight = Preflight::new(RpcClient::new(&url).unwrap(), PreflightPolicy::Reject);
+        let target = MatchTarget::prefix("A", false).unwrap();
+        let config = VanityGeneratorConfig { threads: 1, ..config() };
+        let generator = VanityGenerator::new(target, config).unwrap().with_preflight(preflight);
+
+        let address = generator.generate().unwrap();
+        assert!(address.public_key().starts_with('A'));
+        assert_eq!(generator.preflight_rejected(), 2);
+        assert_eq!(served.load(Ordering::SeqCst), 3);
+        assert!(address.details().iter().all(|(label, _)| !label.starts_with("Skipped")));
+    }
+
+    #[test]
+    fn test_preflight_flag_lists_existing_accounts() {
+        use crate::rpc::{mock, RpcClient};
+
+        let (url, served) = mock::serve(2);
+        let preflight = Preflight::new(RpcClient::new(&url).unwrap(), PreflightPolicy::Flag);
+        let target = MatchTarget::prefix("A", false).unwrap();
+        let config = VanityGeneratorConfig { threads: 1, ..config() };
+        let generator = VanityGenerator::new(target, config).unwrap().with_preflight(preflight);
+
+        // Flagged addresses are skipped too, and listed on the unused match
+        let address = generator.generate().unwrap();
+        assert_eq!(generator.preflight_rejected(), 2);
+        assert_eq!(served.load(Ordering::SeqCst), 3);
+        let skipped: Vec<_> = address
+            .details()
+            .iter()
+            .filter(|(label, _)| label == "Skipped (account exists)")
+            .collect();
+        assert_eq!(skipped.len(), 2);
+        assert!(skipped[0].1.contains("1000 lamports"));
+        assert!(!skipped.iter().any(|(_, value)| value.starts_with(&address.public_key())));
+    }
+
+    #[test]
+    fn test_preflight_failure_stops_search() {
+        use crate::rpc::RpcClient;
+
+        let port = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+        let client = RpcClient::new(&format!("http://127.0.0.1:{port}")).unwrap();
+        for policy in [PreflightPolicy::Reject, PreflightPolicy::Flag] {
+            let target = MatchTarget::prefix("A", false).unwrap();
+            let config = VanityGeneratorConfig { threads: 1, ..config() };
+            let generator = VanityGenerator::new(target, config)
+                .unwrap()
+                .with_preflight(Preflight::new(client.clone(), policy));
+            assert!(matches!(generator.generate(), Err(GeneratorError::PreflightFailed(_))));
+        }
+    }
+
+    #[test]
+    fn test_preflight_failure_keeps_key_on_request() {
+        use crate::rpc::RpcClient;
+
+        let port = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+        let client = RpcClient::new(&format!("http://127.0.0.1:{port}")).unwrap();
+        let target = MatchTarget::prefix("A", false).unwrap();
+        let config = VanityGeneratorConfig { threads: 1, ..config() };
+        let generator = VanityGenerator::new(target, config)
+            .unwrap()
+            .with_preflight(Preflight::new(client, PreflightPolicy::Reject).with_keep_unchecked(true));
+
+        // With the opt-in, an unreachable endpoint doesn't cost the match
+        let address = generator.generate().unwrap();
+        assert!(address.public_key().starts_with('A'));
+        assert!(generator.preflight_error().is_some());
+        let (_, note) = address.details().iter().find(|(label, _)| label == "Pre-flight").unwrap();
+        assert!(note.starts_with("not checked"));
+    }
+
+    #[test]
+    fn test_relax_schedule() {
+        let schedule = RelaxSchedule {
+            step: Duration::from_secs(60),
+            min_score: 4,
+        };
+        assert_eq!(schedule.required_score(7, Duration::from_secs(0)), 7);
+        assert_eq!(schedule.required_score(7, Duration::from_secs(125)), 5);
+        assert_eq!(schedule.required_score(7, Duration::from_secs(3600)), 4);
+        // The floor never exceeds the full pattern length
+        assert_eq!(schedule.required_score(3, Duration::from_secs(0)), 3);
+    }
+
+    #[test]
+    fn test_best_effort_finds_partial_matches() {
+        let target = MatchTarget::prefix("ABCDEFGH", false).unwrap();
+        let generator = VanityGenerator::new(target, config()).unwrap();
+        let options = BestEffortConfig {
+            budget: Duration::from_millis(300),
+            keep: 3,
+            schedule: None,
+        };
+
+        let found = generator.generate_best_effort(&options).unwrap();
+        assert!(!found.is_empty() && found.len() <= 3);
+        assert!(found.windows(2).all(|w| w[0].score >= w[1].score));
+        for m in &found {
+            assert!(m.address.public_key().starts_with('A'));
+            assert_eq!(m.score.prefix, m.score.total());
+        }
+    }
+
+    #[test]
+    fn test_best_effort_schedule_stops_early() {
+        let target = MatchTarget::suffix("ZZZZZZZZ", false).unwrap();
+        let generator = VanityGenerator::with_target(target).unwrap();
+        let options = BestEffortConfig {
+            budget: Duration::from_secs(30),
+            keep: 1,
+            schedule: Some(RelaxSchedule {
+                step: Duration::ZERO,
+                min_score: 1,
+            }),
+        };
+
+        let start = Instant::now();
+        let found = generator.generate_best_effort(&options).unwrap();
+        assert!(start.elapsed() < Duration::from_secs(10));
+        assert!(found[0].address.public_key().ends_with('Z'));
+    }
+
+    #[test]
+    fn test_best_effort_rejects_structural_targets() {
+        let target =
+            MatchTarget::aesthetic(vec![crate::aesthetic::PatternClass::DigitRun(3)]).unwrap();
+        let generator = VanityGenerator::with_target(target).unwrap();
+        let options = BestEffortConfig {
+            budget: Duration::from_millis(10),
+            keep: 1,
+            schedule: None,
+        };
+        assert!(matches!(
+            generator.generate_best_effort(&options),
+            Err(GeneratorError::ConfigError(_))
+        ));
+
+        // Partial matches can't be checked on chain
+        let client = crate::rpc::RpcClient::new("http://127.0.0.1:1").unwrap();
+        let generator = VanityGenerator::with_target(MatchTarget::prefix("A", false).unwrap())
+            .unwrap()
+            .with_preflight(Preflight::new(client, PreflightPolicy::Reject));
+        assert!(matches!(
+            generator.generate_best_effort(&options),
+            Err(GeneratorError::ConfigError(_))
+        ));
+    }
+
+    #[test]
+    fn test_counter_search() {
+        let config = config();
+        let progress = SearchProgress::default();
+
+        let (counter, value) =
+            counter_search(&config, &progress, 100..u64::MAX, |n| (n % 1000 == 7).then_some(n ^ 1))
+                .unwrap();
+        assert_eq!(counter % 1000, 7);
+        assert!(counter >= 100);
+        assert_eq!(value, counter ^ 1);
+        assert!(progress.attempts.load(Ordering::Relaxed) > 0);
+
+        progress.cancelled.store(true, Ordering::SeqCst);
+        assert!(matches!(
+            counter_search(&config, &progress, 0..u64::MAX, |_| Some(())),
+            Err(GeneratorError::Cancelled)
+        ));
+
+        progress.cancelled.store(false, Ordering::SeqCst);
+        assert!(matches!(
+            counter_search(&config, &progress, 0..50, |_| None::<()>),
+            Err(GeneratorError::Exhausted)
+        ));
+    }
+
+    #[test]
+    fn test_top_k_keeps_best() {
+        let mut top = TopK::new(2);
+        for rank in [3, 1, 5, 4] {
+            if top.accepts(&rank) {
+                top.insert(GeneratedAddress::new(Keypair::new(), 1, 0), rank);
+            }
+        }
+        let ranks: Vec<i32> = top.into_sorted().into_iter().map(|(_, r)| r).collect();
+        assert_eq!(ranks, vec![5, 4]);
+    }
+
+    #[test]
+    fn test_ranked_search_respects_budget() {
+        let config = config();
+        let progress = SearchProgress::default();
+
+        let start = Instant::now();
+        let found = ranked_search(
+            &config,
+            &progress,
+            Duration::from_millis(200),
+            4,
+            |address: &str| address.starts_with('A').then_some(address.len()),
+            |_, _| false,
+        )
+        .unwrap();
+
+        assert!(start.elapsed() < Duration::from_secs(5));
+        assert!(found.len() <= 4);
+        assert!(found.iter().all(|(a, _)| a.public_key().starts_with('A')));
+        assert!(progress.attempts.load(Ordering::Relaxed) > 0);
+    }
+
+    #[test]
+    fn test_slot_search_fills_counts() {
+        let config = config();
+        let progress = SearchProgress::default();
+        // Every key matching "A" also matches "a" ignoring case, so the
+        // second slot only gets keys once the first is full
+        let matchers = vec![
+            OptimizedMatcher::new(MatchTarget::prefix("A", false).unwrap()),
+            OptimizedMatcher::new(MatchTarget::prefix("a", true).unwrap()),
+        ];
+        let found = std::sync::Mutex::new(Vec::new());
+
+        slot_search(
+            &config,
+            &progress,
+            &matchers,
+            &[2, 3],
+            || Some((Keypair::new(), ())),
+            |address| !address.starts_with("a"),
+            |slot, address, ()| found.lock().unwrap().push((slot, address.public_key())),
+        )
+        .unwrap();
+
+        let found = found.into_inner().unwrap();
+        assert_eq!(found.iter().filter(|(slot, _)| *slot == 0).count(), 2);
+        assert_eq!(found.iter().filter(|(slot, _)| *slot == 1).count(), 3);
+        assert!(found.iter().all(|(_, address)| address.starts_with('A')));
+    }
+
     #[test]
     fn test_benchmark_generation_rate() {
         let rate = benchmark_generation_rate(1);
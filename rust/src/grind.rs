@@ -0,0 +1,422 @@
+//! `solana-keygen grind` compatible search.
+//!
+//! Specs use keygen's syntax: `PREFIX:COUNT` for `--starts-with`,
+//! `SUFFIX:COUNT` for `--ends-with` and `PREFIX:SUFFIX:COUNT` for
+//! `--starts-and-ends-with`. Like keygen, every key is checked against all
+//! specs that still need matches, and with a mnemonic the key is the BIP39
+//! seed itself unless a derivation path is given.
+
+use crate::config::{validate_prefix, validate_suffix, ConfigError};
+use crate::generator::{slot_search, GeneratorError, GeneratorResult, ParallelSearch, SearchProgress, VanityGeneratorConfig};
+use crate::matcher::{MatchTarget, OptimizedMatcher};
+use crate::mnemonic::{write_phrase_file, WordCount};
+use crate::output::{GeneratedAddress, OutputResult};
+use crate::security::verify_rng_quality;
+use crate::series::expected_attempts_to_fill_counts;
+use bip39::{Language, Mnemonic, Seed};
+use solana_sdk::derivation_path::DerivationPath;
+use solana_sdk::signature::{keypair_from_seed, keypair_from_seed_and_derivation_path, Keypair};
+use std::path::Path;
+use zeroize::Zeroizing;
+
+/// Derivation path used when `--derivation-path` is given without a value
+pub const DEFAULT_DERIVATION_PATH: &str = "m/44'/501'/0'/0'";
+
+/// One `--starts-with`, `--ends-with` or `--starts-and-ends-with` spec
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GrindSpec {
+    /// Required prefix
+    pub prefix: Option<String>,
+    /// Required suffix
+    pub suffix: Option<String>,
+    /// Number of keypairs to find
+    pub count: u64,
+}
+
+impl GrindSpec {
+    /// Parse a `--starts-with` value: `PREFIX:COUNT`
+    pub fn starts_with(spec: &str) -> Result<Self, ConfigError> {
+        let (prefix, count) = split_count(spec)?;
+        validate_prefix(prefix).map_err(|e| invalid(spec, e))?;
+        Ok(Self {
+            prefix: Some(prefix.to_string()),
+            suffix: None,
+            count,
+        })
+    }
+
+    /// Parse an `--ends-with` value: `SUFFIX:COUNT`
+    pub fn ends_with(spec: &str) -> Result<Self, ConfigError> {
+        let (suffix, count) = split_count(spec)?;
+        validate_suffix(suffix).map_err(|e| invalid(spec, e))?;
+        Ok(Self {
+            prefix: None,
+            suffix: Some(suffix.to_string()),
+            count,
+        })
+    }
+
+    /// Parse a `--starts-and-ends-with` value: `PREFIX:SUFFIX:COUNT`
+    pub fn starts_and_ends_with(spec: &str) -> Result<Self, ConfigError> {
+        let (patterns, count) = split_count(spec)?;
+        let (prefix, suffix) = patterns.split_once(':').ok_or_else(|| {
+            ConfigError::InvalidGrindSpec(format!("'{spec}' should be PREFIX:SUFFIX:COUNT"))
+        })?;
+        validate_prefix(prefix).map_err(|e| invalid(spec, e))?;
+        validate_suffix(suffix).map_err(|e| invalid(spec, e))?;
+        Ok(Self {
+            prefix: Some(prefix.to_string()),
+            suffix: Some(suffix.to_string()),
+            count,
+        })
+    }
+
+    /// The match target for one key of this spec
+    pub fn target(&self, ignore_case: bool) -> MatchTarget {
+        // Patterns were validated when the spec was parsed
+        match (&self.prefix, &self.suffix) {
+            (Some(p), Some(s)) => MatchTarget::both(p, s, ignore_case),
+            (Some(p), None) => MatchTarget::prefix(p, ignore_case),
+            (None, Some(s)) => MatchTarget::suffix(s, ignore_case),
+            (None, None) => unreachable!("grind specs have a pattern"),
+        }
+        .expect("grind spec patterns are validated")
+    }
+
+    /// Human-readable description, in keygen's wording
+    pub fn description(&self) -> String {
+        format!(
+            "{} pubkey(s) that start with '{}' and end with '{}'",
+            self.count,
+            self.prefix.as_deref().unwrap_or_default(),
+            self.suffix.as_deref().unwrap_or_default()
+        )
+    }
+}
+
+fn split_count(spec: &str) -> Result<(&str, u64), ConfigError> {
+    let (pattern, count) = spec.rsplit_once(':').ok_or_else(|| {
+        ConfigError::InvalidGrindSpec(format!("'{spec}' is missing a :COUNT"))
+    })?;
+    match count.parse::<u64>() {
+        Ok(count) if count > 0 => Ok((pattern, count)),
+        _ => Err(ConfigError::InvalidGrindSpec(format!(
+            "'{spec}' needs a count of at least 1"
+        ))),
+    }
+}
+
+fn invalid(spec: &str, e: ConfigError) -> ConfigError {
+    ConfigError::InvalidGrindSpec(format!("'{spec}': {e}"))
+}
+
+/// A keypair found for one spec
+pub struct GrindMatch {
+    /// Index of the spec it matched
+    pub spec: usize,
+    /// The keypair and search stats
+    pub address: GeneratedAddress,
+    /// Derivation path, when a mnemonic key was derived from one
+    pub derivation_path: Option<String>,
+    phrase: Option<Zeroizing<String>>,
+}
+
+impl GrindMatch {
+    /// The seed phrase (secret), in mnemonic mode
+    pub fn phrase(&self) -> Option<&str> {
+        self.phrase.as_deref().map(String::as_str)
+    }
+
+    /// Write the seed phrase with restricted permissions, if there is one
+    pub fn write_phrase(&self, path: &Path) -> OutputResult<bool> {
+        let Some(phrase) = self.phrase() else {
+            return Ok(false);
+        };
+        let derivation = self
+            .derivation_path
+            .as_deref()
+            .unwrap_or("none (seed used directly, restore with `solana-keygen recover prompt://`)");
+        write_phrase_file(phrase, derivation, false, path)?;
+        Ok(true)
+    }
+}
+
+struct GrindMnemonic {
+    word_count: WordCount,
+    path: Option<(String, DerivationPath)>,
+}
+
+/// Parallel search that finds keypairs for every grind spec
+pub struct GrindSearch {
+    config: VanityGeneratorConfig,
+    specs: Vec<GrindSpec>,
+    total: u64,
+    matchers: Vec<OptimizedMatcher>,
+    mnemonic: Option<GrindMnemonic>,
+    progress: SearchProgress,
+}
+
+impl GrindSearch {
+    /// Create a search for plain keypairs
+    pub fn new(
+        specs: Vec<GrindSpec>,
+        ignore_case: bool,
+        config: VanityGeneratorConfig,
+    ) -> GeneratorResult<Self> {
+        if specs.is_empty() {
+            return Err(GeneratorError::ConfigError(
+                ConfigError::NoPatternSpecified.to_string(),
+            ));
+        }
+        let total = specs
+            .iter()
+            .try_fold(0u64, |total, spec| total.checked_add(spec.count))
+            .ok_or_else(|| {
+                GeneratorError::ConfigError(
+                    ConfigError::InvalidGrindSpec("the counts add up to more than 2^64 keypairs".to_string())
+                        .to_string(),
+                )
+            })?;
+        verify_rng_quality().map_err(|e| GeneratorError::RngError(e.to_string()))?;
+
+        let matchers = specs
+            .iter()
+            .map(|spec| OptimizedMatcher::new(spec.target(ignore_case)))
+            .collect();
+        Ok(Self {
+            config,
+            specs,
+            total,
+            matchers,
+            mnemonic: None,
+            progress: SearchProgress::default(),
+        })
+    }
+
+    /// Derive each candidate from a fresh mnemonic with no BIP39 passphrase
+    ///
+    /// Without `derivation_path` the first 32 bytes of the seed are the key,
+    /// as with `solana-keygen grind --use-mnemonic`.
+    pub fn with_mnemonic(
+        mut self,
+        word_count: WordCount,
+        derivation_path: Option<&str>,
+    ) -> Result<Self, ConfigError> {
+        let path = derivation_path
+            .map(|text| {
+                DerivationPath::from_absolute_path_str(text)
+                    .map(|path| (text.to_string(), path))
+                    .map_err(|e| ConfigError::InvalidMnemonic(e.to_string()))
+            })
+            .transpose()?;
+        self.mnemonic = Some(GrindMnemonic { word_count, path });
+        Ok(self)
+    }
+
+    /// The specs being searched
+    pub fn specs(&self) -> &[GrindSpec] {
+        &self.specs
+    }
+
+    /// Total number of keypairs wanted across all specs
+    pub fn total_count(&self) -> u64 {
+        self.total
+    }
+
+    /// Expected attempts to find every keypair
+    pub fn expected_attempts(&self) -> f64 {
+        let groups = self
+            .specs
+            .iter()
+            .zip(&self.matchers)
+            .map(|(spec, matcher)| (matcher.target().probability(), spec.count))
+            .collect();
+        expected_attempts_to_fill_counts(groups)
+    }
+
+    /// Generate one candidate key, with its mnemonic in mnemonic mode
+    fn candidate(&self) -> Option<(Keypair, Option<Mnemonic>)> {
+        let Some(ref settings) = self.mnemonic else {
+            return Some((Keypair::new(), None));
+        };
+        let mnemonic = Mnemonic::new(settings.word_count.mnemonic_type(), Language::English);
+        let seed = Seed::new(&mnemonic, "");
+        let keypair = match settings.path {
+            Some((_, ref path)) => {
+                keypair_from_seed_and_derivation_path(seed.as_bytes(), Some(path.clone()))
+            }
+            None => keypair_from_seed(seed.as_bytes()),
+        }
+        .ok()?;
+        Some((keypair, Some(mnemonic)))
+    }
+
+    /// Search until every spec has its count or the search is cancelled
+    ///
+    /// Each key counts toward the first spec it matches and is passed to
+    /// `on_match` as soon as it is found, from the thread that found it.
+    pub fn run<F>(&self, on_match: F) -> GeneratorResult<()>
+    where
+        F: Fn(GrindMatch) + Sync,
+    {
+        let counts: Vec<u64> = self.specs.iter().map(|spec| spec.count).collect();
+        let derivation_path = self
+            .mnemonic
+            .as_ref()
+            .and_then(|settings| settings.path.as_ref())
+            .map(|(text, _)| text.clone());
+
+        slot_search(
+            &self.config,
+            &self.progress,
+            &self.matchers,
+            &counts,
+            || self.candidate(),
+            |_| true,
+            |spec, mut address, mnemonic| {
+                for (label, value) in self.matchers[spec].match_details(&address.public_key()) {
+                    address.add_detail(label, value);
+                }
+                on_match(GrindMatch {
+                    spec,
+                    address,
+                    derivation_path: derivation_path.clone(),
+                    phrase: mnemonic.map(|m| Zeroizing::new(m.phrase().to_string())),
+                });
+            },
+        )
+    }
+}
+
+impl ParallelSearch for GrindSearch {
+    fn progress(&self) -> &SearchProgress {
+        &self.progress
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::config;
+    use solana_sdk::signer::Signer;
+    use std::sync::Mutex;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_parse_specs() {
+        let spec = GrindSpec::starts_with("AB:3").unwrap();
+        assert_eq!((spec.prefix.as_deref(), spec.suffix.as_deref(), spec.count), (Some("AB"), None, 3));
+
+        let spec = GrindSpec::ends_with("pump:1").unwrap();
+        assert_eq!((spec.prefix.as_deref(), spec.suffix.as_deref(), spec.count), (None, Some("pump"), 1));
+
+        let spec = GrindSpec::starts_and_ends_with("X:Y:2").unwrap();
+        assert_eq!((spec.prefix.as_deref(), spec.suffix.as_deref(), spec.count), (Some("X"), Some("Y"), 2));
+        assert_eq!(spec.description(), "2 pubkey(s) that start with 'X' and end with 'Y'");
+
+        assert!(GrindSpec::starts_with("AB").is_err());
+        assert!(GrindSpec::starts_with("AB:0").is_err());
+        assert!(GrindSpec::starts_with("AB:x").is_err());
+        assert!(GrindSpec::starts_with("A0:1").is_err());
+        assert!(GrindSpec::ends_with(":1").is_err());
+        assert!(GrindSpec::starts_and_ends_with("X:2").is_err());
+    }
+
+    /// Run a search to completion, collecting its matches
+    fn run(search: &GrindSearch) -> Vec<GrindMatch> {
+        let found = Mutex::new(Vec::new());
+        search.run(|m| found.lock().unwrap().push(m)).unwrap();
+        found.into_inner().unwrap()
+    }
+
+    #[test]
+    fn test_search_fills_every_spec() {
+        let specs = vec![
+            GrindSpec::starts_with("A:2").unwrap(),
+            GrindSpec::ends_with("b:1").unwrap(),
+        ];
+        let search = GrindSearch::new(specs, false, config()).unwrap();
+        let found = run(&search);
+
+        assert_eq!(found.len(), 3);
+        assert_eq!(found.iter().filter(|m| m.spec == 0).count(), 2);
+        assert_eq!(found.iter().filter(|m| m.spec == 1).count(), 1);
+        for m in &found {
+            let pubkey = m.address.public_key();
+            assert!(if m.spec == 0 { pubkey.starts_with('A') } else { pubkey.ends_with('b') });
+            assert!(m.phrase().is_none());
+        }
+    }
+
+    #[test]
+    fn test_ignore_case() {
+        let specs = vec![GrindSpec::starts_with("ab:1").unwrap()];
+        let found = run(&GrindSearch::new(specs, true, config()).unwrap());
+        assert!(found[0].address.public_key().to_lowercase().starts_with("ab"));
+    }
+
+    #[test]
+    fn test_mnemonic_keys_restore_like_keygen() {
+        let dir = tempdir().unwrap();
+        let specs = vec![GrindSpec::starts_with("A:1").unwrap()];
+        let search = GrindSearch::new(specs.clone(), false, config())
+            .unwrap()
+            .with_mnemonic(WordCount::Twelve, None)
+            .unwrap();
+        let found = run(&search);
+
+        // No derivation path: the key is the start of the BIP39 seed
+        let m = &found[0];
+        let mnemonic = Mnemonic::from_phrase(m.phrase().unwrap(), Language::English).unwrap();
+        let restored = keypair_from_seed(Seed::new(&mnemonic, "").as_bytes()).unwrap();
+        assert_eq!(restored.pubkey().to_string(), m.address.public_key());
+
+        let path = dir.path().join("key.mnemonic.txt");
+        assert!(m.write_phrase(&path).unwrap());
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert!(contents.starts_with(m.phrase().unwrap()));
+
+        // With a derivation path the key is derived along it
+        let search = GrindSearch::new(specs, false, config())
+            .unwrap()
+            .with_mnemonic(WordCount::Twelve, Some(DEFAULT_DERIVATION_PATH))
+            .unwrap();
+        let found = run(&search);
+        let m = &found[0];
+        assert_eq!(m.derivation_path.as_deref(), Some(DEFAULT_DERIVATION_PATH));
+        let mnemonic = Mnemonic::from_phrase(m.phrase().unwrap(), Language::English).unwrap();
+        let restored = crate::mnemonic::keypair_from_mnemonic(&mnemonic, "", 0, 0).unwrap();
+        assert_eq!(restored.pubkey().to_string(), m.address.public_key());
+    }
+
+    #[test]
+    fn test_invalid_derivation_path() {
+        let specs = vec![GrindSpec::starts_with("A:1").unwrap()];
+        let search = GrindSearch::new(specs, false, config()).unwrap();
+        assert!(search.with_mnemonic(WordCount::Twelve, Some("44'/501'")).is_err());
+    }
+
+    #[test]
+    fn test_expected_attempts() {
+        let specs = vec![GrindSpec::starts_with("A:3").unwrap()];
+        let search = GrindSearch::new(specs, false, config()).unwrap();
+        let expected = 58.0 * (1.0 / 3.0 + 1.0 / 2.0 + 1.0);
+        assert!((search.expected_attempts() - expected).abs() < 1e-6);
+        assert_eq!(search.total_count(), 3);
+    }
+
+    #[test]
+    fn test_huge_counts() {
+        // The estimate doesn't allocate a slot per key
+        let specs = vec![GrindSpec::starts_with(&format!("A:{}", u64::MAX)).unwrap()];
+        let search = GrindSearch::new(specs, false, config()).unwrap();
+        assert!(search.expected_attempts().is_finite());
+
+        // Counts whose total overflows are rejected
+        let specs = vec![
+            GrindSpec::starts_with(&format!("A:{}", u64::MAX)).unwrap(),
+            GrindSpec::ends_with("b:1").unwrap(),
+        ];
+        assert!(matches!(GrindSearch::new(specs, false, config()), Err(GeneratorError::ConfigError(_))));
+    }
+}
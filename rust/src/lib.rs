@@ -3,7 +3,8 @@
 //! A high-performance, secure vanity address generator for Solana.
 //!
 //! This library provides functionality to generate Solana keypairs
-//! whose public addresses match specified patterns (prefixes, suffixes, or both).
+//! whose public addresses match specified patterns (prefixes, suffixes, or both),
+//! or that start or end with any word from a dictionary.
 //!
 //! # Features
 //!
@@ -31,22 +32,73 @@
 //! Secret keys are handled securely and zeroized when dropped.
 //! Generated keypair files are written with restricted permissions (0600 on Unix).
 
+pub mod aesthetic;
+pub mod batch;
 pub mod config;
+pub mod derived;
+pub mod events;
+pub mod exclusion;
+pub mod exit;
+pub mod export;
 pub mod generator;
+pub mod grind;
+pub mod lookalike;
 pub mod matcher;
+pub mod mnemonic;
 pub mod output;
+pub mod pda;
+pub mod pump;
+pub mod raw;
+pub mod rpc;
 pub mod security;
+pub mod seeded;
+pub mod series;
+pub mod settings;
+pub mod wordlist;
+
+#[cfg(test)]
+mod test_support;
 
 // Re-export main types for convenience
+pub use aesthetic::PatternClass;
+pub use batch::{BatchSearch, BatchState, JobFile};
 pub use config::{ConfigError, GeneratorConfig, BASE58_ALPHABET};
-pub use generator::{GeneratorError, VanityGenerator, VanityGeneratorConfig};
-pub use matcher::{is_valid_base58_char, MatchTarget, OptimizedMatcher};
+pub use derived::{Derivation, DerivedConstraint};
+pub use events::{AddressResult, Event, EventFormat, EventWriter, ResultFormat};
+pub use exclusion::{ExclusionRules, ExclusionViolation};
+pub use exit::ExitCode;
+pub use export::{export_key, import_keypair, import_public_key, write_export, ExportError, KeyFormat};
+pub use generator::{
+    BestEffortConfig, GeneratorError, ParallelSearch, PartialMatch, RelaxSchedule, SearchProgress,
+    VanityGenerator, VanityGeneratorConfig,
+};
+pub use grind::{GrindMatch, GrindSearch, GrindSpec};
+pub use lookalike::ConfusableTable;
+pub use matcher::{
+    is_valid_base58_char, CaseRule, CharSet, LookalikeTarget, MatchScore, MatchTarget,
+    OptimizedMatcher, Segment, SegmentTarget, Substitution,
+};
+pub use mnemonic::{MnemonicAddress, MnemonicSearch, PathMatch, PathScan, WordCount};
 pub use output::{
-    default_output_path, print_result, write_keypair_file, write_report, GeneratedAddress,
-    OutputError, OutputFormat, VerificationReport,
+    default_output_path, print_result, write_keypair_file, write_report, GeneratedAddress, KeypairEncoding,
+    OutputError, OutputFields, OutputTemplate, VerificationReport,
 };
+pub use pda::{PdaDerivation, PdaMatch, PdaSearch};
+pub use pump::{
+    CreateTokenArgs, PumpError, PumpMintDescriptor, TxLifetime, PUMP_MINT_SUFFIX, PUMP_PROGRAM_ID,
+};
+pub use raw::{ByteConstraint, RawTarget};
+pub use rpc::{Preflight, PreflightPolicy, RpcClient, RpcError};
 pub use security::{
-    secure_write_file, verify_keypair_integrity, verify_rng_quality, SecureBytes, SecurityError,
+    secure_write_file, validate_output_path, verify_keypair_integrity, verify_rng_quality, SecureBytes,
+    SecurityError,
+};
+pub use seeded::{SeedSearch, SeededMatch};
+pub use series::{Series, SeriesManifest, SeriesMatch, SeriesSearch};
+pub use settings::{ConfigFile, Settings};
+pub use wordlist::{
+    DictionaryMatch, DictionarySearch, WordMatch, WordPosition, Wordlist, WordlistError,
+    WordlistMatcher,
 };
 
 /// Library version
@@ -108,6 +160,36 @@ pub fn generate_with_suffix(
     generator.generate()
 }
 
+/// Convenience function to generate a pump.fun mint keypair
+///
+/// The address ends in `pump` (case-sensitive) and, if given, starts with
+/// `ticker` (case-sensitive). The bonding curve accounts derived from the
+/// mint are recorded on the result.
+///
+/// # Arguments
+/// * `ticker` - Optional prefix to match
+/// * `threads` - Number of threads (0 = auto-detect)
+///
+/// # Returns
+/// The generated mint, or an error
+pub fn generate_pump_mint(
+    ticker: Option<&str>,
+    threads: usize,
+) -> Result<GeneratedAddress, GeneratorError> {
+    let target = pump::pump_mint_target(ticker, false)
+        .map_err(|e| GeneratorError::ConfigError(e.to_string()))?;
+
+    let config = VanityGeneratorConfig {
+        threads: if threads == 0 { num_cpus::get() } else { threads },
+        ..Default::default()
+    };
+
+    let generator = VanityGenerator::new(target, config)?;
+    let mut address = generator.generate()?;
+    pump::add_pump_addresses(&mut address);
+    Ok(address)
+}
+
 /// Validate a pattern for use as a prefix or suffix
 ///
 /// Returns Ok(()) if the pattern is valid, or an error describing the problem.
@@ -133,6 +215,12 @@ mod tests {
         assert!(result.unwrap().public_key().ends_with('1'));
     }
 
+    #[test]
+    fn test_generate_pump_mint_rejects_invalid_ticker() {
+        // A real search takes ~58^4 attempts; only the validation is exercised here
+        assert!(generate_pump_mint(Some("0OIl"), 1).is_err());
+    }
+
     #[test]
     fn test_validate_pattern() {
         assert!(validate_pattern("ABC").is_ok());
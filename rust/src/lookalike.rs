@@ -0,0 +1,205 @@
+//! Confusable character tables for lookalike matching.
+//!
+//! Base58 leaves out `0`, `O`, `I` and `l`, but users keep typing them.
+//! A [`ConfusableTable`] maps each intended character onto Base58 characters
+//! that look like it, so a lookalike target can accept any of them.
+
+use crate::config::ConfigError;
+use crate::matcher::{is_valid_base58_char, suggest_valid_chars, CharSet};
+use std::collections::BTreeMap;
+
+/// The characters Base58 leaves out, which get built-in lookalikes
+const EXCLUDED_CHARS: [char; 4] = ['0', 'O', 'I', 'l'];
+
+/// Suggestions left out of the built-in table: `i` and `j` only pass for
+/// `l` in some fonts, so they are left to custom mappings
+const NOT_BUILTIN: &[(char, &str)] = &[('l', "ij")];
+
+/// Optional "leet" digit substitutions
+const LEET_CONFUSABLES: &[(char, &str)] = &[
+    ('A', "4"),
+    ('B', "8"),
+    ('E', "3"),
+    ('G', "6"),
+    ('S', "5"),
+    ('T', "7"),
+    ('Z', "2"),
+];
+
+/// Mapping from intended characters to the Base58 characters accepted in their place
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConfusableTable {
+    entries: BTreeMap<char, CharSet>,
+}
+
+impl ConfusableTable {
+    /// Create an empty table (every character only matches itself)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a table with the built-in lookalikes for `0`, `O`, `I` and `l`
+    ///
+    /// These are the valid Base58 characters among [`suggest_valid_chars`],
+    /// except that `l` only accepts `L` and `1`.
+    pub fn builtin() -> Self {
+        let mut table = Self::new();
+        for intended in EXCLUDED_CHARS {
+            let left_out = NOT_BUILTIN
+                .iter()
+                .find(|(c, _)| *c == intended)
+                .map_or("", |(_, chars)| *chars);
+            let lookalikes: String = suggest_valid_chars(intended)
+                .into_iter()
+                .filter(|c| is_valid_base58_char(*c) && !left_out.contains(*c))
+                .collect();
+            table.add(intended, &lookalikes);
+        }
+        table
+    }
+
+    /// Add the leet digit substitutions (E→3, A→4, ...) for upper and lower case
+    pub fn with_leet(mut self) -> Self {
+        for (intended, lookalikes) in LEET_CONFUSABLES {
+            self.add(*intended, lookalikes);
+            self.add(intended.to_ascii_lowercase(), lookalikes);
+        }
+        self
+    }
+
+    /// Add lookalikes for an intended character
+    ///
+    /// Every lookalike must be a valid Base58 character.
+    pub fn insert(&mut self, intended: char, lookalikes: &str) -> Result<(), ConfigError> {
+        if lookalikes.is_empty() {
+            return Err(ConfigError::InvalidConfusable(format!(
+                "no lookalikes given for '{intended}'"
+            )));
+        }
+        if let Some(c) = lookalikes.chars().find(|c| !is_valid_base58_char(*c)) {
+            return Err(ConfigError::InvalidBase58Character(c));
+        }
+        self.add(intended, lookalikes);
+        Ok(())
+    }
+
+    /// Parse and add a `X=abc` mapping, as given on the command line
+    pub fn insert_spec(&mut self, spec: &str) -> Result<(), ConfigError> {
+        let mut chars = spec.chars();
+        match (chars.next(), chars.next()) {
+            (Some(intended), Some('=')) => self.insert(intended, chars.as_str()),
+            _ => Err(ConfigError::InvalidConfusable(format!(
+                "expected X=abc, got '{spec}'"
+            ))),
+        }
+    }
+
+    /// Characters accepted in place of `intended`
+    ///
+    /// The set contains `intended` itself when it is valid Base58, plus its
+    /// lookalikes. With `case_insensitive`, lookalikes of the other-case
+    /// form are included and every letter is case-folded.
+    pub fn accepted(&self, intended: char, case_insensitive: bool) -> CharSet {
+        let mut set = CharSet::single(intended);
+        let mut merge = |c: char| {
+            if let Some(lookalikes) = self.entries.get(&c) {
+                for l in lookalikes.chars() {
+                    set.insert(l);
+                }
+            }
+        };
+
+        merge(intended);
+        if case_insensitive {
+            merge(intended.to_ascii_uppercase());
+            merge(intended.to_ascii_lowercase());
+            set.insert(intended.to_ascii_uppercase());
+            set.insert(intended.to_ascii_lowercase());
+            set = set.case_folded();
+        }
+        set
+    }
+
+    /// Check if the table has no mappings
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn add(&mut self, intended: char, lookalikes: &str) {
+        let entry = self.entries.entry(intended).or_default();
+        for c in lookalikes.chars() {
+            entry.insert(c);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_table() {
+        let table = ConfusableTable::builtin();
+        let zero: Vec<char> = table.accepted('0', false).chars().collect();
+        assert_eq!(zero, vec!['D', 'Q', 'o']);
+        let ell: Vec<char> = table.accepted('l', false).chars().collect();
+        assert_eq!(ell, vec!['1', 'L']);
+
+        // The table follows the suggestions, leaving out invalid ones like 'O'
+        // for '0' and the font-dependent ones for 'l'
+        for c in EXCLUDED_CHARS.into_iter().filter(|&c| c != 'l') {
+            let accepted = table.accepted(c, false);
+            for s in suggest_valid_chars(c) {
+                assert_eq!(accepted.contains(s), is_valid_base58_char(s));
+            }
+        }
+
+        // Valid characters still match themselves
+        let a = table.accepted('A', false);
+        assert_eq!(a.len(), 1);
+        assert!(a.contains('A'));
+    }
+
+    #[test]
+    fn test_leet_table() {
+        let table = ConfusableTable::builtin().with_leet();
+        assert!(table.accepted('E', false).contains('3'));
+        assert!(table.accepted('e', false).contains('3'));
+        assert!(!table.accepted('X', false).contains('3'));
+    }
+
+    #[test]
+    fn test_case_insensitive_accepts_both_cases() {
+        let table = ConfusableTable::builtin();
+        let set = table.accepted('i', true);
+        // 'i' folds to 'I', whose lookalikes are i/1/L/J (+ j from folding)
+        for c in ['i', '1', 'L', 'J', 'j'] {
+            assert!(set.contains(c), "missing {c}");
+        }
+    }
+
+    #[test]
+    fn test_insert_spec() {
+        let mut table = ConfusableTable::new();
+        table.insert_spec("a=4@").unwrap_err();
+        table.insert_spec("a=4").unwrap();
+        assert!(table.accepted('a', false).contains('4'));
+
+        assert!(matches!(
+            table.insert_spec("a"),
+            Err(ConfigError::InvalidConfusable(_))
+        ));
+        assert!(matches!(
+            table.insert_spec("a=0"),
+            Err(ConfigError::InvalidBase58Character('0'))
+        ));
+    }
+
+    #[test]
+    fn test_empty_table_matches_only_self() {
+        let table = ConfusableTable::new();
+        assert!(table.is_empty());
+        assert!(table.accepted('0', false).is_empty());
+        assert_eq!(table.accepted('z', false).len(), 1);
+    }
+}
@@ -3,7 +3,368 @@
 //! This module provides efficient prefix and suffix matching
 //! for Solana Base58 addresses.
 
-use crate::config::{validate_prefix, validate_suffix, ConfigError, BASE58_ALPHABET};
+use crate::aesthetic::PatternClass;
+use crate::config::{
+    validate_prefix, validate_suffix, ConfigError, BASE58_ALPHABET, MAX_PREFIX_LENGTH,
+    MAX_SUFFIX_LENGTH,
+};
+use crate::lookalike::ConfusableTable;
+use crate::raw::{to_hex, RawTarget, KEY_LENGTH};
+use std::str::FromStr;
+
+/// Lookup table from ASCII byte to its index in the Base58 alphabet (0xFF if invalid)
+const BASE58_INDEX: [u8; 128] = {
+    let alphabet = BASE58_ALPHABET.as_bytes();
+    let mut table = [0xFFu8; 128];
+    let mut i = 0;
+    while i < alphabet.len() {
+        table[alphabet[i] as usize] = i as u8;
+        i += 1;
+    }
+    table
+};
+
+/// A set of Base58 characters, stored as a bitmask over the alphabet
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CharSet(u64);
+
+impl CharSet {
+    /// Create an empty set
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+
+    /// Create a set holding a single character (empty if not Base58)
+    pub fn single(c: char) -> Self {
+        let mut set = Self::empty();
+        set.insert(c);
+        set
+    }
+
+    /// Create a set from a list of characters, ignoring non-Base58 ones
+    pub fn from_chars<I: IntoIterator<Item = char>>(chars: I) -> Self {
+        let mut set = Self::empty();
+        for c in chars {
+            set.insert(c);
+        }
+        set
+    }
+
+    /// Add a character, returning false if it is not in the Base58 alphabet
+    pub fn insert(&mut self, c: char) -> bool {
+        match base58_index(c) {
+            Some(index) => {
+                self.0 |= 1 << index;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Check whether an address byte is in the set
+    #[inline]
+    pub fn contains_byte(self, b: u8) -> bool {
+        match BASE58_INDEX.get(b as usize) {
+            Some(&index) if index != 0xFF => self.0 & (1 << index) != 0,
+            _ => false,
+        }
+    }
+
+    /// Check whether a character is in the set
+    pub fn contains(self, c: char) -> bool {
+        c.is_ascii() && self.contains_byte(c as u8)
+    }
+
+    /// Add the other-case form of every letter in the set, where it is valid Base58
+    pub fn case_folded(self) -> Self {
+        let mut set = self;
+        for c in self.chars() {
+            set.insert(c.to_ascii_uppercase());
+            set.insert(c.to_ascii_lowercase());
+        }
+        set
+    }
+
+    /// Number of characters in the set
+    pub fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    /// Check if the set is empty
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Iterate over the characters in alphabet order
+    pub fn chars(self) -> impl Iterator<Item = char> {
+        BASE58_ALPHABET
+            .chars()
+            .enumerate()
+            .filter(move |(i, _)| self.0 & (1 << i) != 0)
+            .map(|(_, c)| c)
+    }
+}
+
+/// Index of a character in the Base58 alphabet
+fn base58_index(c: char) -> Option<u8> {
+    if !c.is_ascii() {
+        return None;
+    }
+    match BASE58_INDEX[c as usize] {
+        0xFF => None,
+        index => Some(index),
+    }
+}
+
+/// Probability that a uniformly random Base58 character falls in each set
+fn sets_probability(sets: &[CharSet]) -> f64 {
+    sets.iter()
+        .map(|set| set.len() as f64 / BASE58_ALPHABET.len() as f64)
+        .product()
+}
+
+/// Accepted character set for each character of an exact pattern
+fn pattern_sets(pattern: &str, case_insensitive: bool) -> Vec<CharSet> {
+    pattern
+        .chars()
+        .map(|c| {
+            let set = CharSet::single(c);
+            if case_insensitive {
+                set.case_folded()
+            } else {
+                set
+            }
+        })
+        .collect()
+}
+
+/// Probability that a random address segment spells `pattern`
+///
+/// Ignoring case, a letter whose other case is also Base58 matches two
+/// characters and any other character matches one.
+pub fn pattern_probability(pattern: &str, case_insensitive: bool) -> f64 {
+    sets_probability(&pattern_sets(pattern, case_insensitive))
+}
+
+/// Check that every position of an address segment is in its accepted set
+#[inline]
+fn segment_matches(sets: &[CharSet], bytes: &[u8]) -> bool {
+    bytes.len() == sets.len() && sets.iter().zip(bytes).all(|(set, b)| set.contains_byte(*b))
+}
+
+/// How many pattern characters an address satisfies, counted from each end
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MatchScore {
+    /// Leading prefix characters matched before the first mismatch
+    pub prefix: usize,
+    /// Trailing suffix characters matched before the first mismatch
+    pub suffix: usize,
+}
+
+impl MatchScore {
+    /// Total matched characters
+    pub fn total(&self) -> usize {
+        self.prefix + self.suffix
+    }
+}
+
+impl Ord for MatchScore {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.total()
+            .cmp(&other.total())
+            .then(self.prefix.cmp(&other.prefix))
+    }
+}
+
+impl PartialOrd for MatchScore {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// A character in a found address that differs from the intended pattern
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Substitution {
+    /// Zero-based position in the address
+    pub position: usize,
+    /// Character the user asked for
+    pub intended: char,
+    /// Lookalike character that appears in the address
+    pub found: char,
+}
+
+/// Case rule for one pattern segment
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CaseRule {
+    /// Every character must match exactly
+    Sensitive,
+    /// Every letter may appear in either case
+    Insensitive,
+    /// Per-character rule: `true` means that position ignores case
+    PerChar(Vec<bool>),
+}
+
+impl CaseRule {
+    /// Create a rule from the shared `case_insensitive` flag
+    pub fn from_flag(case_insensitive: bool) -> Self {
+        if case_insensitive {
+            Self::Insensitive
+        } else {
+            Self::Sensitive
+        }
+    }
+
+    /// Whether the character at `position` ignores case
+    pub fn ignores_case_at(&self, position: usize) -> bool {
+        match self {
+            Self::Sensitive => false,
+            Self::Insensitive => true,
+            Self::PerChar(mask) => mask.get(position).copied().unwrap_or(false),
+        }
+    }
+
+    /// Whether any position ignores case
+    pub fn ignores_any_case(&self) -> bool {
+        match self {
+            Self::Sensitive => false,
+            Self::Insensitive => true,
+            Self::PerChar(mask) => mask.iter().any(|&i| i),
+        }
+    }
+
+    /// Short human-readable label
+    pub fn label(&self) -> String {
+        match self {
+            Self::Sensitive => "case-sensitive".to_string(),
+            Self::Insensitive => "case-insensitive".to_string(),
+            Self::PerChar(mask) => {
+                let mask: String = mask.iter().map(|&i| if i { 'i' } else { 's' }).collect();
+                format!("case mask '{mask}'")
+            }
+        }
+    }
+}
+
+impl FromStr for CaseRule {
+    type Err = ConfigError;
+
+    /// Parse `sensitive`, `insensitive`, or a per-character mask of `s` and `i`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "sensitive" | "exact" => Ok(Self::Sensitive),
+            "insensitive" | "ignore" => Ok(Self::Insensitive),
+            mask if !mask.is_empty() && mask.chars().all(|c| c == 's' || c == 'i') => {
+                Ok(Self::PerChar(mask.chars().map(|c| c == 'i').collect()))
+            }
+            _ => Err(ConfigError::InvalidCaseRule(s.to_string())),
+        }
+    }
+}
+
+/// One pattern segment with its own case rule
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Segment {
+    /// The pattern characters
+    pub pattern: String,
+    /// How case is compared for this segment
+    pub case: CaseRule,
+}
+
+impl Segment {
+    /// Accepted character set for each position
+    fn position_sets(&self) -> Vec<CharSet> {
+        self.pattern
+            .chars()
+            .enumerate()
+            .map(|(i, c)| {
+                let set = CharSet::single(c);
+                if self.case.ignores_case_at(i) {
+                    set.case_folded()
+                } else {
+                    set
+                }
+            })
+            .collect()
+    }
+
+    fn validate(&self) -> Result<(), ConfigError> {
+        if let CaseRule::PerChar(mask) = &self.case {
+            if mask.len() != self.pattern.chars().count() {
+                return Err(ConfigError::CaseMaskLength {
+                    pattern: self.pattern.clone(),
+                    mask_len: mask.len(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    fn description(&self, kind: &str) -> String {
+        format!("{kind} '{}' ({})", self.pattern, self.case.label())
+    }
+}
+
+/// A prefix and/or suffix, each with its own case rule
+///
+/// Built by [`MatchTarget::segments`], which compiles the per-position sets.
+#[derive(Debug, Clone)]
+pub struct SegmentTarget {
+    prefix: Option<Segment>,
+    suffix: Option<Segment>,
+    /// Accepted characters for each prefix position
+    prefix_sets: Vec<CharSet>,
+    /// Accepted characters for each suffix position
+    suffix_sets: Vec<CharSet>,
+}
+
+impl SegmentTarget {
+    /// The prefix segment, if any
+    pub fn prefix(&self) -> Option<&Segment> {
+        self.prefix.as_ref()
+    }
+
+    /// The suffix segment, if any
+    pub fn suffix(&self) -> Option<&Segment> {
+        self.suffix.as_ref()
+    }
+}
+
+/// A prefix and/or suffix whose characters may be replaced by lookalikes
+///
+/// Built by [`MatchTarget::lookalike`], which compiles the per-position sets.
+#[derive(Debug, Clone)]
+pub struct LookalikeTarget {
+    prefix: Option<String>,
+    suffix: Option<String>,
+    table: ConfusableTable,
+    case_insensitive: bool,
+    /// Accepted characters for each prefix position
+    prefix_sets: Vec<CharSet>,
+    /// Accepted characters for each suffix position
+    suffix_sets: Vec<CharSet>,
+}
+
+impl LookalikeTarget {
+    /// The intended prefix, if any
+    pub fn prefix(&self) -> Option<&str> {
+        self.prefix.as_deref()
+    }
+
+    /// The intended suffix, if any
+    pub fn suffix(&self) -> Option<&str> {
+        self.suffix.as_deref()
+    }
+
+    /// The confusable table the sets were built from
+    pub fn table(&self) -> &ConfusableTable {
+        &self.table
+    }
+
+    /// Check if letters match in either case
+    pub fn is_case_insensitive(&self) -> bool {
+        self.case_insensitive
+    }
+}
 
 /// Target pattern to match against generated addresses
 #[derive(Debug, Clone)]
@@ -24,6 +385,14 @@ pub enum MatchTarget {
         suffix: String,
         case_insensitive: bool,
     },
+    /// Match a prefix and/or suffix, each with its own case rule
+    Segments(SegmentTarget),
+    /// Match a prefix and/or suffix, accepting lookalike characters at each position
+    Lookalike(LookalikeTarget),
+    /// Match structural pattern classes (all must hold) instead of fixed characters
+    Aesthetic { classes: Vec<PatternClass> },
+    /// Match constraints on the raw 32-byte public key instead of its Base58 form
+    Raw(RawTarget),
 }
 
 impl MatchTarget {
@@ -56,6 +425,192 @@ impl MatchTarget {
         })
     }
 
+    /// Create a match target with separate case rules for prefix and suffix
+    ///
+    /// A [`CaseRule::PerChar`] mask must have one entry per pattern character.
+    pub fn segments(
+        prefix: Option<(&str, CaseRule)>,
+        suffix: Option<(&str, CaseRule)>,
+    ) -> Result<Self, ConfigError> {
+        if prefix.is_none() && suffix.is_none() {
+            return Err(ConfigError::NoPatternSpecified);
+        }
+
+        let prefix = match prefix {
+            Some((pattern, case)) => {
+                validate_prefix(pattern)?;
+                let segment = Segment { pattern: pattern.to_string(), case };
+                segment.validate()?;
+                Some(segment)
+            }
+            None => None,
+        };
+        let suffix = match suffix {
+            Some((pattern, case)) => {
+                validate_suffix(pattern)?;
+                let segment = Segment { pattern: pattern.to_string(), case };
+                segment.validate()?;
+                Some(segment)
+            }
+            None => None,
+        };
+
+        let prefix_sets = prefix.as_ref().map(Segment::position_sets).unwrap_or_default();
+        let suffix_sets = suffix.as_ref().map(Segment::position_sets).unwrap_or_default();
+        Ok(Self::Segments(SegmentTarget { prefix, suffix, prefix_sets, suffix_sets }))
+    }
+
+    /// Create a structural match target from one or more pattern classes
+    pub fn aesthetic(classes: Vec<PatternClass>) -> Result<Self, ConfigError> {
+        if classes.is_empty() {
+            return Err(ConfigError::NoPatternSpecified);
+        }
+        for class in &classes {
+            class.validate()?;
+        }
+        Ok(Self::Aesthetic { classes })
+    }
+
+    /// Create a match target over the raw public key bytes
+    ///
+    /// Fails if no constraints are given or no key can satisfy them.
+    pub fn raw(target: RawTarget) -> Result<Self, ConfigError> {
+        if target.is_empty() {
+            return Err(ConfigError::NoPatternSpecified);
+        }
+        if target.probability() <= 0.0 {
+            return Err(ConfigError::Unsatisfiable(format!(
+                "no public key satisfies {}",
+                target.description()
+            )));
+        }
+        Ok(Self::Raw(target))
+    }
+
+    /// Create a lookalike match target
+    ///
+    /// The patterns may contain characters outside Base58 (such as `0` or `l`)
+    /// as long as the table maps them onto at least one valid character.
+    pub fn lookalike(
+        prefix: Option<&str>,
+        suffix: Option<&str>,
+        table: ConfusableTable,
+        case_insensitive: bool,
+    ) -> Result<Self, ConfigError> {
+        if prefix.is_none() && suffix.is_none() {
+            return Err(ConfigError::NoPatternSpecified);
+        }
+        for (pattern, max_len, too_long) in [
+            (prefix, MAX_PREFIX_LENGTH, ConfigError::PrefixTooLong as fn(usize) -> ConfigError),
+            (suffix, MAX_SUFFIX_LENGTH, ConfigError::SuffixTooLong),
+        ] {
+            let Some(pattern) = pattern else { continue };
+            if pattern.is_empty() {
+                return Err(ConfigError::EmptyPattern);
+            }
+            if pattern.chars().count() > max_len {
+                return Err(too_long(pattern.chars().count()));
+            }
+            if let Some(c) = pattern
+                .chars()
+                .find(|c| table.accepted(*c, case_insensitive).is_empty())
+            {
+                return Err(ConfigError::InvalidBase58Character(c));
+            }
+        }
+
+        let sets = |pattern: Option<&str>| -> Vec<CharSet> {
+            pattern
+                .iter()
+                .flat_map(|p| p.chars())
+                .map(|c| table.accepted(c, case_insensitive))
+                .collect()
+        };
+        Ok(Self::Lookalike(LookalikeTarget {
+            prefix_sets: sets(prefix),
+            suffix_sets: sets(suffix),
+            prefix: prefix.map(str::to_string),
+            suffix: suffix.map(str::to_string),
+            table,
+            case_insensitive,
+        }))
+    }
+
+    /// Accepted character sets for each prefix and suffix position
+    pub fn position_sets(&self) -> (Vec<CharSet>, Vec<CharSet>) {
+        match self {
+            Self::Prefix { pattern, case_insensitive } => {
+                (pattern_sets(pattern, *case_insensitive), Vec::new())
+            }
+            Self::Suffix { pattern, case_insensitive } => {
+                (Vec::new(), pattern_sets(pattern, *case_insensitive))
+            }
+            Self::Both { prefix, suffix, case_insensitive } => (
+                pattern_sets(prefix, *case_insensitive),
+                pattern_sets(suffix, *case_insensitive),
+            ),
+            Self::Segments(target) => (target.prefix_sets.clone(), target.suffix_sets.clone()),
+            Self::Lookalike(target) => (target.prefix_sets.clone(), target.suffix_sets.clone()),
+            Self::Aesthetic { .. } | Self::Raw(_) => (Vec::new(), Vec::new()),
+        }
+    }
+
+    /// Probability that a random address matches this target
+    pub fn probability(&self) -> f64 {
+        match self {
+            Self::Segments(SegmentTarget { prefix_sets, suffix_sets, .. })
+            | Self::Lookalike(LookalikeTarget { prefix_sets, suffix_sets, .. }) => {
+                sets_probability(prefix_sets) * sets_probability(suffix_sets)
+            }
+            // Classes are treated as independent
+            Self::Aesthetic { classes } => classes.iter().map(PatternClass::probability).product(),
+            Self::Raw(target) => target.probability(),
+            Self::Prefix { .. } | Self::Suffix { .. } | Self::Both { .. } => {
+                let (prefix_sets, suffix_sets) = self.position_sets();
+                sets_probability(&prefix_sets) * sets_probability(&suffix_sets)
+            }
+        }
+    }
+
+    /// List the lookalike characters used by an address that matches this target
+    ///
+    /// Only lookalike targets produce substitutions; exact and case-insensitive
+    /// matches return an empty list.
+    pub fn substitutions(&self, address: &str) -> Vec<Substitution> {
+        let Self::Lookalike(LookalikeTarget { prefix, suffix, case_insensitive, .. }) = self else {
+            return Vec::new();
+        };
+
+        let differs = |intended: char, found: char| {
+            if *case_insensitive {
+                !intended.eq_ignore_ascii_case(&found)
+            } else {
+                intended != found
+            }
+        };
+
+        let mut found = Vec::new();
+        if let Some(prefix) = prefix {
+            for (position, (intended, actual)) in prefix.chars().zip(address.chars()).enumerate() {
+                if differs(intended, actual) {
+                    found.push(Substitution { position, intended, found: actual });
+                }
+            }
+        }
+        if let Some(suffix) = suffix {
+            // Addresses are ASCII, so a character count is also a byte offset
+            let start = address.len().saturating_sub(suffix.chars().count());
+            for (offset, (intended, actual)) in
+                suffix.chars().zip(address[start..].chars()).enumerate()
+            {
+                if differs(intended, actual) {
+                    found.push(Substitution { position: start + offset, intended, found: actual });
+                }
+            }
+        }
+        found
+    }
+
     /// Check if the given address matches this target
     #[inline]
     pub fn matches(&self, address: &str) -> bool {
@@ -103,6 +658,15 @@ impl MatchTarget {
                     address.ends_with(suffix)
                 }
             }
+            Self::Segments(SegmentTarget { prefix_sets, suffix_sets, .. })
+            | Self::Lookalike(LookalikeTarget { prefix_sets, suffix_sets, .. }) => {
+                let bytes = address.as_bytes();
+                bytes.len() >= prefix_sets.len().max(suffix_sets.len())
+                    && segment_matches(prefix_sets, &bytes[..prefix_sets.len()])
+                    && segment_matches(suffix_sets, &bytes[bytes.len() - suffix_sets.len()..])
+            }
+            Self::Aesthetic { classes } => classes.iter().all(|c| c.matches(address)),
+            Self::Raw(target) => target.matches_address(address),
         }
     }
 
@@ -112,6 +676,16 @@ impl MatchTarget {
             Self::Prefix { pattern, .. } => pattern.len(),
             Self::Suffix { pattern, .. } => pattern.len(),
             Self::Both { prefix, suffix, .. } => prefix.len() + suffix.len(),
+            Self::Segments(SegmentTarget { prefix, suffix, .. }) => {
+                prefix.as_ref().map_or(0, |p| p.pattern.len())
+                    + suffix.as_ref().map_or(0, |s| s.pattern.len())
+            }
+            Self::Lookalike(LookalikeTarget { prefix, suffix, .. }) => {
+                prefix.as_deref().map_or(0, |p| p.chars().count())
+                    + suffix.as_deref().map_or(0, |s| s.chars().count())
+            }
+            Self::Aesthetic { classes } => classes.iter().map(PatternClass::length).sum(),
+            Self::Raw(_) => 0,
         }
     }
 
@@ -130,124 +704,161 @@ impl MatchTarget {
                 let case_str = if *case_insensitive { " (case-insensitive)" } else { "" };
                 format!("prefix '{prefix}' and suffix '{suffix}'{case_str}")
             }
+            Self::Segments(SegmentTarget { prefix, suffix, .. }) => {
+                let parts: Vec<String> = [
+                    prefix.as_ref().map(|p| p.description("prefix")),
+                    suffix.as_ref().map(|s| s.description("suffix")),
+                ]
+                .into_iter()
+                .flatten()
+                .collect();
+                parts.join(" and ")
+            }
+            Self::Lookalike(LookalikeTarget { prefix, suffix, case_insensitive, .. }) => {
+                let case_str = if *case_insensitive { ", case-insensitive" } else { "" };
+                let pattern_str = match (prefix, suffix) {
+                    (Some(p), Some(s)) => format!("prefix '{p}' and suffix '{s}'"),
+                    (Some(p), None) => format!("prefix '{p}'"),
+                    (None, Some(s)) => format!("suffix '{s}'"),
+                    (None, None) => String::new(),
+                };
+                format!("{pattern_str} (lookalikes allowed{case_str})")
+            }
+            Self::Aesthetic { classes } => {
+                let parts: Vec<String> = classes.iter().map(|c| c.to_string()).collect();
+                parts.join(" and ")
+            }
+            Self::Raw(target) => target.description(),
         }
     }
 
     /// Check if case-insensitive matching is enabled
+    ///
+    /// For targets with per-segment rules, this is true if any position ignores case.
     pub fn is_case_insensitive(&self) -> bool {
         match self {
+            Self::Segments(SegmentTarget { prefix, suffix, .. }) => [prefix, suffix]
+                .into_iter()
+                .flatten()
+                .any(|s| s.case.ignores_any_case()),
             Self::Prefix { case_insensitive, .. } => *case_insensitive,
             Self::Suffix { case_insensitive, .. } => *case_insensitive,
             Self::Both { case_insensitive, .. } => *case_insensitive,
+            Self::Lookalike(target) => target.case_insensitive,
+            Self::Aesthetic { .. } | Self::Raw(_) => false,
         }
     }
 }
 
 /// Optimized matcher that pre-computes values for faster matching
+///
+/// Every character target is compiled into one accepted-character set per
+/// position, so exact, case-insensitive and lookalike patterns share the same
+/// fast path. Structural targets are checked with their pattern classes, and
+/// raw key targets with [`OptimizedMatcher::matches_key`] before encoding.
 #[derive(Debug, Clone)]
 pub struct OptimizedMatcher {
     target: MatchTarget,
-    /// Pre-computed lowercase pattern for case-insensitive matching
-    lowercase_prefix: Option<String>,
-    lowercase_suffix: Option<String>,
+    /// Accepted characters for each prefix position
+    prefix_sets: Vec<CharSet>,
+    /// Accepted characters for each suffix position
+    suffix_sets: Vec<CharSet>,
+    /// Structural classes that must all hold
+    classes: Vec<PatternClass>,
+    /// Constraints on the raw key bytes
+    raw: Option<RawTarget>,
 }
 
 impl OptimizedMatcher {
     /// Create a new optimized matcher
     pub fn new(target: MatchTarget) -> Self {
-        let (lowercase_prefix, lowercase_suffix) = match &target {
-            MatchTarget::Prefix { pattern, case_insensitive } => {
-                if *case_insensitive {
-                    (Some(pattern.to_lowercase()), None)
-                } else {
-                    (None, None)
-                }
-            }
-            MatchTarget::Suffix { pattern, case_insensitive } => {
-                if *case_insensitive {
-                    (None, Some(pattern.to_lowercase()))
-                } else {
-                    (None, None)
-                }
-            }
-            MatchTarget::Both { prefix, suffix, case_insensitive } => {
-                if *case_insensitive {
-                    (Some(prefix.to_lowercase()), Some(suffix.to_lowercase()))
-                } else {
-                    (None, None)
-                }
-            }
+        let (prefix_sets, suffix_sets) = target.position_sets();
+        let classes = match &target {
+            MatchTarget::Aesthetic { classes } => classes.clone(),
+            _ => Vec::new(),
+        };
+        let raw = match &target {
+            MatchTarget::Raw(raw) => Some(raw.clone()),
+            _ => None,
         };
 
         Self {
             target,
-            lowercase_prefix,
-            lowercase_suffix,
+            prefix_sets,
+            suffix_sets,
+            classes,
+            raw,
         }
     }
 
+    /// Check the raw public key bytes
+    ///
+    /// Always true for Base58 targets. The generator calls this before
+    /// encoding the key, so raw targets skip Base58 encoding for misses.
+    #[inline]
+    pub fn matches_key(&self, key: &[u8; KEY_LENGTH]) -> bool {
+        self.raw.as_ref().is_none_or(|raw| raw.matches(key))
+    }
+
     /// Check if the given address matches
     #[inline]
     pub fn matches(&self, address: &str) -> bool {
-        match &self.target {
-            MatchTarget::Prefix { pattern, case_insensitive } => {
-                if *case_insensitive {
-                    if let (Some(ref lc_pattern), Some(addr_prefix)) =
-                        (&self.lowercase_prefix, address.get(..pattern.len()))
-                    {
-                        addr_prefix.to_lowercase() == *lc_pattern
-                    } else {
-                        false
-                    }
-                } else {
-                    address.starts_with(pattern)
-                }
-            }
-            MatchTarget::Suffix { pattern, case_insensitive } => {
-                if *case_insensitive {
-                    if let (Some(ref lc_pattern), Some(addr_suffix)) = (
-                        &self.lowercase_suffix,
-                        address.get(address.len().saturating_sub(pattern.len())..),
-                    ) {
-                        addr_suffix.to_lowercase() == *lc_pattern
-                    } else {
-                        false
-                    }
-                } else {
-                    address.ends_with(pattern)
-                }
-            }
-            MatchTarget::Both { prefix, suffix, case_insensitive } => {
-                let prefix_matches = if *case_insensitive {
-                    if let (Some(ref lc_pattern), Some(addr_prefix)) =
-                        (&self.lowercase_prefix, address.get(..prefix.len()))
-                    {
-                        addr_prefix.to_lowercase() == *lc_pattern
-                    } else {
-                        return false;
-                    }
-                } else {
-                    address.starts_with(prefix)
-                };
+        let bytes = address.as_bytes();
+        let (prefix_len, suffix_len) = (self.prefix_sets.len(), self.suffix_sets.len());
+        if bytes.len() < prefix_len.max(suffix_len) {
+            return false;
+        }
 
-                if !prefix_matches {
-                    return false;
-                }
+        segment_matches(&self.prefix_sets, &bytes[..prefix_len])
+            && segment_matches(&self.suffix_sets, &bytes[bytes.len() - suffix_len..])
+            && self.classes.iter().all(|c| c.matches(address))
+            && self.raw.as_ref().is_none_or(|raw| raw.matches_address(address))
+    }
 
-                if *case_insensitive {
-                    if let (Some(ref lc_pattern), Some(addr_suffix)) = (
-                        &self.lowercase_suffix,
-                        address.get(address.len().saturating_sub(suffix.len())..),
-                    ) {
-                        addr_suffix.to_lowercase() == *lc_pattern
-                    } else {
-                        false
-                    }
-                } else {
-                    address.ends_with(suffix)
-                }
-            }
+    /// Count how many prefix characters (from the start) and suffix characters
+    /// (from the end) the address satisfies
+    #[inline]
+    pub fn score(&self, address: &str) -> MatchScore {
+        let bytes = address.as_bytes();
+        let prefix = self
+            .prefix_sets
+            .iter()
+            .zip(bytes)
+            .take_while(|(set, b)| set.contains_byte(**b))
+            .count();
+        let suffix = self
+            .suffix_sets
+            .iter()
+            .rev()
+            .zip(bytes.iter().rev())
+            .take_while(|(set, b)| set.contains_byte(**b))
+            .count();
+        MatchScore { prefix, suffix }
+    }
+
+    /// Highest possible score: the number of positional pattern characters
+    pub fn max_score(&self) -> usize {
+        self.prefix_sets.len() + self.suffix_sets.len()
+    }
+
+    /// Describe notable features of a matching address for reports
+    pub fn match_details(&self, address: &str) -> Vec<(String, String)> {
+        if self.raw.is_some() {
+            let key = address.parse::<solana_sdk::pubkey::Pubkey>();
+            return key
+                .map(|key| vec![("Key bytes".to_string(), to_hex(key.as_ref()))])
+                .unwrap_or_default();
         }
+        self.target
+            .substitutions(address)
+            .into_iter()
+            .map(|s| {
+                (
+                    "Substitution".to_string(),
+                    format!("'{}' -> '{}' at position {}", s.intended, s.found, s.position),
+                )
+            })
+            .collect()
     }
 
     /// Get a reference to the underlying target
@@ -398,6 +1009,211 @@ mod tests {
         assert!(MatchTarget::prefix("lAB", false).is_err());
     }
 
+    #[test]
+    fn test_case_rule_parsing() {
+        assert_eq!("sensitive".parse::<CaseRule>().unwrap(), CaseRule::Sensitive);
+        assert_eq!("Insensitive".parse::<CaseRule>().unwrap(), CaseRule::Insensitive);
+        assert_eq!(
+            "iis".parse::<CaseRule>().unwrap(),
+            CaseRule::PerChar(vec![true, true, false])
+        );
+        assert!(matches!(
+            "maybe".parse::<CaseRule>(),
+            Err(ConfigError::InvalidCaseRule(_))
+        ));
+    }
+
+    #[test]
+    fn test_segments_mixed_case() {
+        let target = MatchTarget::segments(
+            Some(("moon", CaseRule::Insensitive)),
+            Some(("pump", CaseRule::Sensitive)),
+        )
+        .unwrap();
+        assert!(target.matches("MooN23456789abcdefghijkmnopqrstuvpump"));
+        assert!(!target.matches("MooN23456789abcdefghijkmnopqrstuvPUMP"));
+        assert!(target.is_case_insensitive());
+
+        let description = target.description();
+        assert!(description.contains("prefix 'moon' (case-insensitive)"));
+        assert!(description.contains("suffix 'pump' (case-sensitive)"));
+
+        let MatchTarget::Segments(segments) = &target else { panic!("expected segments") };
+        assert_eq!(segments.prefix().unwrap().pattern, "moon");
+        assert_eq!(segments.suffix().unwrap().case, CaseRule::Sensitive);
+    }
+
+    #[test]
+    fn test_segments_per_char_mask() {
+        let target =
+            MatchTarget::segments(Some(("ABc", CaseRule::PerChar(vec![false, true, true]))), None)
+                .unwrap();
+        let matcher = OptimizedMatcher::new(target);
+        assert!(matcher.matches("AbC23456789abcdefghijkmnopqrstuvwxyz"));
+        assert!(!matcher.matches("abC23456789abcdefghijkmnopqrstuvwxyz"));
+
+        let result = MatchTarget::segments(Some(("ABc", CaseRule::PerChar(vec![true]))), None);
+        assert!(matches!(result, Err(ConfigError::CaseMaskLength { mask_len: 1, .. })));
+    }
+
+    #[test]
+    fn test_segments_probability() {
+        let mixed = MatchTarget::segments(
+            Some(("ab", CaseRule::Insensitive)),
+            Some(("cd", CaseRule::Sensitive)),
+        )
+        .unwrap();
+        let expected = (2.0 / 58.0) * (2.0 / 58.0) * (1.0 / 58.0) * (1.0 / 58.0);
+        assert!((mixed.probability() - expected).abs() < 1e-15);
+    }
+
+    #[test]
+    fn test_case_insensitive_probability() {
+        // 'o' has no valid upper case and '1' has no case, so only 'a' doubles
+        let target = MatchTarget::prefix("ao1", true).unwrap();
+        let expected = (2.0 / 58.0) * (1.0 / 58.0) * (1.0 / 58.0);
+        assert!((target.probability() - expected).abs() < 1e-15);
+        assert_eq!(target.probability(), pattern_probability("ao1", true));
+
+        let both = MatchTarget::both("ab", "cd", true).unwrap();
+        assert!((both.probability() - (2.0f64 / 58.0).powi(4)).abs() < 1e-15);
+        let exact = MatchTarget::suffix("ab", false).unwrap();
+        assert!((exact.probability() - (1.0f64 / 58.0).powi(2)).abs() < 1e-15);
+    }
+
+    #[test]
+    fn test_aesthetic_target() {
+        let target = MatchTarget::aesthetic(vec![
+            PatternClass::RepeatPrefix(3),
+            PatternClass::PalindromeSuffix(3),
+        ])
+        .unwrap();
+        let matcher = OptimizedMatcher::new(target.clone());
+        assert!(matcher.matches("777abcdefghijkmnopqrstuvwxyz12345aXa"));
+        assert!(!matcher.matches("777abcdefghijkmnopqrstuvwxyz12345aXb"));
+        assert!(!matcher.matches("778abcdefghijkmnopqrstuvwxyz12345aXa"));
+
+        let expected = (1.0 / 58.0f64).powi(2) * (1.0 / 58.0);
+        assert!((target.probability() - expected).abs() < 1e-15);
+        assert!(target.description().contains("identical leading"));
+
+        assert!(MatchTarget::aesthetic(vec![]).is_err());
+        assert!(MatchTarget::aesthetic(vec![PatternClass::RepeatPrefix(1)]).is_err());
+    }
+
+    #[test]
+    fn test_raw_target() {
+        use crate::raw::ByteConstraint;
+
+        let raw = RawTarget::new()
+            .with(ByteConstraint::masked_prefix("00").unwrap())
+            .unwrap();
+        let target = MatchTarget::raw(raw).unwrap();
+        assert_eq!(target.probability(), 1.0 / 256.0);
+        assert!(target.description().starts_with("raw key"));
+
+        let matcher = OptimizedMatcher::new(target);
+        assert!(matcher.matches_key(&[0u8; 32]));
+        assert!(!matcher.matches_key(&[1u8; 32]));
+        // The system program id is all zero bytes
+        assert!(matcher.matches("11111111111111111111111111111111"));
+        assert_eq!(matcher.match_details("11111111111111111111111111111111")[0].1, "00".repeat(32));
+
+        // Base58 targets accept every key at the byte stage
+        let matcher = OptimizedMatcher::new(MatchTarget::prefix("A", false).unwrap());
+        assert!(matcher.matches_key(&[1u8; 32]));
+
+        assert!(matches!(MatchTarget::raw(RawTarget::new()), Err(ConfigError::NoPatternSpecified)));
+        let impossible = RawTarget::new()
+            .with(ByteConstraint::at_least(&"80".repeat(32)).unwrap())
+            .unwrap()
+            .with(ByteConstraint::below(&"40".repeat(32)).unwrap())
+            .unwrap();
+        assert!(matches!(
+            MatchTarget::raw(impossible),
+            Err(ConfigError::Unsatisfiable(_))
+        ));
+    }
+
+    #[test]
+    fn test_match_score() {
+        let matcher = OptimizedMatcher::new(MatchTarget::both("ABC", "xyz", false).unwrap());
+        assert_eq!(matcher.max_score(), 6);
+
+        let score = matcher.score("ABd23456789abcdefghijkmnopqrstuvwyz");
+        assert_eq!(score, MatchScore { prefix: 2, suffix: 2 });
+
+        let score = matcher.score("XBC23456789abcdefghijkmnopqrstuvxyz");
+        assert_eq!(score, MatchScore { prefix: 0, suffix: 3 });
+        assert!(MatchScore { prefix: 3, suffix: 1 } > MatchScore { prefix: 1, suffix: 2 });
+    }
+
+    #[test]
+    fn test_char_set() {
+        let mut set = CharSet::single('a');
+        assert!(set.contains('a'));
+        assert!(!set.contains('A'));
+        assert!(!set.insert('0'));
+        set = set.case_folded();
+        assert!(set.contains('A'));
+        assert_eq!(set.len(), 2);
+        // 'L' folds to 'l', which is not Base58
+        assert_eq!(CharSet::single('L').case_folded().len(), 1);
+    }
+
+    #[test]
+    fn test_lookalike_matching() {
+        let target =
+            MatchTarget::lookalike(Some("C0IN"), None, ConfusableTable::builtin(), false).unwrap();
+        assert!(target.matches("CoiNdefghijkmnopqrstuvwxyz123456789"));
+        assert!(target.matches("CQ1Ndefghijkmnopqrstuvwxyz123456789"));
+        assert!(!target.matches("COINdefghijkmnopqrstuvwxyz123456789"));
+
+        let matcher = OptimizedMatcher::new(target.clone());
+        assert!(matcher.matches("CDJNdefghijkmnopqrstuvwxyz123456789"));
+        assert!(!matcher.matches("CoiXdefghijkmnopqrstuvwxyz123456789"));
+
+        let MatchTarget::Lookalike(lookalike) = &target else { panic!("expected lookalike") };
+        assert_eq!((lookalike.prefix(), lookalike.suffix()), (Some("C0IN"), None));
+        assert!(!lookalike.is_case_insensitive());
+        assert_eq!(lookalike.table(), &ConfusableTable::builtin());
+    }
+
+    #[test]
+    fn test_lookalike_rejects_unmappable() {
+        let result = MatchTarget::lookalike(Some("A-B"), None, ConfusableTable::builtin(), false);
+        assert!(matches!(result, Err(ConfigError::InvalidBase58Character('-'))));
+    }
+
+    #[test]
+    fn test_lookalike_substitutions() {
+        let target =
+            MatchTarget::lookalike(None, Some("c0ol"), ConfusableTable::builtin(), false).unwrap();
+        let address = "23456789abcdefghijkmnopqrstuvwxycooL";
+        assert!(target.matches(address));
+        let subs = target.substitutions(address);
+        assert_eq!(subs.len(), 2);
+        assert_eq!(subs[0], Substitution { position: 33, intended: '0', found: 'o' });
+        assert_eq!(subs[1].found, 'L');
+
+        // Positions count characters, so a multi-byte pattern character lines up
+        let mut table = ConfusableTable::new();
+        table.insert('\u{41e}', "o").unwrap();
+        let target = MatchTarget::lookalike(None, Some("c\u{41e}o"), table, false).unwrap();
+        let address = "23456789abcdefghijkmnopqrstuvwxyzcoo";
+        assert!(target.matches(address));
+        assert_eq!(target.substitutions(address), [Substitution { position: 34, intended: '\u{41e}', found: 'o' }]);
+    }
+
+    #[test]
+    fn test_lookalike_probability_wider() {
+        let exact = MatchTarget::prefix("Co", false).unwrap();
+        let lookalike =
+            MatchTarget::lookalike(Some("C0"), None, ConfusableTable::builtin(), false).unwrap();
+        // '0' accepts o, Q and D
+        assert!((lookalike.probability() - 3.0 * exact.probability()).abs() < 1e-12);
+    }
+
     #[test]
     fn test_empty_pattern_rejected() {
         assert!(MatchTarget::prefix("", false).is_err());
@@ -0,0 +1,583 @@
+//! BIP39 mnemonic-backed vanity generation.
+//!
+//! Each candidate is a fresh random BIP39 mnemonic whose Solana key is
+//! derived at `m/44'/501'/0'/0'` with SLIP-0010, so a found address can be
+//! restored in any wallet from its seed phrase. Every attempt runs a
+//! 2048-round PBKDF2, making it orders of magnitude slower than a plain
+//! keypair; [`benchmark_mnemonic_rate`] measures the rate for estimates.
+//!
+//! [`PathScan`] works the other way round: it keeps an existing phrase and
+//! walks account and change indices until a derived address matches, so the
+//! result is recoverable from the user's existing backup.
+
+use crate::config::ConfigError;
+use crate::exclusion::ExclusionRules;
+use crate::generator::{
+    counter_search, GeneratorError, GeneratorResult, ParallelSearch, SearchProgress, VanityGeneratorConfig,
+};
+use crate::matcher::{MatchTarget, OptimizedMatcher};
+use crate::output::{GeneratedAddress, OutputResult};
+use crate::security::{secure_write_file, verify_keypair_integrity};
+use bip39::{Language, Mnemonic, MnemonicType, Seed};
+use serde::{Deserialize, Serialize};
+use solana_sdk::derivation_path::DerivationPath;
+use solana_sdk::signature::{keypair_from_seed_and_derivation_path, Keypair};
+use solana_sdk::signer::Signer;
+use std::ops::Range;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+use zeroize::Zeroizing;
+
+/// Account index of the default Solana derivation path
+pub const DEFAULT_ACCOUNT: u32 = 0;
+
+/// Change index of the default Solana derivation path
+pub const DEFAULT_CHANGE: u32 = 0;
+
+/// Exclusive upper bound of a hardened derivation index
+pub const MAX_HARDENED_INDEX: u32 = 1 << 31;
+
+/// Number of words in a generated mnemonic
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WordCount {
+    /// 12 words (128 bits of entropy)
+    #[default]
+    Twelve,
+    /// 24 words (256 bits of entropy)
+    TwentyFour,
+}
+
+impl WordCount {
+    /// Number of words
+    pub fn words(self) -> usize {
+        match self {
+            Self::Twelve => 12,
+            Self::TwentyFour => 24,
+        }
+    }
+
+    pub(crate) fn mnemonic_type(self) -> MnemonicType {
+        match self {
+            Self::Twelve => MnemonicType::Words12,
+            Self::TwentyFour => MnemonicType::Words24,
+        }
+    }
+}
+
+impl FromStr for WordCount {
+    type Err = ConfigError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "12" => Ok(Self::Twelve),
+            "24" => Ok(Self::TwentyFour),
+            _ => Err(ConfigError::InvalidMnemonic(format!(
+                "word count must be 12 or 24, got '{s}'"
+            ))),
+        }
+    }
+}
+
+/// Solana BIP44 path `m/44'/501'/{account}'/{change}'` as text
+pub fn format_path(account: u32, change: u32) -> String {
+    format!("m/44'/501'/{account}'/{change}'")
+}
+
+/// Derive the keypair for `mnemonic` at `m/44'/501'/{account}'/{change}'`
+pub fn keypair_from_mnemonic(
+    mnemonic: &Mnemonic,
+    passphrase: &str,
+    account: u32,
+    change: u32,
+) -> GeneratorResult<Keypair> {
+    let seed = Seed::new(mnemonic, passphrase);
+    let path = DerivationPath::new_bip44(Some(account), Some(change));
+    keypair_from_seed_and_derivation_path(seed.as_bytes(), Some(path))
+        .map_err(|e| GeneratorError::VerificationError(e.to_string()))
+}
+
+/// A vanity address with the mnemonic that restores it
+pub struct MnemonicAddress {
+    /// The derived keypair and search stats
+    pub address: GeneratedAddress,
+    /// Derivation path of the key
+    pub path: String,
+    /// Whether a BIP39 passphrase is needed to restore the key
+    pub passphrase_protected: bool,
+    phrase: Zeroizing<String>,
+}
+
+impl MnemonicAddress {
+    /// The seed phrase (secret)
+    pub fn phrase(&self) -> &str {
+        &self.phrase
+    }
+}
+
+/// Write the seed phrase and derivation path with restricted permissions
+pub fn write_mnemonic_file(found: &MnemonicAddress, path: &Path) -> OutputResult<()> {
+    write_phrase_file(found.phrase(), &found.path, found.passphrase_protected, path)
+}
+
+/// Write a seed phrase with restore notes; `derivation` is a path or how the seed is used
+pub(crate) fn write_phrase_file(
+    phrase: &str,
+    derivation: &str,
+    passphrase_protected: bool,
+    path: &Path,
+) -> OutputResult<()> {
+    let contents = Zeroizing::new(format!(
+        "{}\n# derivation path: {}\n# BIP39 passphrase: {}\n",
+        phrase,
+        derivation,
+        if passphrase_protected { "required" } else { "none" }
+    ));
+    secure_write_file(path, contents.as_bytes())?;
+    log::info!("Wrote mnemonic to: {}", path.display());
+    Ok(())
+}
+
+/// Parallel search over random mnemonics for a derived address matching a target
+pub struct MnemonicSearch {
+    config: VanityGeneratorConfig,
+    matcher: OptimizedMatcher,
+    exclusions: ExclusionRules,
+    word_count: WordCount,
+    passphrase: Zeroizing<String>,
+    progress: SearchProgress,
+    excluded: Arc<AtomicU64>,
+}
+
+impl MnemonicSearch {
+    /// Create a search over 12-word mnemonics with no passphrase
+    pub fn new(target: MatchTarget, config: VanityGeneratorConfig) -> Self {
+        Self {
+            config,
+            matcher: OptimizedMatcher::new(target),
+            exclusions: ExclusionRules::default(),
+            word_count: WordCount::default(),
+            passphrase: Zeroizing::new(String::new()),
+            progress: SearchProgress::default(),
+            excluded: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Generate mnemonics with this many words
+    pub fn with_word_count(mut self, word_count: WordCount) -> Self {
+        self.word_count = word_count;
+        self
+    }
+
+    /// Derive keys with a BIP39 passphrase
+    pub fn with_passphrase(mut self, passphrase: &str) -> Self {
+        self.passphrase = Zeroizing::new(passphrase.to_string());
+        self
+    }
+
+    /// Reject matching addresses that violate these exclusion rules
+    pub fn with_exclusions(mut self, exclusions: ExclusionRules) -> Self {
+        self.exclusions = exclusions;
+        self
+    }
+
+    /// Stop when `cancel` is set, e.g. by a Ctrl+C handler covering several searches
+    pub fn with_cancel_handle(mut self, cancel: Arc<AtomicBool>) -> Self {
+        self.progress = SearchProgress::with_cancel(cancel);
+        self
+    }
+
+    /// Get the number of matches rejected by exclusion rules
+    pub fn excluded(&self) -> u64 {
+        self.excluded.load(Ordering::Relaxed)
+    }
+
+    /// Expected number of mnemonics to try
+    pub fn expected_attempts(&self) -> f64 {
+        let probability =
+            self.matcher.target().probability() * self.exclusions.acceptance_probability();
+        1.0 / probability
+    }
+
+    /// Search until a mnemonic's derived address matches
+    pub fn run(&self) -> GeneratorResult<MnemonicAddress> {
+        let start_time = Instant::now();
+        let (_, (mnemonic, keypair)) = counter_search(
+            &self.config,
+            &self.progress,
+            0..u64::MAX,
+            |_| {
+                let mnemonic = Mnemonic::new(self.word_count.mnemonic_type(), Language::English);
+                let keypair =
+                    keypair_from_mnemonic(&mnemonic, &self.passphrase, DEFAULT_ACCOUNT, DEFAULT_CHANGE)
+                        .ok()?;
+                let pubkey = keypair.pubkey();
+                if !self.matcher.matches_key(&pubkey.to_bytes()) {
+                    return None;
+                }
+                let pubkey_str = pubkey.to_string();
+                if !self.matcher.matches(&pubkey_str) {
+                    return None;
+                }
+                if let Some(violation) = self.exclusions.check(&pubkey_str) {
+                    log::debug!("Excluded {}: {}", pubkey_str, violation);
+                    self.excluded.fetch_add(1, Ordering::Relaxed);
+                    return None;
+                }
+                Some((mnemonic, keypair))
+            },
+        )?;
+
+        if self.config.verify_keypairs {
+            verify_keypair_integrity(&keypair)
+                .map_err(|e| GeneratorError::VerificationError(e.to_string()))?;
+        }
+
+        let pubkey_str = keypair.pubkey().to_string();
+        let path = format_path(DEFAULT_ACCOUNT, DEFAULT_CHANGE);
+        let mut address = GeneratedAddress::new(
+            keypair,
+            self.attempts(),
+            start_time.elapsed().as_millis() as u64,
+        );
+        for (label, value) in self.matcher.match_details(&pubkey_str) {
+            address.add_detail(label, value);
+        }
+        address.add_detail("Derivation path", path.clone());
+        address.add_detail("Mnemonic", format!("{} words", self.word_count.words()));
+
+        Ok(MnemonicAddress {
+            address,
+            path,
+            passphrase_protected: !self.passphrase.is_empty(),
+            phrase: Zeroizing::new(mnemonic.phrase().to_string()),
+        })
+    }
+}
+
+impl ParallelSearch for MnemonicSearch {
+    fn progress(&self) -> &SearchProgress {
+        &self.progress
+    }
+}
+
+/// A derivation path of an existing mnemonic whose address matches a target
+///
+/// Holds no secret; the phrase it was derived from is the user's backup.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PathMatch {
+    /// The derived address
+    pub address: String,
+    /// Account index
+    pub account: u32,
+    /// Change index
+    pub change: u32,
+    /// Full derivation path
+    pub path: String,
+    /// Whether a BIP39 passphrase is needed to restore the key
+    pub passphrase_protected: bool,
+    /// Paths tried across all threads
+    pub attempts: u64,
+    /// Search time in milliseconds
+    pub time_ms: u64,
+}
+
+/// Write a path record as pretty-printed JSON
+pub fn write_path_record(record: &PathMatch, path: &Path) -> OutputResult<()> {
+    let json = serde_json::to_string_pretty(record)?;
+    secure_write_file(path, json.as_bytes())?;
+    log::info!("Wrote path record to: {}", path.display());
+    Ok(())
+}
+
+/// Parallel search over the derivation paths of an existing mnemonic
+///
+/// Only the BIP39 seed is kept, and it is zeroized on drop.
+pub struct PathScan {
+    config: VanityGeneratorConfig,
+    matcher: OptimizedMatcher,
+    exclusions: ExclusionRules,
+    seed: Seed,
+    passphrase_protected: bool,
+    accounts: Range<u32>,
+    changes: Range<u32>,
+    progress: SearchProgress,
+    excluded: Arc<AtomicU64>,
+}
+
+impl PathScan {
+    /// Scan accounts `0..10000` with change index 0 of `phrase`
+    pub fn new(
+        phrase: &str,
+        passphrase: &str,
+        target: MatchTarget,
+        config: VanityGeneratorConfig,
+    ) -> Result<Self, ConfigError> {
+        let mnemonic = Mnemonic::from_phrase(phrase.trim(), Language::English)
+            .map_err(|e| ConfigError::InvalidMnemonic(e.to_string()))?;
+        Ok(Self {
+            config,
+            matcher: OptimizedMatcher::new(target),
+            exclusions: ExclusionRules::default(),
+            seed: Seed::new(&mnemonic, passphrase),
+            passphrase_protected: !passphrase.is_empty(),
+            accounts: 0..10_000,
+            changes: DEFAULT_CHANGE..DEFAULT_CHANGE + 1,
+            progress: SearchProgress::default(),
+            excluded: Arc::new(AtomicU64::new(0)),
+        })
+    }
+
+    /// Reject matching addresses that violate these exclusion rules
+    pub fn with_exclusions(mut self, exclusions: ExclusionRules) -> Self {
+        self.exclusions = exclusions;
+        self
+    }
+
+    /// Scan these account indices
+    pub fn with_accounts(mut self, accounts: Range<u32>) -> Result<Self, ConfigError> {
+        self.accounts = check_index_range("account", accounts)?;
+        Ok(self)
+    }
+
+    /// Scan these change indices for every account
+    pub fn with_changes(mut self, changes: Range<u32>) -> Result<Self, ConfigError> {
+        self.changes = check_index_range("change", changes)?;
+        Ok(self)
+    }
+
+    /// Get the number of matches rejected by exclusion rules
+    pub fn excluded(&self) -> u64 {
+        self.excluded.load(Ordering::Relaxed)
+    }
+
+    /// Number of paths in the scan
+    pub fn total_paths(&self) -> u64 {
+        self.accounts.len() as u64 * self.changes.len() as u64
+    }
+
+    /// Expected number of paths to try
+    pub fn expected_attempts(&self) -> f64 {
+        let probability =
+            self.matcher.target().probability() * self.exclusions.acceptance_probability();
+        1.0 / probability
+    }
+
+    /// Account and change indices for a position in the scan
+    fn indices(&self, n: u64) -> (u32, u32) {
+        let changes = self.changes.len() as u64;
+        (
+            self.accounts.start + (n / changes) as u32,
+            self.changes.start + (n % changes) as u32,
+        )
+    }
+
+    /// Scan every path, returning `Exhausted` if none matches
+    pub fn run(&self) -> GeneratorResult<PathMatch> {
+        let start_time = Instant::now();
+        let (n, keypair) = counter_search(
+            &self.config,
+            &self.progress,
+            0..self.total_paths(),
+            |n| {
+                let (account, change) = self.indices(n);
+                let path = DerivationPath::new_bip44(Some(account), Some(change));
+                let keypair =
+                    keypair_from_seed_and_derivation_path(self.seed.as_bytes(), Some(path)).ok()?;
+                let pubkey = keypair.pubkey();
+                if !self.matcher.matches_key(&pubkey.to_bytes()) {
+                    return None;
+                }
+                let pubkey_str = pubkey.to_string();
+                if !self.matcher.matches(&pubkey_str) {
+                    return None;
+                }
+                if let Some(violation) = self.exclusions.check(&pubkey_str) {
+                    log::debug!("Excluded {}: {}", pubkey_str, violation);
+                    self.excluded.fetch_add(1, Ordering::Relaxed);
+                    return None;
+                }
+                Some(keypair)
+            },
+        )?;
+
+        if self.config.verify_keypairs {
+            verify_keypair_integrity(&keypair)
+                .map_err(|e| GeneratorError::VerificationError(e.to_string()))?;
+        }
+
+        let (account, change) = self.indices(n);
+        Ok(PathMatch {
+            address: keypair.pubkey().to_string(),
+            account,
+            change,
+            path: format_path(account, change),
+            passphrase_protected: self.passphrase_protected,
+            attempts: self.attempts(),
+            time_ms: start_time.elapsed().as_millis() as u64,
+        })
+    }
+}
+
+impl ParallelSearch for PathScan {
+    fn progress(&self) -> &SearchProgress {
+        &self.progress
+    }
+}
+
+fn check_index_range(name: &str, range: Range<u32>) -> Result<Range<u32>, ConfigError> {
+    if range.is_empty() || range.end > MAX_HARDENED_INDEX {
+        return Err(ConfigError::InvalidMnemonic(format!(
+            "{name} indices must be a non-empty range below {MAX_HARDENED_INDEX}"
+        )));
+    }
+    Ok(range)
+}
+
+/// Benchmark mnemonic-to-key derivation speed (single thread)
+pub fn benchmark_mnemonic_rate(duration_secs: u64) -> u64 {
+    let start = Instant::now();
+    let mut count = 0u64;
+
+    while start.elapsed().as_secs() < duration_secs {
+        let mnemonic = Mnemonic::new(MnemonicType::Words12, Language::English);
+        if let Ok(keypair) = keypair_from_mnemonic(&mnemonic, "", DEFAULT_ACCOUNT, DEFAULT_CHANGE) {
+            let _ = keypair.pubkey().to_string();
+        }
+        count += 1;
+    }
+
+    (count / duration_secs).max(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::config;
+    use tempfile::tempdir;
+
+    // BIP39 test vector; Solana wallets derive this address at m/44'/501'/0'/0'
+    const PHRASE: &str =
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    #[test]
+    fn test_word_count_parse() {
+        assert_eq!("12".parse::<WordCount>().unwrap().words(), 12);
+        assert_eq!("24".parse::<WordCount>().unwrap().words(), 24);
+        assert!("18".parse::<WordCount>().is_err());
+    }
+
+    #[test]
+    fn test_known_derivation() {
+        let mnemonic = Mnemonic::from_phrase(PHRASE, Language::English).unwrap();
+        let keypair = keypair_from_mnemonic(&mnemonic, "", 0, 0).unwrap();
+        assert_eq!(
+            keypair.pubkey().to_string(),
+            "HAgk14JpMQLgt6rVgv7cBQFJWFto5Dqxi472uT3DKpqk"
+        );
+
+        // Different paths and passphrases give different keys
+        let other = keypair_from_mnemonic(&mnemonic, "", 1, 0).unwrap();
+        assert_ne!(other.pubkey(), keypair.pubkey());
+        let protected = keypair_from_mnemonic(&mnemonic, "secret", 0, 0).unwrap();
+        assert_ne!(protected.pubkey(), keypair.pubkey());
+    }
+
+    #[test]
+    fn test_search_finds_restorable_address() {
+        let target = MatchTarget::prefix("A", false).unwrap();
+        let found = MnemonicSearch::new(target, config()).run().unwrap();
+        assert!(found.address.public_key().starts_with('A'));
+        assert_eq!(found.phrase().split_whitespace().count(), 12);
+        assert_eq!(found.path, "m/44'/501'/0'/0'");
+
+        // The phrase restores the same key
+        let mnemonic = Mnemonic::from_phrase(found.phrase(), Language::English).unwrap();
+        let restored = keypair_from_mnemonic(&mnemonic, "", 0, 0).unwrap();
+        assert_eq!(restored.pubkey().to_string(), found.address.public_key());
+    }
+
+    #[test]
+    fn test_write_mnemonic_file() {
+        let dir = tempdir().unwrap();
+        let target = MatchTarget::prefix("A", false).unwrap();
+        let found = MnemonicSearch::new(target, config())
+            .with_word_count(WordCount::TwentyFour)
+            .with_passphrase("pw")
+            .run()
+            .unwrap();
+
+        let path = dir.path().join("key.mnemonic.txt");
+        write_mnemonic_file(&found, &path).unwrap();
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let mut lines = contents.lines();
+        assert_eq!(lines.next().unwrap().split_whitespace().count(), 24);
+        assert_eq!(lines.next().unwrap(), "# derivation path: m/44'/501'/0'/0'");
+        assert_eq!(lines.next().unwrap(), "# BIP39 passphrase: required");
+    }
+
+    #[test]
+    fn test_path_scan_finds_known_account() {
+        let mnemonic = Mnemonic::from_phrase(PHRASE, Language::English).unwrap();
+        let expected = keypair_from_mnemonic(&mnemonic, "", 3, 1).unwrap().pubkey().to_string();
+
+        // An 8-character prefix won't match any other path in the scan
+        let target = MatchTarget::prefix(&expected[..8], false).unwrap();
+        let scan = PathScan::new(PHRASE, "", target, config())
+            .unwrap()
+            .with_accounts(0..5)
+            .unwrap()
+            .with_changes(0..2)
+            .unwrap();
+        assert_eq!(scan.total_paths(), 10);
+
+        let found = scan.run().unwrap();
+        assert_eq!(found.address, expected);
+        assert_eq!((found.account, found.change), (3, 1));
+        assert_eq!(found.path, "m/44'/501'/3'/1'");
+        assert!(!found.passphrase_protected);
+    }
+
+    #[test]
+    fn test_path_scan_exhausts_range() {
+        let target = MatchTarget::prefix("ABCDEFGH", false).unwrap();
+        let scan = PathScan::new(PHRASE, "", target, config())
+            .unwrap()
+            .with_accounts(0..20)
+            .unwrap();
+        assert!(matches!(scan.run(), Err(GeneratorError::Exhausted)));
+        assert_eq!(scan.attempts(), 20);
+    }
+
+    #[test]
+    fn test_path_scan_validation() {
+        let target = || MatchTarget::prefix("A", false).unwrap();
+        assert!(PathScan::new("not a real phrase", "", target(), config()).is_err());
+        let scan = || PathScan::new(PHRASE, "", target(), config()).unwrap();
+        assert!(scan().with_accounts(5..5).is_err());
+        assert!(scan().with_changes(0..MAX_HARDENED_INDEX + 1).is_err());
+        assert!(scan().with_accounts(0..MAX_HARDENED_INDEX).is_ok());
+    }
+
+    #[test]
+    fn test_write_path_record() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("path.json");
+        let record = PathMatch {
+            address: "Addr".to_string(),
+            account: 7,
+            change: 0,
+            path: format_path(7, 0),
+            passphrase_protected: true,
+            attempts: 8,
+            time_ms: 1,
+        };
+        write_path_record(&record, &path).unwrap();
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert!(!contents.contains("abandon"));
+        let restored: PathMatch = serde_json::from_str(&contents).unwrap();
+        assert_eq!(restored, record);
+    }
+}
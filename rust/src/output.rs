@@ -3,12 +3,18 @@
 //! This module handles the secure output of generated keypairs
 //! in formats compatible with the Solana CLI.
 
-use crate::security::{secure_write_file, verify_keypair_integrity, SecurityError};
+use crate::config::ConfigError;
+use crate::raw::to_hex;
+use crate::security::{secure_write_file, validate_output_path, verify_keypair_integrity, SecurityError};
 use serde::{Deserialize, Serialize};
 use solana_sdk::signer::keypair::Keypair;
 use solana_sdk::signature::Signer;
+use std::collections::HashSet;
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
 use thiserror::Error;
+use zeroize::Zeroizing;
 
 /// Output-related errors
 #[derive(Error, Debug)]
@@ -24,6 +30,9 @@ pub enum OutputError {
 
     #[error("Invalid keypair data")]
     InvalidKeypairData,
+
+    #[error("{0}")]
+    ConfigError(#[from] ConfigError),
 }
 
 /// Result type for output operations
@@ -37,6 +46,10 @@ pub struct GeneratedAddress {
     pub attempts: u64,
     /// Time taken to find this address (in milliseconds)
     pub time_ms: u64,
+    /// Labelled notes about the match, shown in results and reports
+    details: Vec<(String, String)>,
+    /// Addresses derived from this key, with their labels
+    derived: Vec<(String, String)>,
 }
 
 impl GeneratedAddress {
@@ -46,9 +59,31 @@ impl GeneratedAddress {
             keypair,
             attempts,
             time_ms,
+            details: Vec::new(),
+            derived: Vec::new(),
         }
     }
 
+    /// Attach a labelled note about the match (never secret data)
+    pub fn add_detail(&mut self, label: impl Into<String>, value: impl Into<String>) {
+        self.details.push((label.into(), value.into()));
+    }
+
+    /// Labelled notes about the match
+    pub fn details(&self) -> &[(String, String)] {
+        &self.details
+    }
+
+    /// Record an address derived from this key (e.g. an ATA or PDA)
+    pub fn add_derived(&mut self, label: impl Into<String>, address: impl Into<String>) {
+        self.derived.push((label.into(), address.into()));
+    }
+
+    /// Addresses derived from this key, as `(label, address)`
+    pub fn derived(&self) -> &[(String, String)] {
+        &self.derived
+    }
+
     /// Get the public key as a Base58 string
     pub fn public_key(&self) -> String {
         self.keypair.pubkey().to_string()
@@ -86,13 +121,132 @@ impl std::fmt::Debug for GeneratedAddress {
     }
 }
 
-/// Output format for keypair files
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum OutputFormat {
-    /// JSON array of bytes (Solana CLI compatible)
+/// Encoding of the secret key in a keypair file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum KeypairEncoding {
+    /// JSON array of the 64 secret key bytes (Solana CLI)
+    #[default]
     SolanaJson,
-    /// Human-readable report
-    Report,
+    /// Base58 string of the 64 secret key bytes (wallet import format)
+    Base58,
+    /// Lowercase hex string of the 64 secret key bytes
+    Hex,
+    /// The 64 secret key bytes, unencoded
+    Raw,
+}
+
+impl KeypairEncoding {
+    /// Every encoding, in the order they are documented
+    pub const ALL: [Self; 4] = [Self::SolanaJson, Self::Base58, Self::Hex, Self::Raw];
+
+    /// Guess the encoding of keypair file contents
+    pub fn detect(data: &[u8]) -> Self {
+        // No text encoding of 64 bytes is 64 bytes long
+        if data.len() == KEYPAIR_LENGTH {
+            return Self::Raw;
+        }
+        let text = data.trim_ascii();
+        match text.first() {
+            Some(b'[') => Self::SolanaJson,
+            _ if text.len() == 2 * KEYPAIR_LENGTH && text.iter().all(u8::is_ascii_hexdigit) => Self::Hex,
+            _ => Self::Base58,
+        }
+    }
+
+    /// Short name, as accepted by `FromStr`
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::SolanaJson => "json",
+            Self::Base58 => "base58",
+            Self::Hex => "hex",
+            Self::Raw => "raw",
+        }
+    }
+
+    /// Conventional file extension
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::SolanaJson => "json",
+            Self::Base58 => "b58",
+            Self::Hex => "hex",
+            Self::Raw => "bin",
+        }
+    }
+}
+
+impl FromStr for KeypairEncoding {
+    type Err = ConfigError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "json" => Ok(Self::SolanaJson),
+            "base58" => Ok(Self::Base58),
+            "hex" => Ok(Self::Hex),
+            "raw" | "bin" => Ok(Self::Raw),
+            _ => Err(ConfigError::InvalidKeyFormat(s.to_string())),
+        }
+    }
+}
+
+/// Length of a keypair's secret key bytes (secret seed + public key)
+pub const KEYPAIR_LENGTH: usize = 64;
+
+/// Encode a keypair's 64 secret key bytes
+pub fn encode_keypair(keypair: &Keypair, encoding: KeypairEncoding) -> OutputResult<Zeroizing<Vec<u8>>> {
+    let bytes = Zeroizing::new(keypair.to_bytes());
+    let encoded = match encoding {
+        KeypairEncoding::SolanaJson => serde_json::to_vec(&bytes[..])?,
+        KeypairEncoding::Base58 => solana_sdk::bs58::encode(&bytes[..]).into_vec(),
+        KeypairEncoding::Hex => to_hex(&bytes[..]).into_bytes(),
+        KeypairEncoding::Raw => bytes.to_vec(),
+    };
+    Ok(Zeroizing::new(encoded))
+}
+
+/// Decode keypair file contents, detecting the encoding
+pub fn decode_keypair(data: &[u8]) -> OutputResult<(Keypair, KeypairEncoding)> {
+    let encoding = KeypairEncoding::detect(data);
+    let text = || std::str::from_utf8(data).map(str::trim).map_err(|_| OutputError::InvalidKeypairData);
+    let bytes: Zeroizing<Vec<u8>> = Zeroizing::new(match encoding {
+        KeypairEncoding::SolanaJson => serde_json::from_slice(data)?,
+        KeypairEncoding::Base58 => solana_sdk::bs58::decode(text()?)
+            .into_vec()
+            .map_err(|_| OutputError::InvalidKeypairData)?,
+        KeypairEncoding::Hex => decode_hex(text()?)?,
+        KeypairEncoding::Raw => data.to_vec(),
+    });
+    if bytes.len() != KEYPAIR_LENGTH {
+        return Err(OutputError::InvalidKeypairData);
+    }
+    let keypair = Keypair::from_bytes(&bytes).map_err(|_| OutputError::InvalidKeypairData)?;
+    Ok((keypair, encoding))
+}
+
+/// Decode a hex string of either case
+fn decode_hex(text: &str) -> OutputResult<Vec<u8>> {
+    let digit = |c: u8| (c as char).to_digit(16).ok_or(OutputError::InvalidKeypairData);
+    text.as_bytes()
+        .chunks(2)
+        .map(|pair| match *pair {
+            [high, low] => Ok((digit(high)? << 4 | digit(low)?) as u8),
+            _ => Err(OutputError::InvalidKeypairData),
+        })
+        .collect()
+}
+
+/// Read a keypair file in any supported encoding
+pub fn read_keypair_file(path: &Path) -> OutputResult<(Keypair, KeypairEncoding)> {
+    let data = Zeroizing::new(std::fs::read(path)?);
+    decode_keypair(&data)
+}
+
+/// Write a keypair with restricted permissions in the given encoding
+pub fn write_keypair_as(keypair: &Keypair, encoding: KeypairEncoding, path: &Path) -> OutputResult<()> {
+    verify_keypair_integrity(keypair)?;
+    let encoded = encode_keypair(keypair, encoding)?;
+    secure_write_file(path, &encoded)?;
+    log::info!("Wrote {} keypair to: {}", encoding.name(), path.display());
+    Ok(())
 }
 
 /// Generate the default output path for an address
@@ -100,28 +254,162 @@ pub fn default_output_path(public_key: &str) -> PathBuf {
     PathBuf::from(format!("{public_key}.json"))
 }
 
-/// Write a keypair to a file in Solana CLI compatible JSON format
-///
-/// The format is a JSON array of 64 bytes representing the secret key.
-/// This is the same format used by `solana-keygen`.
-///
-/// # Security
-/// - File is written with mode 0o600 on Unix
-/// - Keypair integrity is verified before writing
-pub fn write_keypair_file(address: &GeneratedAddress, path: &Path) -> OutputResult<()> {
-    // Verify keypair before writing
-    address.verify()?;
+/// Placeholders an [`OutputTemplate`] may contain
+pub const TEMPLATE_PLACEHOLDERS: &[&str] = &["pubkey", "prefix", "suffix", "index", "date", "job"];
+
+/// Values for the placeholders of an [`OutputTemplate`], except `{pubkey}`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OutputFields {
+    /// The pattern's prefix, or empty
+    pub prefix: String,
+    /// The pattern's suffix, or empty
+    pub suffix: String,
+    /// 1-based number of the file within the run or job
+    pub index: usize,
+    /// Date the run started, as `YYYY-MM-DD`
+    pub date: String,
+    /// Batch job name; templates using `{job}` need one
+    pub job: Option<String>,
+}
+
+/// Keypair filename template, such as `{job}/{prefix}-{index}-{pubkey}.json`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutputTemplate {
+    template: String,
+}
 
-    // Serialize to Solana CLI format (JSON array of bytes)
-    let bytes = address.secret_key_bytes();
-    let json = serde_json::to_string(&bytes.to_vec())?;
+impl OutputTemplate {
+    /// Template matching [`default_output_path`]
+    pub const DEFAULT: &'static str = "{pubkey}.json";
 
-    // Write securely
-    secure_write_file(path, json.as_bytes())?;
+    /// Parse a template, checking every `{placeholder}` is known
+    pub fn new(template: &str) -> Result<Self, ConfigError> {
+        let invalid = |reason: &str| ConfigError::InvalidOutputTemplate(format!("'{template}' {reason}"));
+        if template.trim().is_empty() {
+            return Err(invalid("is empty"));
+        }
+        if template.ends_with('/') || template.ends_with(std::path::MAIN_SEPARATOR) {
+            return Err(invalid("names a directory, not a file"));
+        }
+        let mut rest = template;
+        while let Some(start) = rest.find(['{', '}']) {
+            if rest[start..].starts_with('}') {
+                return Err(invalid("has an unmatched '}'"));
+            }
+            let end = rest[start..].find('}').ok_or_else(|| invalid("has an unmatched '{'"))?;
+            let name = &rest[start + 1..start + end];
+            if !TEMPLATE_PLACEHOLDERS.contains(&name) {
+                return Err(invalid(&format!(
+                    "has an unknown placeholder {{{name}}}; use {}",
+                    TEMPLATE_PLACEHOLDERS.iter().map(|p| format!("{{{p}}}")).collect::<Vec<_>>().join(", ")
+                )));
+            }
+            rest = &rest[start + end + 1..];
+        }
+        Ok(Self {
+            template: template.to_string(),
+        })
+    }
 
-    log::info!("Wrote keypair to: {}", path.display());
+    /// The template text
+    pub fn as_str(&self) -> &str {
+        &self.template
+    }
 
-    Ok(())
+    /// Whether the template contains `{name}`
+    pub fn uses(&self, name: &str) -> bool {
+        self.template.contains(&format!("{{{name}}}"))
+    }
+
+    /// Render the file name for `pubkey`
+    pub fn render(&self, pubkey: &str, fields: &OutputFields) -> String {
+        self.template
+            .replace("{prefix}", &fields.prefix)
+            .replace("{suffix}", &fields.suffix)
+            .replace("{index}", &fields.index.to_string())
+            .replace("{date}", &fields.date)
+            .replace("{job}", fields.job.as_deref().unwrap_or_default())
+            .replace("{pubkey}", pubkey)
+    }
+
+    /// Work out the files a run will write, before any key is found
+    ///
+    /// Each entry of `fields` is one file to be written in `dir`. Paths are
+    /// checked with [`validate_output_path`], and two files rendering to the
+    /// same path are an error. Returns the paths, or `None` when they depend
+    /// on `{pubkey}` and so can't be known (or collide) in advance.
+    pub fn planned_paths(&self, dir: &Path, fields: &[OutputFields]) -> OutputResult<Option<Vec<PathBuf>>> {
+        if self.uses("job") && fields.iter().any(|f| f.job.is_none()) {
+            return Err(ConfigError::InvalidOutputTemplate(format!(
+                "'{}' uses {{job}}, which is only set for batch jobs",
+                self.template
+            ))
+            .into());
+        }
+
+        let paths: Vec<PathBuf> = fields
+            .iter()
+            .map(|fields| dir.join(self.render("{pubkey}", fields)))
+            .collect();
+        for path in &paths {
+            validate_output_path(path)?;
+        }
+        if self.uses("pubkey") {
+            return Ok(None);
+        }
+
+        let mut seen = HashSet::new();
+        for path in &paths {
+            if !seen.insert(path) {
+                return Err(ConfigError::OutputCollision(path.display().to_string()).into());
+            }
+        }
+        Ok(Some(paths))
+    }
+}
+
+impl Default for OutputTemplate {
+    fn default() -> Self {
+        Self {
+            template: Self::DEFAULT.to_string(),
+        }
+    }
+}
+
+impl FromStr for OutputTemplate {
+    type Err = ConfigError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s)
+    }
+}
+
+/// Format a time as a UTC `YYYY-MM-DD` date, for `{date}`
+pub fn utc_date(time: SystemTime) -> String {
+    let days = time.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs() / 86_400) as i64;
+    // Civil-from-days conversion (Howard Hinnant's algorithm)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+/// Write a generated address's keypair file in the given encoding
+///
+/// [`KeypairEncoding::SolanaJson`] is the JSON array of 64 bytes used by
+/// `solana-keygen`; see [`KeypairEncoding`] for the others.
+///
+/// # Security
+/// - File is written with mode 0o600 on Unix
+/// - Keypair integrity is verified before writing, in every encoding
+pub fn write_keypair_file(address: &GeneratedAddress, encoding: KeypairEncoding, path: &Path) -> OutputResult<()> {
+    write_keypair_as(address.keypair(), encoding, path)
 }
 
 /// Write a human-readable report about the generated address
@@ -134,6 +422,26 @@ pub fn write_report(address: &GeneratedAddress, path: &Path) -> OutputResult<()>
 /// Generate a human-readable report
 pub fn generate_report(address: &GeneratedAddress) -> String {
     let public_key = address.public_key();
+    let details = if address.details().is_empty() {
+        String::new()
+    } else {
+        let lines: Vec<String> = address
+            .details()
+            .iter()
+            .map(|(label, value)| format!("  - {label}: {value}"))
+            .collect();
+        format!("\nMatch Details:\n{}\n", lines.join("\n"))
+    };
+    let derived = if address.derived().is_empty() {
+        String::new()
+    } else {
+        let lines: Vec<String> = address
+            .derived()
+            .iter()
+            .map(|(label, value)| format!("  - {label}: {value}"))
+            .collect();
+        format!("\nDerived Addresses:\n{}\n", lines.join("\n"))
+    };
 
     format!(
         r#"================================================================================
@@ -141,7 +449,7 @@ pub fn generate_report(address: &GeneratedAddress) -> String {
 ================================================================================
 
 Public Key: {public_key}
-
+{details}{derived}
 Statistics:
   - Attempts: {attempts:>15}
   - Time:     {time:>15.2} seconds
@@ -160,6 +468,8 @@ Verification:
 ================================================================================
 "#,
         public_key = public_key,
+        details = details,
+        derived = derived,
         attempts = address.attempts,
         time = address.time_ms as f64 / 1000.0,
         rate = if address.time_ms > 0 {
@@ -188,6 +498,12 @@ pub fn print_result(address: &GeneratedAddress, verbose: bool) {
                 (address.attempts as f64) / (address.time_ms as f64 / 1000.0)
             );
         }
+        for (label, value) in address.details() {
+            println!("   {:<12}{value}", format!("{label}:"));
+        }
+        for (label, value) in address.derived() {
+            println!("   {label}: {value}");
+        }
     } else {
         println!("{public_key}");
     }
@@ -212,18 +528,7 @@ pub fn verify_keypair_file(path: &Path) -> OutputResult<VerificationReport> {
     use crate::security::verify_file_permissions;
 
     // Read and parse the keypair file
-    let content = std::fs::read_to_string(path)?;
-    let bytes: Vec<u8> = serde_json::from_str(&content)?;
-
-    if bytes.len() != 64 {
-        return Err(OutputError::InvalidKeypairData);
-    }
-
-    let mut key_bytes = [0u8; 64];
-    key_bytes.copy_from_slice(&bytes);
-
-    let keypair =
-        Keypair::from_bytes(&key_bytes).map_err(|_| OutputError::InvalidKeypairData)?;
+    let (keypair, _) = read_keypair_file(path)?;
 
     // Verify signature
     let signature_valid = verify_keypair_integrity(&keypair).is_ok();
@@ -286,7 +591,7 @@ mod tests {
         let address = GeneratedAddress::new(keypair, 100, 1000);
         let path = dir.path().join("test.json");
 
-        write_keypair_file(&address, &path).unwrap();
+        write_keypair_file(&address, KeypairEncoding::SolanaJson, &path).unwrap();
 
         // Read back and verify
         let content = std::fs::read_to_string(&path).unwrap();
@@ -302,7 +607,7 @@ mod tests {
         let address = GeneratedAddress::new(keypair, 100, 1000);
         let path = dir.path().join("test.json");
 
-        write_keypair_file(&address, &path).unwrap();
+        write_keypair_file(&address, KeypairEncoding::SolanaJson, &path).unwrap();
 
         // Read back like Solana CLI would
         let content = std::fs::read_to_string(&path).unwrap();
@@ -323,17 +628,134 @@ mod tests {
         let address = GeneratedAddress::new(keypair, 100, 1000);
         let path = dir.path().join("test.json");
 
-        write_keypair_file(&address, &path).unwrap();
+        write_keypair_file(&address, KeypairEncoding::SolanaJson, &path).unwrap();
 
         let report = verify_keypair_file(&path).unwrap();
         assert!(report.signature_valid);
         assert!(report.keypair_format_valid);
     }
 
+    #[test]
+    fn test_keypair_encodings_round_trip() {
+        let dir = tempdir().unwrap();
+        let keypair = Keypair::new();
+        let address = GeneratedAddress::new(Keypair::from_bytes(&keypair.to_bytes()).unwrap(), 1, 0);
+        for encoding in KeypairEncoding::ALL {
+            let path = dir.path().join(encoding.name());
+            write_keypair_as(&keypair, encoding, &path).unwrap();
+            let (restored, detected) = read_keypair_file(&path).unwrap();
+            assert_eq!(restored.to_bytes(), keypair.to_bytes());
+            assert_eq!(detected, encoding);
+            assert_eq!(encoding.name().parse::<KeypairEncoding>().unwrap(), encoding);
+
+            // Generated addresses go through the same secure write and verify
+            let path = dir.path().join(format!("address.{}", encoding.extension()));
+            write_keypair_file(&address, encoding, &path).unwrap();
+            let report = verify_keypair_file(&path).unwrap();
+            assert_eq!(report.public_key, address.public_key());
+            assert!(report.signature_valid && report.keypair_format_valid);
+            #[cfg(unix)]
+            assert!(report.file_permissions_secure);
+        }
+
+        // Hex is 128 lowercase digits, read back in either case; raw is the bytes themselves
+        let hex = encode_keypair(&keypair, KeypairEncoding::Hex).unwrap();
+        assert_eq!(hex.len(), 128);
+        assert!(hex.iter().all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(b)));
+        let upper = format!("{}\n", std::str::from_utf8(&hex).unwrap().to_uppercase());
+        assert_eq!(decode_keypair(upper.as_bytes()).unwrap().0.to_bytes(), keypair.to_bytes());
+        let raw = encode_keypair(&keypair, KeypairEncoding::Raw).unwrap();
+        assert_eq!(&raw[..], &keypair.to_bytes()[..]);
+        assert_eq!("bin".parse::<KeypairEncoding>().unwrap(), KeypairEncoding::Raw);
+
+        // The Base58 form is the wallet import string
+        let encoded = encode_keypair(&keypair, KeypairEncoding::Base58).unwrap();
+        assert_eq!(std::str::from_utf8(&encoded).unwrap(), keypair.to_base58_string());
+        assert_eq!("BASE58".parse::<KeypairEncoding>().unwrap(), KeypairEncoding::Base58);
+        assert!("pem".parse::<KeypairEncoding>().is_err());
+    }
+
+    #[test]
+    fn test_decode_rejects_bad_keypairs() {
+        assert!(decode_keypair(b"[1, 2, 3]").is_err());
+        assert!(decode_keypair(b"not-base58-0OIl").is_err());
+        assert!(decode_keypair(Keypair::new().pubkey().to_string().as_bytes()).is_err());
+        assert!(decode_keypair(&[0u8; 63]).is_err());
+        assert!(decode_keypair("g".repeat(128).as_bytes()).is_err());
+        // A raw key whose public half doesn't match its secret half
+        let mut raw = Keypair::new().to_bytes();
+        raw[63] ^= 1;
+        assert!(decode_keypair(&raw).is_err());
+    }
+
     #[test]
     fn test_default_output_path() {
         let path = default_output_path("ABC123xyz");
         assert_eq!(path.to_string_lossy(), "ABC123xyz.json");
+        let fields = OutputFields::default();
+        assert_eq!(OutputTemplate::default().render("ABC123xyz", &fields), "ABC123xyz.json");
+    }
+
+    #[test]
+    fn test_output_template_render() {
+        let template: OutputTemplate = "{job}/{prefix}-{suffix}-{index}-{date}-{pubkey}.json".parse().unwrap();
+        let fields = OutputFields {
+            prefix: "AB".to_string(),
+            suffix: "pump".to_string(),
+            index: 3,
+            date: "2026-10-18".to_string(),
+            job: Some("treasury".to_string()),
+        };
+        assert_eq!(template.render("ABxyz", &fields), "treasury/AB-pump-3-2026-10-18-ABxyz.json");
+        assert!(template.uses("job") && !OutputTemplate::default().uses("job"));
+
+        for bad in ["", "keys/", "{name}.json", "{pubkey.json", "pubkey}.json", "{}.json"] {
+            assert!(
+                matches!(OutputTemplate::new(bad), Err(ConfigError::InvalidOutputTemplate(_))),
+                "{bad}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_output_template_plans_paths() {
+        let dir = Path::new("keys");
+        let fields: Vec<OutputFields> = (1..=3)
+            .map(|index| OutputFields {
+                prefix: "AB".to_string(),
+                index,
+                ..OutputFields::default()
+            })
+            .collect();
+
+        // Names that depend on the key can't be known in advance
+        assert_eq!(OutputTemplate::default().planned_paths(dir, &fields).unwrap(), None);
+
+        let indexed = OutputTemplate::new("{prefix}-{index}.json").unwrap();
+        let paths = indexed.planned_paths(dir, &fields).unwrap().unwrap();
+        assert_eq!(paths[2], Path::new("keys/AB-3.json"));
+
+        let fixed = OutputTemplate::new("{prefix}.json").unwrap();
+        assert!(matches!(
+            fixed.planned_paths(dir, &fields),
+            Err(OutputError::ConfigError(ConfigError::OutputCollision(_)))
+        ));
+        assert!(fixed.planned_paths(dir, &fields[..1]).is_ok());
+
+        assert!(matches!(
+            OutputTemplate::default().planned_paths(Path::new("/etc/keys"), &fields),
+            Err(OutputError::SecurityError(SecurityError::UnsafePathError(_)))
+        ));
+        let job = OutputTemplate::new("{job}-{pubkey}.json").unwrap();
+        assert!(job.planned_paths(dir, &fields).is_err());
+    }
+
+    #[test]
+    fn test_utc_date() {
+        let at = |secs: u64| utc_date(UNIX_EPOCH + std::time::Duration::from_secs(secs));
+        assert_eq!(at(0), "1970-01-01");
+        assert_eq!(at(951_782_400), "2000-02-29");
+        assert_eq!(at(1_791_331_200), "2026-10-07");
     }
 
     #[test]
@@ -345,6 +767,30 @@ mod tests {
         assert!(report.contains(&address.public_key()));
         assert!(report.contains("1000")); // attempts
         assert!(report.contains("NEVER share"));
+        assert!(!report.contains("Match Details"));
+    }
+
+    #[test]
+    fn test_report_includes_details() {
+        let mut address = GeneratedAddress::new(Keypair::new(), 10, 50);
+        address.add_detail("Substitution", "'0' -> 'o' at position 1");
+        let report = generate_report(&address);
+
+        assert!(report.contains("Match Details:"));
+        assert!(report.contains("Substitution: '0' -> 'o' at position 1"));
+        assert!(!report.contains("Derived Addresses:"));
+    }
+
+    #[test]
+    fn test_report_lists_derived_addresses() {
+        let mut address = GeneratedAddress::new(Keypair::new(), 10, 50);
+        address.add_derived("ATA of Owner", "AtaAddress");
+        address.add_derived("programdata", "DataAddress");
+        let report = generate_report(&address);
+
+        assert!(report.contains("Derived Addresses:"));
+        assert!(report.contains("ATA of Owner: AtaAddress"));
+        assert!(report.contains("programdata: DataAddress"));
     }
 }
 
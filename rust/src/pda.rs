@@ -0,0 +1,425 @@
+//! Vanity search for Program Derived Addresses.
+//!
+//! A PDA is derived from a program id and a list of seeds, so its vanity
+//! search varies a nonce seed instead of generating keypairs. There is no
+//! secret key: the result is a record of the seeds and bump that reproduce
+//! the address.
+
+use crate::config::ConfigError;
+use crate::generator::{counter_search, GeneratorResult, ParallelSearch, SearchProgress, VanityGeneratorConfig};
+use crate::matcher::{MatchTarget, OptimizedMatcher};
+use crate::output::OutputResult;
+use crate::raw::{from_hex, to_hex};
+use crate::security::secure_write_file;
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::{Pubkey, MAX_SEEDS, MAX_SEED_LEN};
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+use std::time::Instant;
+
+/// How the PDA is derived from its seeds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PdaDerivation {
+    /// `Pubkey::create_program_address`: the seeds as given, skipping
+    /// nonces whose address falls on the curve
+    CreateProgramAddress,
+    /// `Pubkey::find_program_address`: the canonical bump is appended
+    FindProgramAddress,
+}
+
+impl PdaDerivation {
+    /// Seed slots left for the caller after the derivation's own needs
+    fn max_seeds(self) -> usize {
+        match self {
+            Self::CreateProgramAddress => MAX_SEEDS,
+            // find_program_address appends the bump seed
+            Self::FindProgramAddress => MAX_SEEDS - 1,
+        }
+    }
+}
+
+impl fmt::Display for PdaDerivation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::CreateProgramAddress => write!(f, "create_program_address"),
+            Self::FindProgramAddress => write!(f, "find_program_address (canonical bump)"),
+        }
+    }
+}
+
+/// How the nonce counter is turned into seed bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NonceEncoding {
+    /// 8-byte little-endian `u64` (`nonce.to_le_bytes()` on-chain)
+    LittleEndian,
+    /// 8-byte big-endian `u64`
+    BigEndian,
+    /// Decimal digits as UTF-8 (`nonce.to_string().as_bytes()`)
+    Decimal,
+}
+
+impl NonceEncoding {
+    /// Encode a nonce as seed bytes
+    pub fn encode(self, nonce: u64) -> Vec<u8> {
+        match self {
+            Self::LittleEndian => nonce.to_le_bytes().to_vec(),
+            Self::BigEndian => nonce.to_be_bytes().to_vec(),
+            Self::Decimal => nonce.to_string().into_bytes(),
+        }
+    }
+}
+
+impl FromStr for NonceEncoding {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "le" | "u64le" => Ok(Self::LittleEndian),
+            "be" | "u64be" => Ok(Self::BigEndian),
+            "str" | "decimal" => Ok(Self::Decimal),
+            other => Err(format!(
+                "unknown nonce encoding '{other}' (expected le, be or str)"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for NonceEncoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::LittleEndian => write!(f, "u64 little-endian"),
+            Self::BigEndian => write!(f, "u64 big-endian"),
+            Self::Decimal => write!(f, "decimal string"),
+        }
+    }
+}
+
+/// Parse a seed given as `str:TEXT`, `hex:BYTES`, `pubkey:BASE58`, or plain text
+pub fn parse_seed(spec: &str) -> Result<Vec<u8>, ConfigError> {
+    let seed = if let Some(text) = spec.strip_prefix("str:") {
+        text.as_bytes().to_vec()
+    } else if let Some(hex) = spec.strip_prefix("hex:") {
+        from_hex(hex).ok_or_else(|| ConfigError::InvalidSeed(format!("'{hex}' is not hex")))?
+    } else if let Some(key) = spec.strip_prefix("pubkey:") {
+        Pubkey::from_str(key)
+            .map_err(|_| ConfigError::InvalidSeed(format!("'{key}' is not a valid pubkey")))?
+            .to_bytes()
+            .to_vec()
+    } else {
+        spec.as_bytes().to_vec()
+    };
+
+    if seed.len() > MAX_SEED_LEN {
+        return Err(ConfigError::InvalidSeed(format!(
+            "seed is {} bytes, the maximum is {MAX_SEED_LEN}",
+            seed.len()
+        )));
+    }
+    Ok(seed)
+}
+
+/// A seed as written to a PDA record
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SeedRecord {
+    /// Seed bytes as lowercase hex
+    pub hex: String,
+    /// Seed bytes as text, when they are printable UTF-8
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub utf8: Option<String>,
+}
+
+impl SeedRecord {
+    fn new(bytes: &[u8]) -> Self {
+        Self {
+            hex: to_hex(bytes),
+            utf8: std::str::from_utf8(bytes)
+                .ok()
+                .filter(|text| !text.chars().any(char::is_control))
+                .map(str::to_string),
+        }
+    }
+}
+
+/// A vanity PDA and everything needed to derive it again
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PdaMatch {
+    /// The derived address
+    pub address: String,
+    /// Program the address is derived under
+    pub program_id: String,
+    /// How the address was derived
+    pub derivation: PdaDerivation,
+    /// Every seed in order, including the nonce seed (but not the bump)
+    pub seeds: Vec<SeedRecord>,
+    /// The nonce counter value
+    pub nonce: u64,
+    /// How the nonce was encoded into its seed
+    pub nonce_encoding: NonceEncoding,
+    /// Index of the nonce seed within `seeds`
+    pub nonce_index: usize,
+    /// Canonical bump, for `find_program_address` derivations
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub bump: Option<u8>,
+    /// Nonces tried across all threads
+    pub attempts: u64,
+    /// Search time in milliseconds
+    pub time_ms: u64,
+}
+
+/// Write a PDA record as pretty-printed JSON
+pub fn write_pda_record(record: &PdaMatch, path: &Path) -> OutputResult<()> {
+    let json = serde_json::to_string_pretty(record)?;
+    secure_write_file(path, json.as_bytes())?;
+    log::info!("Wrote PDA record to: {}", path.display());
+    Ok(())
+}
+
+/// Parallel search for a nonce seed whose PDA matches a target
+pub struct PdaSearch {
+    config: VanityGeneratorConfig,
+    matcher: OptimizedMatcher,
+    program_id: Pubkey,
+    seeds: Vec<Vec<u8>>,
+    derivation: PdaDerivation,
+    encoding: NonceEncoding,
+    progress: SearchProgress,
+}
+
+impl PdaSearch {
+    /// Create a search; the nonce seed is appended after `seeds`
+    pub fn new(
+        program_id: Pubkey,
+        seeds: Vec<Vec<u8>>,
+        target: MatchTarget,
+        derivation: PdaDerivation,
+        config: VanityGeneratorConfig,
+    ) -> Result<Self, ConfigError> {
+        if seeds.len() + 1 > derivation.max_seeds() {
+            return Err(ConfigError::InvalidSeed(format!(
+                "{} fixed seeds plus the nonce exceed the limit of {}",
+                seeds.len(),
+                derivation.max_seeds()
+            )));
+        }
+        if let Some(seed) = seeds.iter().find(|s| s.len() > MAX_SEED_LEN) {
+            return Err(ConfigError::InvalidSeed(format!(
+                "seed is {} bytes, the maximum is {MAX_SEED_LEN}",
+                seed.len()
+            )));
+        }
+
+        Ok(Self {
+            config,
+            matcher: OptimizedMatcher::new(target),
+            program_id,
+            seeds,
+            derivation,
+            encoding: NonceEncoding::LittleEndian,
+            progress: SearchProgress::default(),
+        })
+    }
+
+    /// Encode the nonce seed differently (default: little-endian `u64`)
+    pub fn with_encoding(mut self, encoding: NonceEncoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    /// Expected number of nonces to try
+    ///
+    /// About half of all hashes land on the curve; `create_program_address`
+    /// rejects those nonces, while `find_program_address` retries with the
+    /// next bump instead (costing time, not attempts).
+    pub fn expected_attempts(&self) -> f64 {
+        let probability = self.matcher.target().probability();
+        match self.derivation {
+            PdaDerivation::CreateProgramAddress => 2.0 / probability,
+            PdaDerivation::FindProgramAddress => 1.0 / probability,
+        }
+    }
+
+    /// Derive the address for one nonce, if it has one
+    pub fn derive(&self, nonce: u64) -> Option<(Pubkey, Option<u8>)> {
+        let nonce_seed = self.encoding.encode(nonce);
+        let mut seeds: Vec<&[u8]> = self.seeds.iter().map(Vec::as_slice).collect();
+        seeds.push(&nonce_seed);
+
+        match self.derivation {
+            PdaDerivation::CreateProgramAddress => {
+                Pubkey::create_program_address(&seeds, &self.program_id)
+                    .ok()
+                    .map(|address| (address, None))
+            }
+            PdaDerivation::FindProgramAddress => {
+                Pubkey::try_find_program_address(&seeds, &self.program_id)
+                    .map(|(address, bump)| (address, Some(bump)))
+            }
+        }
+    }
+
+    /// Search nonces from `start` until a derived address matches
+    pub fn run(&self, start: u64) -> GeneratorResult<PdaMatch> {
+        let start_time = Instant::now();
+        let (nonce, (address, bump)) = counter_search(
+            &self.config,
+            &self.progress,
+            start..u64::MAX,
+            |nonce| {
+                let (address, bump) = self.derive(nonce)?;
+                (self.matcher.matches_key(&address.to_bytes())
+                    && self.matcher.matches(&address.to_string()))
+                .then_some((address, bump))
+            },
+        )?;
+
+        let mut seeds: Vec<SeedRecord> = self.seeds.iter().map(|s| SeedRecord::new(s)).collect();
+        seeds.push(SeedRecord::new(&self.encoding.encode(nonce)));
+
+        Ok(PdaMatch {
+            address: address.to_string(),
+            program_id: self.program_id.to_string(),
+            derivation: self.derivation,
+            nonce_index: seeds.len() - 1,
+            seeds,
+            nonce,
+            nonce_encoding: self.encoding,
+            bump,
+            attempts: self.attempts(),
+            time_ms: start_time.elapsed().as_millis() as u64,
+        })
+    }
+}
+
+impl ParallelSearch for PdaSearch {
+    fn progress(&self) -> &SearchProgress {
+        &self.progress
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::config;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_parse_seed() {
+        assert_eq!(parse_seed("vault").unwrap(), b"vault".to_vec());
+        assert_eq!(parse_seed("str:hex:x").unwrap(), b"hex:x".to_vec());
+        assert_eq!(parse_seed("hex:00ff").unwrap(), vec![0x00, 0xff]);
+        assert_eq!(
+            parse_seed("pubkey:11111111111111111111111111111111").unwrap(),
+            vec![0u8; 32]
+        );
+        assert!(parse_seed("hex:0").is_err());
+        assert!(parse_seed(&"x".repeat(33)).is_err());
+    }
+
+    #[test]
+    fn test_nonce_encoding() {
+        assert_eq!(NonceEncoding::LittleEndian.encode(1), vec![1, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(NonceEncoding::BigEndian.encode(1), vec![0, 0, 0, 0, 0, 0, 0, 1]);
+        assert_eq!(NonceEncoding::Decimal.encode(42), b"42".to_vec());
+        assert_eq!("str".parse::<NonceEncoding>().unwrap(), NonceEncoding::Decimal);
+        assert_eq!(NonceEncoding::LittleEndian.to_string(), "u64 little-endian");
+        assert_eq!(PdaDerivation::CreateProgramAddress.to_string(), "create_program_address");
+    }
+
+    #[test]
+    fn test_too_many_seeds() {
+        let target = MatchTarget::prefix("A", false).unwrap();
+        let seeds = vec![b"s".to_vec(); MAX_SEEDS - 1];
+        assert!(PdaSearch::new(
+            Pubkey::new_unique(),
+            seeds.clone(),
+            target.clone(),
+            PdaDerivation::FindProgramAddress,
+            config()
+        )
+        .is_err());
+        assert!(PdaSearch::new(
+            Pubkey::new_unique(),
+            seeds,
+            target,
+            PdaDerivation::CreateProgramAddress,
+            config()
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_find_program_address_search() {
+        let program_id = Pubkey::new_unique();
+        let target = MatchTarget::prefix("A", false).unwrap();
+        let search = PdaSearch::new(
+            program_id,
+            vec![b"vault".to_vec()],
+            target,
+            PdaDerivation::FindProgramAddress,
+            config(),
+        )
+        .unwrap();
+
+        let found = search.run(0).unwrap();
+        assert!(found.address.starts_with('A'));
+
+        // The record reproduces the address with the canonical bump
+        let nonce = found.nonce.to_le_bytes();
+        let (expected, bump) = Pubkey::find_program_address(&[b"vault", &nonce], &program_id);
+        assert_eq!(found.address, expected.to_string());
+        assert_eq!(found.bump, Some(bump));
+        assert_eq!(found.seeds[0].utf8.as_deref(), Some("vault"));
+        assert_eq!(found.seeds[1].hex, to_hex(&nonce));
+        assert_eq!(found.nonce_index, 1);
+    }
+
+    #[test]
+    fn test_create_program_address_search() {
+        let program_id = Pubkey::new_unique();
+        let target = MatchTarget::suffix("z", false).unwrap();
+        let search = PdaSearch::new(
+            program_id,
+            Vec::new(),
+            target,
+            PdaDerivation::CreateProgramAddress,
+            config(),
+        )
+        .unwrap()
+        .with_encoding(NonceEncoding::Decimal);
+
+        let found = search.run(1_000).unwrap();
+        assert!(found.nonce >= 1_000);
+        assert_eq!(found.bump, None);
+
+        let seed = found.nonce.to_string();
+        let expected = Pubkey::create_program_address(&[seed.as_bytes()], &program_id).unwrap();
+        assert_eq!(found.address, expected.to_string());
+    }
+
+    #[test]
+    fn test_write_record() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("pda.json");
+        let record = PdaMatch {
+            address: "Addr".to_string(),
+            program_id: "Prog".to_string(),
+            derivation: PdaDerivation::FindProgramAddress,
+            seeds: vec![SeedRecord::new(b"vault"), SeedRecord::new(&[0xff])],
+            nonce: 7,
+            nonce_encoding: NonceEncoding::LittleEndian,
+            nonce_index: 1,
+            bump: Some(254),
+            attempts: 10,
+            time_ms: 1,
+        };
+        write_pda_record(&record, &path).unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(content.contains("\"find_program_address\""));
+        let restored: PdaMatch = serde_json::from_str(&content).unwrap();
+        assert_eq!(restored, record);
+    }
+}
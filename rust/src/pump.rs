@@ -0,0 +1,565 @@
+//! pump.fun mint mode.
+//!
+//! pump.fun launches use mint keypairs whose address ends in `pump`. This
+//! module builds that target (with an optional ticker prefix) and describes
+//! a found mint with the accounts pump.fun derives from it, in a JSON shape
+//! the TypeScript SDK can read with `JSON.parse` and `new PublicKey(...)`.
+//!
+//! It can also build the `createV2` transaction for a found mint offline and
+//! sign it with the mint, so the mint secret never leaves this process; the
+//! creator's wallet adds the remaining signature.
+
+use crate::config::ConfigError;
+use crate::derived::{
+    associated_token_address, ASSOCIATED_TOKEN_PROGRAM_ID, TOKEN_2022_PROGRAM_ID, TOKEN_PROGRAM_ID,
+};
+use crate::matcher::{CaseRule, MatchTarget};
+use crate::output::{GeneratedAddress, OutputResult};
+use crate::security::secure_write_file;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use solana_sdk::hash::Hash;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::message::Message;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Keypair;
+use solana_sdk::signer::{Signer, SignerError};
+use solana_sdk::transaction::Transaction;
+use solana_sdk::{system_instruction, system_program};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// pump.fun bonding curve program
+pub const PUMP_PROGRAM_ID: Pubkey = solana_sdk::pubkey!("6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P");
+
+/// Mayhem mode program used by `createV2`
+pub const MAYHEM_PROGRAM_ID: Pubkey = solana_sdk::pubkey!("MAyhSmzXzV1pTf7LsNkrNwkWKTo4ougAJ1PPg47MD4e");
+
+/// Anchor discriminator of the `create_v2` instruction
+pub const CREATE_V2_DISCRIMINATOR: [u8; 8] = [214, 144, 76, 236, 95, 139, 49, 180];
+
+/// Longest token name Metaplex metadata accepts, in bytes
+pub const MAX_NAME_LENGTH: usize = 32;
+
+/// Longest token symbol Metaplex metadata accepts, in bytes
+pub const MAX_SYMBOL_LENGTH: usize = 10;
+
+/// Longest metadata URI Metaplex metadata accepts, in bytes
+pub const MAX_URI_LENGTH: usize = 200;
+
+/// Suffix pump.fun mint addresses end with
+pub const PUMP_MINT_SUFFIX: &str = "pump";
+
+/// Version of the descriptor layout
+pub const DESCRIPTOR_VERSION: u32 = 1;
+
+/// Bonding curve PDA for a mint (`["bonding-curve", mint]`)
+pub fn bonding_curve_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"bonding-curve", mint.as_ref()], &PUMP_PROGRAM_ID).0
+}
+
+/// Token account of the bonding curve for a mint under `token_program`
+pub fn associated_bonding_curve_address(mint: &Pubkey, token_program: &Pubkey) -> Pubkey {
+    associated_token_address(&bonding_curve_address(mint), mint, token_program)
+}
+
+fn pump_pda(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &PUMP_PROGRAM_ID).0
+}
+
+fn mayhem_pda(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &MAYHEM_PROGRAM_ID).0
+}
+
+/// Match target for a pump.fun mint: optional ticker prefix, exact `pump` suffix
+///
+/// `ticker_case_insensitive` only relaxes the ticker; the suffix always
+/// matches exactly, as pump.fun requires.
+pub fn pump_mint_target(
+    ticker: Option<&str>,
+    ticker_case_insensitive: bool,
+) -> Result<MatchTarget, ConfigError> {
+    MatchTarget::segments(
+        ticker.map(|t| (t, CaseRule::from_flag(ticker_case_insensitive))),
+        Some((PUMP_MINT_SUFFIX, CaseRule::Sensitive)),
+    )
+}
+
+/// Record the pump.fun accounts derived from a mint on the result
+pub fn add_pump_addresses(address: &mut GeneratedAddress) {
+    let mint = address.keypair().pubkey();
+    address.add_derived("Bonding curve", bonding_curve_address(&mint).to_string());
+    address.add_derived(
+        "Associated bonding curve",
+        associated_bonding_curve_address(&mint, &TOKEN_PROGRAM_ID).to_string(),
+    );
+    address.add_derived(
+        "Associated bonding curve (Token-2022)",
+        associated_bonding_curve_address(&mint, &TOKEN_2022_PROGRAM_ID).to_string(),
+    );
+}
+
+/// SDK-ready description of a vanity pump.fun mint (no secret data)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PumpMintDescriptor {
+    /// Descriptor layout version
+    pub version: u32,
+    /// Mint address
+    pub mint: String,
+    /// Keypair file holding the mint secret, if written
+    pub keypair_file: Option<String>,
+    /// Ticker the address starts with, if any
+    pub ticker: Option<String>,
+    /// pump.fun program id
+    pub program_id: String,
+    /// Bonding curve PDA
+    pub bonding_curve: String,
+    /// Bonding curve token account for an SPL Token mint (`create`)
+    pub associated_bonding_curve: String,
+    /// Bonding curve token account for a Token-2022 mint (`createV2`)
+    pub associated_bonding_curve_token2022: String,
+    /// Keys generated before the match
+    pub attempts: u64,
+    /// Search time in milliseconds
+    pub time_ms: u64,
+    /// When the descriptor was written (seconds since the Unix epoch)
+    pub created_at: u64,
+    /// Base64 `createV2` transaction signed by the mint, if one was built
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub create_transaction: Option<String>,
+}
+
+impl PumpMintDescriptor {
+    /// Describe a found mint, optionally pointing at its keypair file
+    pub fn new(address: &GeneratedAddress, ticker: Option<&str>, keypair_file: Option<&Path>) -> Self {
+        let mint = address.keypair().pubkey();
+        Self {
+            version: DESCRIPTOR_VERSION,
+            mint: mint.to_string(),
+            keypair_file: keypair_file.map(|p| p.display().to_string()),
+            ticker: ticker.map(str::to_string),
+            program_id: PUMP_PROGRAM_ID.to_string(),
+            bonding_curve: bonding_curve_address(&mint).to_string(),
+            associated_bonding_curve: associated_bonding_curve_address(&mint, &TOKEN_PROGRAM_ID)
+                .to_string(),
+            associated_bonding_curve_token2022: associated_bonding_curve_address(
+                &mint,
+                &TOKEN_2022_PROGRAM_ID,
+            )
+            .to_string(),
+            attempts: address.attempts,
+            time_ms: address.time_ms,
+            created_at: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or_default(),
+            create_transaction: None,
+        }
+    }
+
+    /// Attach a base64 create transaction
+    pub fn with_create_transaction(mut self, transaction: String) -> Self {
+        self.create_transaction = Some(transaction);
+        self
+    }
+}
+
+/// Descriptor path next to a keypair file: `<stem>.pump.json`
+pub fn descriptor_path(keypair_path: &Path) -> PathBuf {
+    let stem = keypair_path.file_stem().unwrap_or_default().to_string_lossy();
+    keypair_path.with_file_name(format!("{stem}.pump.json"))
+}
+
+/// Write a mint descriptor as pretty-printed JSON
+pub fn write_pump_descriptor(descriptor: &PumpMintDescriptor, path: &Path) -> OutputResult<()> {
+    let json = serde_json::to_string_pretty(descriptor)?;
+    secure_write_file(path, json.as_bytes())?;
+    log::info!("Wrote pump.fun mint descriptor to: {}", path.display());
+    Ok(())
+}
+
+/// Errors from building a create transaction
+#[derive(Error, Debug)]
+pub enum PumpError {
+    #[error("Configuration error: {0}")]
+    ConfigError(#[from] ConfigError),
+
+    #[error("Signing failed: {0}")]
+    SigningFailed(#[from] SignerError),
+
+    #[error("Serialization failed: {0}")]
+    SerializationFailed(#[from] bincode::Error),
+}
+
+/// Token metadata and launch options for `createV2`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CreateTokenArgs {
+    name: String,
+    symbol: String,
+    uri: String,
+    mayhem_mode: bool,
+    cashback: bool,
+}
+
+impl CreateTokenArgs {
+    /// Validate token metadata against the Metaplex length limits
+    pub fn new(name: &str, symbol: &str, uri: &str) -> Result<Self, ConfigError> {
+        for (field, value, max) in [
+            ("name", name, MAX_NAME_LENGTH),
+            ("symbol", symbol, MAX_SYMBOL_LENGTH),
+            ("uri", uri, MAX_URI_LENGTH),
+        ] {
+            if value.is_empty() {
+                return Err(ConfigError::InvalidTokenMetadata(format!("{field} is empty")));
+            }
+            if value.len() > max {
+                return Err(ConfigError::InvalidTokenMetadata(format!(
+                    "{field} is {} bytes, the limit is {max}",
+                    value.len()
+                )));
+            }
+        }
+        Ok(Self {
+            name: name.to_string(),
+            symbol: symbol.to_string(),
+            uri: uri.to_string(),
+            mayhem_mode: false,
+            cashback: false,
+        })
+    }
+
+    /// Launch the token in mayhem mode
+    pub fn with_mayhem_mode(mut self, enabled: bool) -> Self {
+        self.mayhem_mode = enabled;
+        self
+    }
+
+    /// Enable cashback for the token
+    pub fn with_cashback(mut self, enabled: bool) -> Self {
+        self.cashback = enabled;
+        self
+    }
+
+    /// Borsh-encoded instruction arguments, after the discriminator
+    fn encode(&self, creator: &Pubkey) -> Vec<u8> {
+        let mut data = Vec::new();
+        for text in [&self.name, &self.symbol, &self.uri] {
+            data.extend_from_slice(&(text.len() as u32).to_le_bytes());
+            data.extend_from_slice(text.as_bytes());
+        }
+        data.extend_from_slice(creator.as_ref());
+        data.push(self.mayhem_mode as u8);
+        data.push(self.cashback as u8);
+        data
+    }
+}
+
+/// How the transaction stays valid until the creator signs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TxLifetime {
+    /// A recent blockhash (valid for about a minute)
+    Blockhash(Hash),
+    /// A durable nonce; the nonce authority must also sign
+    DurableNonce {
+        account: Pubkey,
+        authority: Pubkey,
+        nonce: Hash,
+    },
+}
+
+/// Build the pump.fun `createV2` instruction for a Token-2022 mint
+///
+/// `user` pays for the accounts and `creator` receives creator fees; both
+/// are usually the same wallet.
+pub fn create_v2_instruction(
+    mint: &Pubkey,
+    creator: &Pubkey,
+    user: &Pubkey,
+    args: &CreateTokenArgs,
+) -> Instruction {
+    let bonding_curve = bonding_curve_address(mint);
+    let sol_vault = mayhem_pda(&[b"sol-vault"]);
+
+    let mut data = CREATE_V2_DISCRIMINATOR.to_vec();
+    data.extend(args.encode(creator));
+
+    Instruction {
+        program_id: PUMP_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*mint, true),
+            AccountMeta::new_readonly(pump_pda(&[b"mint-authority"]), false),
+            AccountMeta::new(bonding_curve, false),
+            AccountMeta::new(
+                associated_token_address(&bonding_curve, mint, &TOKEN_2022_PROGRAM_ID),
+                false,
+            ),
+            AccountMeta::new_readonly(pump_pda(&[b"global"]), false),
+            AccountMeta::new(*user, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
+            AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+            AccountMeta::new(MAYHEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(mayhem_pda(&[b"global-params"]), false),
+            AccountMeta::new(sol_vault, false),
+            AccountMeta::new(mayhem_pda(&[b"mayhem-state", mint.as_ref()]), false),
+            AccountMeta::new(
+                associated_token_address(&sol_vault, mint, &TOKEN_2022_PROGRAM_ID),
+                false,
+            ),
+            AccountMeta::new_readonly(pump_pda(&[b"__event_authority"]), false),
+            AccountMeta::new_readonly(PUMP_PROGRAM_ID, false),
+        ],
+        data,
+    }
+}
+
+/// Build a create transaction paid by `creator` and signed by the mint only
+///
+/// The creator's signature (and the nonce authority's, for a durable nonce)
+/// is left empty for their wallet to add.
+pub fn build_create_transaction(
+    mint: &Keypair,
+    creator: &Pubkey,
+    args: &CreateTokenArgs,
+    lifetime: &TxLifetime,
+) -> Result<Transaction, PumpError> {
+    let create = create_v2_instruction(&mint.pubkey(), creator, creator, args);
+    let (instructions, blockhash) = match *lifetime {
+        TxLifetime::Blockhash(hash) => (vec![create], hash),
+        TxLifetime::DurableNonce { account, authority, nonce } => (
+            vec![system_instruction::advance_nonce_account(&account, &authority), create],
+            nonce,
+        ),
+    };
+
+    let message = Message::new_with_blockhash(&instructions, Some(creator), &blockhash);
+    let mut transaction = Transaction::new_unsigned(message);
+    transaction.try_partial_sign(&[mint], blockhash)?;
+    Ok(transaction)
+}
+
+/// Serialize a transaction in wire format and encode it as base64
+pub fn encode_transaction(transaction: &Transaction) -> Result<String, PumpError> {
+    let bytes = bincode::serialize(transaction)?;
+    Ok(base64::engine::general_purpose::STANDARD.encode(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_pump_mint_target() {
+        let target = pump_mint_target(Some("dog"), true).unwrap();
+        assert!(target.matches("DoGxxxxpump"));
+        assert!(!target.matches("DoGxxxxPUMP"));
+
+        let target = pump_mint_target(None, false).unwrap();
+        assert!(target.matches("anythingpump"));
+        assert!(!target.matches("anythingPump"));
+
+        assert!(pump_mint_target(Some("0"), false).is_err());
+    }
+
+    #[test]
+    fn test_bonding_curve_addresses() {
+        let mint = Pubkey::new_unique();
+        let (expected, _) =
+            Pubkey::find_program_address(&[b"bonding-curve", mint.as_ref()], &PUMP_PROGRAM_ID);
+        assert_eq!(bonding_curve_address(&mint), expected);
+        assert_eq!(
+            associated_bonding_curve_address(&mint, &TOKEN_PROGRAM_ID),
+            associated_token_address(&expected, &mint, &TOKEN_PROGRAM_ID)
+        );
+        assert_ne!(
+            associated_bonding_curve_address(&mint, &TOKEN_PROGRAM_ID),
+            associated_bonding_curve_address(&mint, &TOKEN_2022_PROGRAM_ID)
+        );
+    }
+
+    #[test]
+    fn test_descriptor_round_trip() {
+        let dir = tempdir().unwrap();
+        let address = GeneratedAddress::new(Keypair::new(), 42, 7);
+        let keypair_path = dir.path().join("mint.json");
+        let descriptor = PumpMintDescriptor::new(&address, Some("MEME"), Some(&keypair_path));
+        assert_eq!(descriptor.mint, address.public_key());
+        assert_eq!(descriptor.attempts, 42);
+
+        let path = descriptor_path(&keypair_path);
+        assert_eq!(path, dir.path().join("mint.pump.json"));
+        write_pump_descriptor(&descriptor, &path).unwrap();
+
+        let json = std::fs::read_to_string(&path).unwrap();
+        assert!(json.contains("\"bondingCurve\""));
+        assert!(json.contains("\"associatedBondingCurve\""));
+        let restored: PumpMintDescriptor = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, descriptor);
+    }
+
+    #[test]
+    fn test_program_constants_match_idl() {
+        // Program ids the IDL embeds as raw bytes in its PDA definitions
+        let mayhem = [
+            5, 42, 229, 215, 167, 218, 167, 36, 166, 234, 176, 167, 41, 84, 145, 133, 90, 212,
+            160, 103, 22, 96, 103, 76, 78, 3, 69, 89, 128, 61, 101, 163,
+        ];
+        let associated_token = [
+            140, 151, 37, 143, 78, 36, 137, 241, 187, 61, 16, 41, 20, 142, 13, 131, 11, 90, 19,
+            153, 218, 255, 16, 132, 4, 142, 123, 216, 219, 233, 248, 89,
+        ];
+        assert_eq!(MAYHEM_PROGRAM_ID, Pubkey::new_from_array(mayhem));
+        assert_eq!(ASSOCIATED_TOKEN_PROGRAM_ID, Pubkey::new_from_array(associated_token));
+
+        // Fixed account addresses of `create_v2` in the IDL
+        assert_eq!(TOKEN_2022_PROGRAM_ID.to_string(), "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+        assert_eq!(ASSOCIATED_TOKEN_PROGRAM_ID.to_string(), "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
+        assert_eq!(MAYHEM_PROGRAM_ID.to_string(), "MAyhSmzXzV1pTf7LsNkrNwkWKTo4ougAJ1PPg47MD4e");
+    }
+
+    #[test]
+    fn test_create_token_args_validation() {
+        assert!(CreateTokenArgs::new("Meme", "MEME", "https://example.com/m.json").is_ok());
+        assert!(CreateTokenArgs::new("", "MEME", "uri").is_err());
+        assert!(CreateTokenArgs::new("Meme", "TOOLONGSYMBOL", "uri").is_err());
+        assert!(CreateTokenArgs::new(&"n".repeat(33), "M", "uri").is_err());
+    }
+
+    #[test]
+    fn test_create_v2_instruction_layout() {
+        let mint = Pubkey::new_unique();
+        let creator = Pubkey::new_unique();
+        let args = CreateTokenArgs::new("Meme", "MEME", "uri").unwrap().with_cashback(true);
+        let ix = create_v2_instruction(&mint, &creator, &creator, &args);
+
+        assert_eq!(ix.program_id, PUMP_PROGRAM_ID);
+        assert_eq!(ix.accounts.len(), 16);
+        assert_eq!(ix.accounts[0], AccountMeta::new(mint, true));
+        assert_eq!(ix.accounts[2].pubkey, bonding_curve_address(&mint));
+        assert_eq!(
+            ix.accounts[3].pubkey,
+            associated_bonding_curve_address(&mint, &TOKEN_2022_PROGRAM_ID)
+        );
+        assert_eq!(ix.accounts[5], AccountMeta::new(creator, true));
+
+        let mut expected = CREATE_V2_DISCRIMINATOR.to_vec();
+        expected.extend([4, 0, 0, 0]);
+        expected.extend(b"Meme");
+        expected.extend([4, 0, 0, 0]);
+        expected.extend(b"MEME");
+        expected.extend([3, 0, 0, 0]);
+        expected.extend(b"uri");
+        expected.extend(creator.to_bytes());
+        expected.extend([0, 1]);
+        assert_eq!(ix.data, expected);
+    }
+
+    #[test]
+    fn test_create_v2_accounts_match_idl() {
+        let mint = solana_sdk::signer::keypair::keypair_from_seed(&[7; 32]).unwrap().pubkey();
+        let user = Pubkey::new_unique();
+        let args = CreateTokenArgs::new("Meme", "MEME", "uri").unwrap();
+        let ix = create_v2_instruction(&mint, &user, &user, &args);
+
+        // `create_v2` accounts in IDL order: (address, writable, signer). Fixed
+        // addresses come from the IDL; PDAs were derived independently from its seeds
+        let expected = [
+            ("GmaDrppBC7P5ARKV8g3djiwP89vz1jLK23V2GBjuAEGB", true, true), // mint
+            ("TSLvdd1pWpHVjahSpsvCXUbgwsL3JAcvokwaKt1eokM", false, false), // mint_authority
+            ("62QBCZYVD5otiQP21Ls6tfVscDhuhD74Ri6zBMFpCTdo", true, false), // bonding_curve
+            ("76gUG8RShyFDKwQEFtJFtFvqsgKmwCBNK6deB4DVKDb1", true, false), // associated_bonding_curve
+            ("4wTV1YmiEkRvAtNtsSGPtUrqRYQMe5SKy2uB4Jjaxnjf", false, false), // global
+            (&user.to_string(), true, true),                                 // user
+            ("11111111111111111111111111111111", false, false),            // system_program
+            ("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb", false, false), // token_program
+            ("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL", false, false), // associated_token_program
+            ("MAyhSmzXzV1pTf7LsNkrNwkWKTo4ougAJ1PPg47MD4e", true, false),  // mayhem_program_id
+            ("13ec7XdrjF3h3YcqBTFDSReRcUFwbCnJaAQspM4j6DDJ", false, false), // global_params
+            ("BwWK17cbHxwWBKZkUYvzxLcNQ1YVyaFezduWbtm2de6s", true, false), // sol_vault
+            ("2AEgEoikAhJxpddizGVRHhxo1ZiWp5MY9NXiFwZ3pYEZ", true, false), // mayhem_state
+            ("ExrWw2gLuWFyfVx6RTecup2k4bjUhw75jGa2wGafbZze", true, false), // mayhem_token_vault
+            ("Ce6TQqeHC9p8KetsN6JsjHK7UTZk7nasjjnr7XxXp9F1", false, false), // event_authority
+            ("6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P", false, false), // program
+        ];
+        let actual: Vec<(String, bool, bool)> =
+            ix.accounts.iter().map(|meta| (meta.pubkey.to_string(), meta.is_writable, meta.is_signer)).collect();
+        let expected: Vec<(String, bool, bool)> =
+            expected.iter().map(|&(address, writable, signer)| (address.to_string(), writable, signer)).collect();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_build_create_transaction_partially_signed() {
+        let mint = Keypair::new();
+        let creator = Pubkey::new_unique();
+        let args = CreateTokenArgs::new("Meme", "MEME", "uri").unwrap();
+        let blockhash = Hash::new_unique();
+        let tx = build_create_transaction(&mint, &creator, &args, &TxLifetime::Blockhash(blockhash))
+            .unwrap();
+
+        assert_eq!(tx.message.account_keys[0], creator);
+        assert_eq!(tx.message.recent_blockhash, blockhash);
+        // Creator signature is left empty, the mint's is present
+        assert_eq!(tx.signatures.len(), 2);
+        assert_eq!(tx.signatures[0], Default::default());
+        let mint_index = tx.message.account_keys.iter().position(|k| *k == mint.pubkey()).unwrap();
+        assert!(tx.signatures[mint_index]
+            .verify(mint.pubkey().as_ref(), &tx.message_data()));
+
+        let encoded = encode_transaction(&tx).unwrap();
+        let bytes = base64::engine::general_purpose::STANDARD.decode(encoded).unwrap();
+        let decoded: Transaction = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(decoded, tx);
+    }
+
+    #[test]
+    fn test_build_create_transaction_durable_nonce() {
+        let mint = Keypair::new();
+        let creator = Pubkey::new_unique();
+        let account = Pubkey::new_unique();
+        let nonce = Hash::new_unique();
+        let args = CreateTokenArgs::new("Meme", "MEME", "uri").unwrap();
+        let lifetime = TxLifetime::DurableNonce { account, authority: creator, nonce };
+        let tx = build_create_transaction(&mint, &creator, &args, &lifetime).unwrap();
+
+        assert_eq!(tx.message.recent_blockhash, nonce);
+        assert_eq!(tx.message.instructions.len(), 2);
+        let advance = &tx.message.instructions[0];
+        assert_eq!(tx.message.account_keys[advance.program_id_index as usize], system_program::id());
+        assert_eq!(tx.message.account_keys[advance.accounts[0] as usize], account);
+    }
+
+    #[test]
+    fn test_add_pump_addresses() {
+        let mut address = GeneratedAddress::new(Keypair::new(), 1, 1);
+        add_pump_addresses(&mut address);
+        let mint: Pubkey = address.public_key().parse().unwrap();
+        assert_eq!(address.derived()[0].1, bonding_curve_address(&mint).to_string());
+        assert_eq!(address.derived().len(), 3);
+    }
+
+    #[test]
+    fn test_derived_accounts_known_values() {
+        // Derived independently from the IDL's seeds for the mint with secret seed [7; 32]
+        let mint = solana_sdk::signer::keypair::keypair_from_seed(&[7; 32]).unwrap();
+        assert_eq!(mint.pubkey().to_string(), "GmaDrppBC7P5ARKV8g3djiwP89vz1jLK23V2GBjuAEGB");
+        let mut address = GeneratedAddress::new(mint, 0, 0);
+        let descriptor = PumpMintDescriptor::new(&address, None, None);
+        assert_eq!(descriptor.bonding_curve, "62QBCZYVD5otiQP21Ls6tfVscDhuhD74Ri6zBMFpCTdo");
+        assert_eq!(descriptor.associated_bonding_curve, "BbrDVPrE3tPBfxc4WKuoVTdAuhvMoqDUfoEaN9VsHfUe");
+        assert_eq!(descriptor.associated_bonding_curve_token2022, "76gUG8RShyFDKwQEFtJFtFvqsgKmwCBNK6deB4DVKDb1");
+
+        add_pump_addresses(&mut address);
+        let report: Vec<&str> = address.derived().iter().map(|(_, value)| value.as_str()).collect();
+        assert_eq!(
+            report,
+            [
+                descriptor.bonding_curve.as_str(),
+                descriptor.associated_bonding_curve.as_str(),
+                descriptor.associated_bonding_curve_token2022.as_str(),
+            ]
+        );
+    }
+}
@@ -0,0 +1,516 @@
+//! Byte- and bit-level constraints on the raw 32-byte public key.
+//!
+//! Some programs sort or shard accounts by pubkey bytes, which Base58
+//! patterns can't express. A [`RawTarget`] combines masked byte prefixes,
+//! shard predicates on the top bits and lexicographic bounds. Masked
+//! constraints are merged into a single mask/value pair, so the difficulty
+//! estimate is an exact count rather than an approximation.
+
+use crate::config::ConfigError;
+use solana_sdk::pubkey::Pubkey;
+use std::fmt;
+use std::str::FromStr;
+
+/// Number of bytes in a public key
+pub const KEY_LENGTH: usize = 32;
+
+/// Number of bits in a public key
+const KEY_BITS: usize = KEY_LENGTH * 8;
+
+/// Largest shard width accepted by [`ByteConstraint::shard`]
+pub const MAX_SHARD_BITS: u32 = 32;
+
+/// A single constraint on the raw public key bytes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ByteConstraint {
+    /// Leading bytes must equal `value` wherever `mask` has a bit set
+    MaskedPrefix { value: Vec<u8>, mask: Vec<u8> },
+    /// The top `bits` bits, read as a big-endian integer, must equal `id`
+    Shard { bits: u32, id: u32 },
+    /// The key must sort at or above this key (byte-wise)
+    AtLeast([u8; KEY_LENGTH]),
+    /// The key must sort strictly below this key (byte-wise)
+    Below([u8; KEY_LENGTH]),
+}
+
+impl ByteConstraint {
+    /// Parse a masked prefix given as `HEX` or `HEX/MASK`
+    ///
+    /// Without a mask every bit of the given bytes must match.
+    pub fn masked_prefix(spec: &str) -> Result<Self, ConfigError> {
+        let (value, mask) = match spec.split_once('/') {
+            Some((value, mask)) => (parse_hex(value)?, parse_hex(mask)?),
+            None => {
+                let value = parse_hex(spec)?;
+                let mask = vec![0xff; value.len()];
+                (value, mask)
+            }
+        };
+        if value.is_empty() || value.len() > KEY_LENGTH {
+            return Err(ConfigError::InvalidRawConstraint(format!(
+                "byte prefix must be 1 to {KEY_LENGTH} bytes, got {}",
+                value.len()
+            )));
+        }
+        if mask.len() != value.len() {
+            return Err(ConfigError::InvalidRawConstraint(format!(
+                "mask has {} bytes but prefix has {}",
+                mask.len(),
+                value.len()
+            )));
+        }
+        Ok(Self::MaskedPrefix { value, mask })
+    }
+
+    /// Parse a shard predicate given as `BITS:ID` (ID in decimal or `0x` hex)
+    pub fn shard(spec: &str) -> Result<Self, ConfigError> {
+        let invalid = || {
+            ConfigError::InvalidRawConstraint(format!("expected BITS:ID for shard, got '{spec}'"))
+        };
+        let (bits, id) = spec.split_once(':').ok_or_else(invalid)?;
+        let bits: u32 = bits.trim().parse().map_err(|_| invalid())?;
+        let id = id.trim();
+        let id = match id.strip_prefix("0x") {
+            Some(hex) => u64::from_str_radix(hex, 16),
+            None => id.parse(),
+        }
+        .map_err(|_| invalid())?;
+
+        if bits == 0 || bits > MAX_SHARD_BITS {
+            return Err(ConfigError::InvalidRawConstraint(format!(
+                "shard width must be 1 to {MAX_SHARD_BITS} bits, got {bits}"
+            )));
+        }
+        if id >> bits != 0 {
+            return Err(ConfigError::InvalidRawConstraint(format!(
+                "shard id {id} does not fit in {bits} bits"
+            )));
+        }
+        Ok(Self::Shard { bits, id: id as u32 })
+    }
+
+    /// Parse an inclusive lower bound (Base58 pubkey or 64 hex characters)
+    pub fn at_least(spec: &str) -> Result<Self, ConfigError> {
+        parse_key(spec).map(Self::AtLeast)
+    }
+
+    /// Parse an exclusive upper bound (Base58 pubkey or 64 hex characters)
+    pub fn below(spec: &str) -> Result<Self, ConfigError> {
+        parse_key(spec).map(Self::Below)
+    }
+}
+
+impl fmt::Display for ByteConstraint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MaskedPrefix { value, mask } => {
+                if mask.iter().all(|m| *m == 0xff) {
+                    write!(f, "bytes start with {}", to_hex(value))
+                } else {
+                    write!(f, "bytes start with {}/{}", to_hex(value), to_hex(mask))
+                }
+            }
+            Self::Shard { bits, id } => write!(f, "top {bits} bits = {id:#x}"),
+            Self::AtLeast(key) => write!(f, "key >= {}", Pubkey::new_from_array(*key)),
+            Self::Below(key) => write!(f, "key < {}", Pubkey::new_from_array(*key)),
+        }
+    }
+}
+
+/// A set of raw key constraints that must all hold
+///
+/// Bit constraints are compiled into one mask/value pair; bounds keep only
+/// the tightest lower and upper key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawTarget {
+    constraints: Vec<ByteConstraint>,
+    mask: [u8; KEY_LENGTH],
+    value: [u8; KEY_LENGTH],
+    lower: Option<[u8; KEY_LENGTH]>,
+    upper: Option<[u8; KEY_LENGTH]>,
+}
+
+impl Default for RawTarget {
+    fn default() -> Self {
+        Self {
+            constraints: Vec::new(),
+            mask: [0; KEY_LENGTH],
+            value: [0; KEY_LENGTH],
+            lower: None,
+            upper: None,
+        }
+    }
+}
+
+impl RawTarget {
+    /// Create a target with no constraints
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a constraint
+    ///
+    /// Fails if a bit constraint contradicts one already added.
+    pub fn with(mut self, constraint: ByteConstraint) -> Result<Self, ConfigError> {
+        match &constraint {
+            ByteConstraint::MaskedPrefix { value, mask } => {
+                for (i, (v, m)) in value.iter().zip(mask).enumerate() {
+                    self.require_bits(i, *v & *m, *m, &constraint)?;
+                }
+            }
+            ByteConstraint::Shard { bits, id } => {
+                let bits = *bits as usize;
+                let shifted = u64::from(*id) << (64 - bits);
+                let top_mask = u64::MAX << (64 - bits);
+                for i in 0..8 {
+                    let shift = 56 - 8 * i;
+                    let m = (top_mask >> shift) as u8;
+                    if m != 0 {
+                        self.require_bits(i, (shifted >> shift) as u8, m, &constraint)?;
+                    }
+                }
+            }
+            ByteConstraint::AtLeast(key) => {
+                self.lower = Some(self.lower.map_or(*key, |lower| lower.max(*key)));
+            }
+            ByteConstraint::Below(key) => {
+                self.upper = Some(self.upper.map_or(*key, |upper| upper.min(*key)));
+            }
+        }
+        self.constraints.push(constraint);
+        Ok(self)
+    }
+
+    /// The constraints in the order they were added
+    pub fn constraints(&self) -> &[ByteConstraint] {
+        &self.constraints
+    }
+
+    /// Check if no constraints are configured
+    pub fn is_empty(&self) -> bool {
+        self.constraints.is_empty()
+    }
+
+    /// Check whether a public key satisfies every constraint
+    #[inline]
+    pub fn matches(&self, key: &[u8; KEY_LENGTH]) -> bool {
+        key.iter()
+            .zip(&self.mask)
+            .zip(&self.value)
+            .all(|((k, m), v)| k & m == *v)
+            && self.lower.is_none_or(|lower| *key >= lower)
+            && self.upper.is_none_or(|upper| *key < upper)
+    }
+
+    /// Check whether a Base58 address satisfies every constraint
+    pub fn matches_address(&self, address: &str) -> bool {
+        Pubkey::from_str(address).is_ok_and(|key| self.matches(&key.to_bytes()))
+    }
+
+    /// Exact probability that a uniformly random key satisfies the target
+    ///
+    /// Counts the keys matching the mask between the bounds bit by bit, so
+    /// the only error is floating-point rounding.
+    pub fn probability(&self) -> f64 {
+        let masked_after = self.masked_after();
+        let fixed = |i: usize| -> f64 { 2f64.powi(-((i + 1 + masked_after[i]) as i32)) };
+
+        match (self.lower, self.upper) {
+            (None, None) => {
+                let masked: u32 = self.mask.iter().map(|m| m.count_ones()).sum();
+                2f64.powi(-(masked as i32))
+            }
+            (None, Some(upper)) => self.tail(&upper, 0, false, &fixed),
+            (Some(lower), None) => self.tail(&lower, 0, true, &fixed),
+            (Some(lower), Some(upper)) => {
+                if lower >= upper {
+                    return 0.0;
+                }
+                // Bits above the first difference are shared by both bounds
+                let split = (0..KEY_BITS)
+                    .find(|&i| bit(&lower, i) != bit(&upper, i))
+                    .expect("distinct bounds differ in some bit");
+                if (0..split).any(|i| !self.allows(i, bit(&lower, i))) {
+                    return 0.0;
+                }
+                // At the split lower has 0 and upper has 1
+                let mut probability = 0.0;
+                if self.allows(split, false) {
+                    probability += self.tail(&lower, split + 1, true, &fixed);
+                }
+                if self.allows(split, true) {
+                    probability += self.tail(&upper, split + 1, false, &fixed);
+                }
+                probability
+            }
+        }
+    }
+
+    /// Human-readable summary of the constraints
+    pub fn description(&self) -> String {
+        let parts: Vec<String> = self.constraints.iter().map(|c| c.to_string()).collect();
+        format!("raw key: {}", parts.join(", "))
+    }
+
+    fn require_bits(
+        &mut self,
+        index: usize,
+        value: u8,
+        mask: u8,
+        constraint: &ByteConstraint,
+    ) -> Result<(), ConfigError> {
+        let overlap = self.mask[index] & mask;
+        if (self.value[index] ^ value) & overlap != 0 {
+            return Err(ConfigError::Unsatisfiable(format!(
+                "'{constraint}' contradicts an earlier constraint on byte {index}"
+            )));
+        }
+        self.mask[index] |= mask;
+        self.value[index] |= value & mask;
+        Ok(())
+    }
+
+    /// Whether bit `i` (most significant first) may take the given value
+    fn allows(&self, i: usize, set: bool) -> bool {
+        !bit(&self.mask, i) || bit(&self.value, i) == set
+    }
+
+    /// Number of masked bits after each bit position
+    fn masked_after(&self) -> Vec<usize> {
+        let mut after = vec![0; KEY_BITS];
+        let mut count = 0;
+        for i in (0..KEY_BITS).rev() {
+            after[i] = count;
+            count += usize::from(bit(&self.mask, i));
+        }
+        after
+    }
+
+    /// Probability of keys that equal `bound` on bits before `start`, match
+    /// the mask, and sort at or above (`at_least`) or strictly below it
+    ///
+    /// Assumes the bits before `start` are already compatible with the mask.
+    fn tail(
+        &self,
+        bound: &[u8; KEY_LENGTH],
+        start: usize,
+        at_least: bool,
+        fixed: &dyn Fn(usize) -> f64,
+    ) -> f64 {
+        let mut probability = 0.0;
+        for i in start..KEY_BITS {
+            let b = bit(bound, i);
+            // Branch off where the key can differ from the bound in the right direction
+            if b != at_least && self.allows(i, !b) {
+                probability += fixed(i);
+            }
+            if !self.allows(i, b) {
+                return probability;
+            }
+        }
+        if at_least {
+            // The bound itself
+            probability += 2f64.powi(-(KEY_BITS as i32));
+        }
+        probability
+    }
+}
+
+/// Bit `i` of a key, most significant bit of byte 0 first
+#[inline]
+fn bit(bytes: &[u8; KEY_LENGTH], i: usize) -> bool {
+    bytes[i / 8] & (0x80 >> (i % 8)) != 0
+}
+
+fn parse_hex(text: &str) -> Result<Vec<u8>, ConfigError> {
+    let text = text.trim();
+    let text = text.strip_prefix("0x").unwrap_or(text);
+    if !text.len().is_multiple_of(2) {
+        return Err(ConfigError::InvalidRawConstraint(format!(
+            "'{text}' has an odd number of hex digits"
+        )));
+    }
+    from_hex(text)
+        .ok_or_else(|| ConfigError::InvalidRawConstraint(format!("'{text}' is not hex")))
+}
+
+/// Decode lowercase or uppercase hex, or `None` if it isn't valid
+pub(crate) fn from_hex(text: &str) -> Option<Vec<u8>> {
+    if !text.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..text.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(text.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+fn parse_key(spec: &str) -> Result<[u8; KEY_LENGTH], ConfigError> {
+    let spec = spec.trim();
+    if spec.len() == KEY_LENGTH * 2 || spec.starts_with("0x") {
+        let bytes = parse_hex(spec)?;
+        return bytes.try_into().map_err(|bytes: Vec<u8>| {
+            ConfigError::InvalidRawConstraint(format!(
+                "key bound must be {KEY_LENGTH} bytes, got {}",
+                bytes.len()
+            ))
+        });
+    }
+    Pubkey::from_str(spec).map(|key| key.to_bytes()).map_err(|_| {
+        ConfigError::InvalidRawConstraint(format!(
+            "'{spec}' is neither a Base58 public key nor {} hex digits",
+            KEY_LENGTH * 2
+        ))
+    })
+}
+
+/// Lowercase hex encoding
+///
+/// Writes into one buffer of the final size, so encoding secret bytes
+/// leaves no partial copies behind.
+pub fn to_hex(bytes: &[u8]) -> String {
+    const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+    let mut hex = String::with_capacity(bytes.len() * 2);
+    for b in bytes {
+        hex.push(HEX_DIGITS[(b >> 4) as usize] as char);
+        hex.push(HEX_DIGITS[(b & 0x0f) as usize] as char);
+    }
+    hex
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(first: &[u8]) -> [u8; KEY_LENGTH] {
+        let mut key = [0u8; KEY_LENGTH];
+        key[..first.len()].copy_from_slice(first);
+        key
+    }
+
+    #[test]
+    fn test_masked_prefix() {
+        let target = RawTarget::new()
+            .with(ByteConstraint::masked_prefix("00f0/ffF0").unwrap())
+            .unwrap();
+        assert!(target.matches(&key(&[0x00, 0xf3])));
+        assert!(!target.matches(&key(&[0x00, 0xe3])));
+        assert!(!target.matches(&key(&[0x01, 0xf0])));
+        assert_eq!(target.probability(), 2f64.powi(-12));
+    }
+
+    #[test]
+    fn test_shard() {
+        let target = RawTarget::new()
+            .with(ByteConstraint::shard("12:0x5a3").unwrap())
+            .unwrap();
+        assert!(target.matches(&key(&[0x5a, 0x3f])));
+        assert!(!target.matches(&key(&[0x5a, 0x4f])));
+        assert_eq!(target.probability(), 2f64.powi(-12));
+
+        assert!(ByteConstraint::shard("4:16").is_err());
+        assert!(ByteConstraint::shard("0:0").is_err());
+        assert!(ByteConstraint::shard("33:1").is_err());
+        assert!(ByteConstraint::shard("12").is_err());
+    }
+
+    #[test]
+    fn test_conflicting_bits() {
+        let target = RawTarget::new()
+            .with(ByteConstraint::masked_prefix("ff").unwrap())
+            .unwrap();
+        assert!(matches!(
+            target.clone().with(ByteConstraint::shard("4:0").unwrap()),
+            Err(ConfigError::Unsatisfiable(_))
+        ));
+        // Overlapping but compatible constraints are merged
+        let merged = target.with(ByteConstraint::shard("4:15").unwrap()).unwrap();
+        assert_eq!(merged.probability(), 2f64.powi(-8));
+    }
+
+    #[test]
+    fn test_bounds() {
+        let half = key(&[0x80]);
+        let below = RawTarget::new()
+            .with(ByteConstraint::Below(half))
+            .unwrap();
+        assert!(below.matches(&key(&[0x7f, 0xff])));
+        assert!(!below.matches(&half));
+        assert_eq!(below.probability(), 0.5);
+
+        let above = RawTarget::new()
+            .with(ByteConstraint::AtLeast(half))
+            .unwrap();
+        assert!(above.matches(&half));
+        assert_eq!(above.probability(), 0.5);
+
+        // A quarter of the space, then restricted to odd first bytes
+        let range = RawTarget::new()
+            .with(ByteConstraint::AtLeast(key(&[0x40])))
+            .unwrap()
+            .with(ByteConstraint::Below(half))
+            .unwrap();
+        assert_eq!(range.probability(), 0.25);
+        let odd = range
+            .with(ByteConstraint::masked_prefix("01/01").unwrap())
+            .unwrap();
+        assert_eq!(odd.probability(), 0.125);
+    }
+
+    #[test]
+    fn test_probability_matches_brute_force() {
+        // Constraints on the first byte only, so every first byte can be enumerated
+        let target = RawTarget::new()
+            .with(ByteConstraint::masked_prefix("a0/e4").unwrap())
+            .unwrap()
+            .with(ByteConstraint::AtLeast(key(&[0x13, 0x77])))
+            .unwrap()
+            .with(ByteConstraint::Below(key(&[0xb1])))
+            .unwrap();
+
+        // Keys sharing a first byte with a bound are split by the bound;
+        // count the admitted fraction of each first byte exactly
+        let mut expected = 0.0;
+        for first in 0u8..=255 {
+            if first & 0xe4 != 0xa0 & 0xe4 || !(0x13..0xb1).contains(&first) {
+                continue;
+            }
+            expected += if first == 0x13 {
+                (1.0 - (0x77 as f64 / 256.0)) / 256.0
+            } else {
+                1.0 / 256.0
+            };
+        }
+        assert!((target.probability() - expected).abs() < 1e-15);
+    }
+
+    #[test]
+    fn test_empty_range() {
+        let target = RawTarget::new()
+            .with(ByteConstraint::AtLeast(key(&[0x80])))
+            .unwrap()
+            .with(ByteConstraint::Below(key(&[0x40])))
+            .unwrap();
+        assert_eq!(target.probability(), 0.0);
+    }
+
+    #[test]
+    fn test_parse_bounds() {
+        let system = "11111111111111111111111111111111";
+        assert_eq!(ByteConstraint::at_least(system).unwrap(), ByteConstraint::AtLeast([0; 32]));
+
+        let hex = "ff".repeat(32);
+        assert_eq!(ByteConstraint::below(&hex).unwrap(), ByteConstraint::Below([0xff; 32]));
+        assert!(ByteConstraint::below("0xff").is_err());
+        assert!(ByteConstraint::below("not-a-key").is_err());
+    }
+
+    #[test]
+    fn test_matches_address() {
+        let target = RawTarget::new()
+            .with(ByteConstraint::masked_prefix("00").unwrap())
+            .unwrap();
+        assert!(target.matches_address("11111111111111111111111111111111"));
+        assert!(!target.matches_address("not base58"));
+    }
+}
@@ -0,0 +1,305 @@
+//! Optional JSON-RPC pre-flight check that a found address is unused.
+//!
+//! Generation is fully offline by default. When an RPC endpoint is
+//! configured, each match that passes `verify_keypair_integrity` is looked
+//! up with `getAccountInfo`; addresses that already hold an account are
+//! skipped and the search continues.
+
+use crate::config::ConfigError;
+use serde_json::{json, Value};
+use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
+use std::time::Duration;
+use thiserror::Error;
+
+/// Default timeout for one RPC request
+pub const DEFAULT_RPC_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Errors from talking to the RPC endpoint
+#[derive(Error, Debug)]
+pub enum RpcError {
+    #[error("RPC request failed: {0}")]
+    Transport(String),
+
+    #[error("RPC error {code}: {message}")]
+    Rpc { code: i64, message: String },
+
+    #[error("Unexpected RPC response: {0}")]
+    InvalidResponse(String),
+}
+
+/// An account that already exists at an address
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccountInfo {
+    /// Balance in lamports
+    pub lamports: u64,
+    /// Owner program
+    pub owner: String,
+    /// Whether the account holds a program
+    pub executable: bool,
+}
+
+impl AccountInfo {
+    /// One-line summary for results and logs
+    pub fn summary(&self) -> String {
+        format!("{} lamports, owner {}", self.lamports, self.owner)
+    }
+}
+
+/// Blocking JSON-RPC client for `getAccountInfo`
+#[derive(Debug, Clone)]
+pub struct RpcClient {
+    url: String,
+    agent: ureq::Agent,
+}
+
+impl RpcClient {
+    /// Create a client for an `http://` or `https://` endpoint
+    pub fn new(url: &str) -> Result<Self, ConfigError> {
+        if !(url.starts_with("http://") || url.starts_with("https://")) {
+            return Err(ConfigError::InvalidRpcUrl(url.to_string()));
+        }
+        Ok(Self {
+            url: url.to_string(),
+            agent: ureq::AgentBuilder::new().timeout(DEFAULT_RPC_TIMEOUT).build(),
+        })
+    }
+
+    /// Give up on a request after `timeout`
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.agent = ureq::AgentBuilder::new().timeout(timeout).build();
+        self
+    }
+
+    /// The endpoint URL
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Look up the account at `address`, or `None` if it doesn't exist
+    pub fn get_account_info(&self, address: &Pubkey) -> Result<Option<AccountInfo>, RpcError> {
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "getAccountInfo",
+            "params": [
+                address.to_string(),
+                // Only existence matters, so skip the account data
+                { "encoding": "base64", "dataSlice": { "offset": 0, "length": 0 } }
+            ]
+        });
+
+        let response: Value = match self.agent.post(&self.url).send_json(request) {
+            Ok(response) => response
+                .into_json()
+                .map_err(|e| RpcError::InvalidResponse(e.to_string()))?,
+            // JSON-RPC errors may come back with an HTTP error status
+            Err(ureq::Error::Status(code, response)) => response
+                .into_json()
+                .map_err(|_| RpcError::Transport(format!("HTTP status {code}")))?,
+            Err(e) => return Err(RpcError::Transport(e.to_string())),
+        };
+
+        if let Some(error) = response.get("error") {
+            return Err(RpcError::Rpc {
+                code: error["code"].as_i64().unwrap_or_default(),
+                message: error["message"].as_str().unwrap_or_default().to_string(),
+            });
+        }
+
+        let value = response
+            .get("result")
+            .and_then(|result| result.get("value"))
+            .ok_or_else(|| RpcError::InvalidResponse("missing result.value".to_string()))?;
+        if value.is_null() {
+            return Ok(None);
+        }
+
+        let lamports = value["lamports"]
+            .as_u64()
+            .ok_or_else(|| RpcError::InvalidResponse("missing lamports".to_string()))?;
+        Ok(Some(AccountInfo {
+            lamports,
+            owner: value["owner"].as_str().unwrap_or_default().to_string(),
+            executable: value["executable"].as_bool().unwrap_or_default(),
+        }))
+    }
+}
+
+/// What to do with a match whose address already exists on chain
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PreflightPolicy {
+    /// Skip it quietly and keep searching
+    #[default]
+    Reject,
+    /// Skip it, keep searching, and list it on the eventual result
+    Flag,
+}
+
+impl FromStr for PreflightPolicy {
+    type Err = ConfigError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "reject" => Ok(Self::Reject),
+            "flag" => Ok(Self::Flag),
+            _ => Err(ConfigError::InvalidPreflightPolicy(s.to_string())),
+        }
+    }
+}
+
+/// RPC pre-flight check applied to matches before they are accepted
+#[derive(Debug, Clone)]
+pub struct Preflight {
+    client: RpcClient,
+    policy: PreflightPolicy,
+    keep_unchecked: bool,
+}
+
+impl Preflight {
+    /// Check matches against `client`, handling existing accounts per `policy`
+    pub fn new(client: RpcClient, policy: PreflightPolicy) -> Self {
+        Self { client, policy, keep_unchecked: false }
+    }
+
+    /// Keep a match whose lookup fails, marked as not checked
+    ///
+    /// By default a failed lookup stops the search instead.
+    pub fn with_keep_unchecked(mut self, keep: bool) -> Self {
+        self.keep_unchecked = keep;
+        self
+    }
+
+    /// The RPC client
+    pub fn client(&self) -> &RpcClient {
+        &self.client
+    }
+
+    /// How existing accounts are handled
+    pub fn policy(&self) -> PreflightPolicy {
+        self.policy
+    }
+
+    /// Whether matches are kept when their lookup fails
+    pub fn keeps_unchecked(&self) -> bool {
+        self.keep_unchecked
+    }
+
+    /// Look up `address`; `Some` means an account already exists there
+    pub fn check(&self, address: &Pubkey) -> Result<Option<AccountInfo>, RpcError> {
+        self.client.get_account_info(address)
+    }
+
+    /// Human-readable description of the check
+    pub fn description(&self) -> String {
+        let action = match self.policy {
+            PreflightPolicy::Reject => "reject",
+            PreflightPolicy::Flag => "flag",
+        };
+        let unchecked = if self.keep_unchecked { ", keep unchecked" } else { "" };
+        format!("{} ({} existing accounts{})", self.client.url, action, unchecked)
+    }
+}
+
+/// Minimal JSON-RPC server for tests
+#[cfg(test)]
+pub(crate) mod mock {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    /// Serve `getAccountInfo`; the first `existing` requests report an account
+    ///
+    /// Returns the endpoint URL and a count of requests served.
+    pub fn serve(existing: usize) -> (String, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let served = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&served);
+
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else { break };
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    if reader.read_line(&mut line).unwrap_or(0) == 0 || line == "\r\n" {
+                        break;
+                    }
+                    if let Some((name, value)) = line.split_once(':') {
+                        if name.eq_ignore_ascii_case("content-length") {
+                            content_length = value.trim().parse().unwrap_or(0);
+                        }
+                    }
+                }
+                let mut body = vec![0; content_length];
+                let _ = reader.read_exact(&mut body);
+
+                let n = counter.fetch_add(1, Ordering::SeqCst);
+                let value = if n < existing {
+                    r#"{"lamports":1000,"owner":"11111111111111111111111111111111","executable":false,"data":["","base64"],"rentEpoch":0}"#
+                } else {
+                    "null"
+                };
+                let payload =
+                    format!(r#"{{"jsonrpc":"2.0","id":1,"result":{{"context":{{"slot":1}},"value":{value}}}}}"#);
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    payload.len(),
+                    payload
+                );
+            }
+        });
+
+        (url, served)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_client_rejects_non_http_url() {
+        assert!(RpcClient::new("http://127.0.0.1:8899").is_ok());
+        assert!(RpcClient::new("https://api.mainnet-beta.solana.com").is_ok());
+        assert!(RpcClient::new("ws://127.0.0.1:8900").is_err());
+        assert!(RpcClient::new("localhost").is_err());
+    }
+
+    #[test]
+    fn test_get_account_info_against_mock() {
+        let (url, served) = mock::serve(1);
+        let client = RpcClient::new(&url).unwrap();
+        let address = Pubkey::new_unique();
+
+        let existing = client.get_account_info(&address).unwrap().unwrap();
+        assert_eq!(existing.lamports, 1000);
+        assert_eq!(existing.owner, "11111111111111111111111111111111");
+        assert!(client.get_account_info(&address).unwrap().is_none());
+        assert_eq!(served.load(std::sync::atomic::Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_unreachable_endpoint_is_an_error() {
+        // Bind then drop a listener so the port is closed
+        let port = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+        let client = RpcClient::new(&format!("http://127.0.0.1:{port}"))
+            .unwrap()
+            .with_timeout(Duration::from_secs(2));
+        assert!(matches!(
+            client.get_account_info(&Pubkey::new_unique()),
+            Err(RpcError::Transport(_))
+        ));
+    }
+
+    #[test]
+    fn test_policy_parse() {
+        assert_eq!("reject".parse::<PreflightPolicy>().unwrap(), PreflightPolicy::Reject);
+        assert_eq!("FLAG".parse::<PreflightPolicy>().unwrap(), PreflightPolicy::Flag);
+        assert!("skip".parse::<PreflightPolicy>().is_err());
+    }
+}
@@ -131,7 +131,10 @@ pub fn secure_write_file(path: &Path, data: &[u8]) -> SecurityResult<()> {
 }
 
 /// Validate that a path is safe for writing sensitive data
-fn validate_output_path(path: &Path) -> SecurityResult<()> {
+///
+/// Rejects system directories and warns about temporary ones. Called by
+/// [`secure_write_file`], and before a search so bad paths fail early.
+pub fn validate_output_path(path: &Path) -> SecurityResult<()> {
     // Check for obviously unsafe paths
     let path_str = path.to_string_lossy();
 
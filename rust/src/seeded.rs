@@ -0,0 +1,293 @@
+//! Vanity search for `create_with_seed` accounts.
+//!
+//! `Pubkey::create_with_seed(base, seed, owner)` is a single SHA-256, so a
+//! vanity seed-derived account is much cheaper to find than a vanity keypair
+//! and involves no secret. The search enumerates seed strings over an
+//! alphabet until the derived address matches.
+
+use crate::config::ConfigError;
+use crate::generator::{counter_search, GeneratorResult, ParallelSearch, SearchProgress, VanityGeneratorConfig};
+use crate::matcher::{MatchTarget, OptimizedMatcher};
+use crate::output::OutputResult;
+use crate::security::secure_write_file;
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::{Pubkey, MAX_SEED_LEN};
+use std::collections::HashSet;
+use std::path::Path;
+use std::time::Instant;
+
+/// Default seed alphabet: ASCII digits and letters
+pub const DEFAULT_SEED_ALPHABET: &str =
+    "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+/// Map a counter to a seed string using bijective base-N numbering
+///
+/// Every counter value gives a distinct seed, and shorter seeds come first:
+/// with alphabet `ab`, 0..6 map to `a`, `b`, `aa`, `ab`, `ba`, `bb`.
+pub fn seed_for_counter(mut counter: u64, alphabet: &[u8]) -> String {
+    let base = alphabet.len() as u64;
+    let mut seed = Vec::new();
+    loop {
+        seed.push(alphabet[(counter % base) as usize]);
+        counter /= base;
+        if counter == 0 {
+            break;
+        }
+        counter -= 1;
+    }
+    seed.reverse();
+    // The alphabet is validated to be ASCII
+    String::from_utf8(seed).expect("seed alphabet is ASCII")
+}
+
+/// Number of counters whose seed fits in [`MAX_SEED_LEN`] characters
+///
+/// Counts seeds of every length from 1 to the limit, saturating at
+/// `u64::MAX`; counters from this value on map to seeds that are too long.
+pub fn seed_count(alphabet_len: usize) -> u64 {
+    let base = alphabet_len as u64;
+    let mut total = 0u64;
+    let mut of_length = 1u64;
+    for _ in 0..MAX_SEED_LEN {
+        of_length = of_length.saturating_mul(base);
+        total = total.saturating_add(of_length);
+    }
+    total
+}
+
+/// A vanity seed-derived account and how to derive it again
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SeededMatch {
+    /// The derived address
+    pub address: String,
+    /// Base pubkey (must sign to create the account)
+    pub base: String,
+    /// Owner program of the account
+    pub owner: String,
+    /// The seed string
+    pub seed: String,
+    /// Seeds tried across all threads
+    pub attempts: u64,
+    /// Search time in milliseconds
+    pub time_ms: u64,
+}
+
+/// Write a seed record as pretty-printed JSON
+pub fn write_seeded_record(record: &SeededMatch, path: &Path) -> OutputResult<()> {
+    let json = serde_json::to_string_pretty(record)?;
+    secure_write_file(path, json.as_bytes())?;
+    log::info!("Wrote seed record to: {}", path.display());
+    Ok(())
+}
+
+/// Parallel search for a `create_with_seed` seed whose address matches a target
+pub struct SeedSearch {
+    config: VanityGeneratorConfig,
+    matcher: OptimizedMatcher,
+    base: Pubkey,
+    owner: Pubkey,
+    alphabet: Vec<u8>,
+    progress: SearchProgress,
+}
+
+impl SeedSearch {
+    /// Create a search over seeds from [`DEFAULT_SEED_ALPHABET`]
+    ///
+    /// Fails if `owner` can't own seed-derived accounts, which is the case
+    /// when its bytes end in the PDA marker.
+    pub fn new(
+        base: Pubkey,
+        owner: Pubkey,
+        target: MatchTarget,
+        config: VanityGeneratorConfig,
+    ) -> Result<Self, ConfigError> {
+        Pubkey::create_with_seed(&base, "", &owner).map_err(|e| {
+            ConfigError::InvalidSeed(format!("owner {owner} can't own seed-derived accounts: {e}"))
+        })?;
+
+        Ok(Self {
+            config,
+            matcher: OptimizedMatcher::new(target),
+            base,
+            owner,
+            alphabet: DEFAULT_SEED_ALPHABET.as_bytes().to_vec(),
+            progress: SearchProgress::default(),
+        })
+    }
+
+    /// Draw seed characters from `alphabet` instead
+    ///
+    /// The alphabet needs at least two distinct printable ASCII characters.
+    pub fn with_alphabet(mut self, alphabet: &str) -> Result<Self, ConfigError> {
+        if let Some(c) = alphabet.chars().find(|c| !c.is_ascii_graphic()) {
+            return Err(ConfigError::InvalidSeed(format!(
+                "seed alphabet may only contain printable ASCII, found {c:?}"
+            )));
+        }
+        let mut seen = HashSet::new();
+        if let Some(c) = alphabet.chars().find(|c| !seen.insert(*c)) {
+            return Err(ConfigError::InvalidSeed(format!(
+                "seed alphabet repeats '{c}'"
+            )));
+        }
+        if alphabet.len() < 2 {
+            return Err(ConfigError::InvalidSeed(
+                "seed alphabet needs at least two characters".to_string(),
+            ));
+        }
+        self.alphabet = alphabet.as_bytes().to_vec();
+        Ok(self)
+    }
+
+    /// Expected number of seeds to try
+    pub fn expected_attempts(&self) -> f64 {
+        1.0 / self.matcher.target().probability()
+    }
+
+    /// Seed string for a counter value
+    pub fn seed(&self, counter: u64) -> String {
+        seed_for_counter(counter, &self.alphabet)
+    }
+
+    /// Search seeds from counter `start` until a derived address matches
+    ///
+    /// Only counters below [`seed_count`] are tried, so every seed fits the
+    /// 32-character limit; the search is exhausted once they run out.
+    pub fn run(&self, start: u64) -> GeneratorResult<SeededMatch> {
+        let start_time = Instant::now();
+        let (_, (seed, address)) = counter_search(
+            &self.config,
+            &self.progress,
+            start..seed_count(self.alphabet.len()),
+            |counter| {
+                let seed = self.seed(counter);
+                // The range bounds the seed length and `new` checked the owner
+                let address = Pubkey::create_with_seed(&self.base, &seed, &self.owner)
+                    .expect("seed length and owner are checked up front");
+                (self.matcher.matches_key(&address.to_bytes())
+                    && self.matcher.matches(&address.to_string()))
+                .then_some((seed, address))
+            },
+        )?;
+
+        Ok(SeededMatch {
+            address: address.to_string(),
+            base: self.base.to_string(),
+            owner: self.owner.to_string(),
+            seed,
+            attempts: self.attempts(),
+            time_ms: start_time.elapsed().as_millis() as u64,
+        })
+    }
+}
+
+impl ParallelSearch for SeedSearch {
+    fn progress(&self) -> &SearchProgress {
+        &self.progress
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::GeneratorError;
+    use crate::test_support::config;
+    use tempfile::tempdir;
+
+    const PDA_MARKER: &[u8] = b"ProgramDerivedAddress";
+
+    #[test]
+    fn test_seed_for_counter() {
+        let seeds: Vec<String> = (0..7).map(|n| seed_for_counter(n, b"ab")).collect();
+        assert_eq!(seeds, vec!["a", "b", "aa", "ab", "ba", "bb", "aaa"]);
+
+        // The full u64 range fits in 32 characters with the default alphabet
+        assert!(seed_for_counter(u64::MAX, DEFAULT_SEED_ALPHABET.as_bytes()).len() <= MAX_SEED_LEN);
+    }
+
+    #[test]
+    fn test_alphabet_validation() {
+        let search = || {
+            SeedSearch::new(
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                MatchTarget::prefix("A", false).unwrap(),
+                config(),
+            )
+            .unwrap()
+        };
+        assert!(search().with_alphabet("abc").is_ok());
+        assert!(search().with_alphabet("a").is_err());
+        assert!(search().with_alphabet("aba").is_err());
+        assert!(search().with_alphabet("a b").is_err());
+    }
+
+    #[test]
+    fn test_search_finds_seed() {
+        let base = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let target = MatchTarget::prefix("AB", false).unwrap();
+        let search = SeedSearch::new(base, owner, target, config())
+            .unwrap()
+            .with_alphabet("xyz123")
+            .unwrap();
+
+        let found = search.run(0).unwrap();
+        assert!(found.address.starts_with("AB"));
+        assert!(found.seed.chars().all(|c| "xyz123".contains(c)));
+
+        let expected = Pubkey::create_with_seed(&base, &found.seed, &owner).unwrap();
+        assert_eq!(found.address, expected.to_string());
+    }
+
+    #[test]
+    fn test_seed_count() {
+        assert_eq!(seed_count(2), (1u64 << 33) - 2);
+        assert_eq!(seed_count(DEFAULT_SEED_ALPHABET.len()), u64::MAX);
+
+        // The last counted seed is the longest allowed, the next is too long
+        let last = seed_count(2) - 1;
+        assert_eq!(seed_for_counter(last, b"ab").len(), MAX_SEED_LEN);
+        assert_eq!(seed_for_counter(last + 1, b"ab").len(), MAX_SEED_LEN + 1);
+    }
+
+    #[test]
+    fn test_search_exhausts_seeds() {
+        let target = MatchTarget::prefix("ABCDEFG", false).unwrap();
+        let search = SeedSearch::new(Pubkey::new_unique(), Pubkey::new_unique(), target, config())
+            .unwrap()
+            .with_alphabet("ab")
+            .unwrap();
+
+        let start = seed_count(2) - 100;
+        assert!(matches!(search.run(start), Err(GeneratorError::Exhausted)));
+        assert_eq!(search.attempts(), 100);
+    }
+
+    #[test]
+    fn test_owner_must_allow_seeds() {
+        let mut owner = [7u8; 32];
+        owner[32 - PDA_MARKER.len()..].copy_from_slice(PDA_MARKER);
+        let target = MatchTarget::prefix("A", false).unwrap();
+        let result = SeedSearch::new(Pubkey::new_unique(), Pubkey::new_from_array(owner), target, config());
+        assert!(matches!(result, Err(ConfigError::InvalidSeed(_))));
+    }
+
+    #[test]
+    fn test_write_record() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("seed.json");
+        let record = SeededMatch {
+            address: "Addr".to_string(),
+            base: "Base".to_string(),
+            owner: "Owner".to_string(),
+            seed: "x1".to_string(),
+            attempts: 3,
+            time_ms: 0,
+        };
+        write_seeded_record(&record, &path).unwrap();
+        let restored: SeededMatch =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(restored, record);
+    }
+}
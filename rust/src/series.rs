@@ -0,0 +1,461 @@
+//! Templated series of vanity addresses.
+//!
+//! A series is a family of related targets built from one template, such as
+//! `TEAM{n}` over `1..=9` or a shared prefix with a `{word}` suffix for each
+//! member of a list. [`SeriesSearch`] checks every generated key against all
+//! unfilled slots at once, so a series of N addresses costs far less than N
+//! separate searches.
+
+use crate::config::ConfigError;
+use crate::exclusion::ExclusionRules;
+use crate::generator::{slot_search, GeneratorError, GeneratorResult, ParallelSearch, SearchProgress, VanityGeneratorConfig};
+use crate::matcher::{MatchTarget, OptimizedMatcher};
+use crate::output::{GeneratedAddress, OutputResult};
+use crate::security::{secure_write_file, verify_rng_quality};
+use serde::{Deserialize, Serialize};
+use solana_sdk::signer::keypair::Keypair;
+use std::collections::HashSet;
+use std::ops::RangeInclusive;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Placeholder replaced by each number of a numbered series
+pub const NUMBER_PLACEHOLDER: &str = "{n}";
+
+/// Placeholder replaced by each word of a word series
+pub const WORD_PLACEHOLDER: &str = "{word}";
+
+/// One member of a series
+#[derive(Debug, Clone)]
+pub struct SeriesSlot {
+    /// Slot name, built from the rendered patterns (e.g. `TEAM3`)
+    pub name: String,
+    /// The target this slot must match
+    pub target: MatchTarget,
+}
+
+/// A family of targets rendered from a prefix and/or suffix template
+#[derive(Debug, Clone)]
+pub struct Series {
+    template: String,
+    slots: Vec<SeriesSlot>,
+}
+
+impl Series {
+    /// Build a series by replacing `{n}` with each number in `numbers`
+    ///
+    /// Numbers containing `0` render to invalid Base58 and are rejected.
+    pub fn numbered(
+        prefix: Option<&str>,
+        suffix: Option<&str>,
+        numbers: RangeInclusive<u64>,
+        case_insensitive: bool,
+    ) -> Result<Self, ConfigError> {
+        if let Some(n) = numbers.clone().find(|n| n.to_string().contains('0')) {
+            return Err(ConfigError::InvalidTemplate(format!(
+                "number {n} contains '0', which is not valid Base58; use a range without zeros (e.g. 1-9)"
+            )));
+        }
+        let values = numbers.map(|n| n.to_string());
+        Self::build(prefix, suffix, NUMBER_PLACEHOLDER, values, case_insensitive)
+    }
+
+    /// Build a series by replacing `{word}` with each word in `words`
+    ///
+    /// Blank lines, `#` comments and duplicate words are skipped.
+    pub fn from_words<I, S>(
+        prefix: Option<&str>,
+        suffix: Option<&str>,
+        words: I,
+        case_insensitive: bool,
+    ) -> Result<Self, ConfigError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut seen = HashSet::new();
+        let values = words
+            .into_iter()
+            .map(|w| w.as_ref().trim().to_string())
+            .filter(|w| !w.is_empty() && !w.starts_with('#'))
+            .filter(|w| seen.insert(w.clone()))
+            .collect::<Vec<_>>();
+        Self::build(prefix, suffix, WORD_PLACEHOLDER, values, case_insensitive)
+    }
+
+    fn build(
+        prefix: Option<&str>,
+        suffix: Option<&str>,
+        placeholder: &str,
+        values: impl IntoIterator<Item = String>,
+        case_insensitive: bool,
+    ) -> Result<Self, ConfigError> {
+        if prefix.is_none() && suffix.is_none() {
+            return Err(ConfigError::NoPatternSpecified);
+        }
+        let templates = [prefix, suffix];
+        if !templates.iter().flatten().any(|t| t.contains(placeholder)) {
+            return Err(ConfigError::InvalidTemplate(format!(
+                "template needs a {placeholder} placeholder"
+            )));
+        }
+
+        let mut slots = Vec::new();
+        for value in values {
+            let [prefix, suffix] = templates.map(|t| t.map(|t| t.replace(placeholder, &value)));
+            let target = match (&prefix, &suffix) {
+                (Some(p), Some(s)) => MatchTarget::both(p, s, case_insensitive),
+                (Some(p), None) => MatchTarget::prefix(p, case_insensitive),
+                (None, Some(s)) => MatchTarget::suffix(s, case_insensitive),
+                (None, None) => unreachable!(), // Checked above
+            }
+            .map_err(|e| {
+                ConfigError::InvalidTemplate(format!("slot '{value}' is not a valid pattern: {e}"))
+            })?;
+            let name = [prefix, suffix].into_iter().flatten().collect::<Vec<_>>().join("_");
+            slots.push(SeriesSlot { name, target });
+        }
+
+        if slots.is_empty() {
+            return Err(ConfigError::InvalidTemplate(
+                "series has no members".to_string(),
+            ));
+        }
+
+        let template = [prefix.map(|p| format!("{p}...")), suffix.map(|s| format!("...{s}"))]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .join(" ");
+        Ok(Self { template, slots })
+    }
+
+    /// The slots in the order they were rendered
+    pub fn slots(&self) -> &[SeriesSlot] {
+        &self.slots
+    }
+
+    /// Number of slots
+    pub fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Check if the series has no slots
+    pub fn is_empty(&self) -> bool {
+        self.slots.is_empty()
+    }
+
+    /// The template the series was rendered from
+    pub fn template(&self) -> &str {
+        &self.template
+    }
+
+    /// Human-readable description of the series
+    pub fn description(&self) -> String {
+        format!("series {} ({} slots)", self.template, self.slots.len())
+    }
+
+    /// Expected attempts to fill every slot
+    ///
+    /// Each slot is a separate event; while `k` slots remain, a key fills one
+    /// of them with the sum of their probabilities, so the total is the sum of
+    /// `1 / p_remaining` as slots are filled (hardest slots last).
+    pub fn expected_attempts(&self) -> f64 {
+        expected_attempts_to_fill(self.slots.iter().map(|s| s.target.probability()).collect())
+    }
+}
+
+/// Expected attempts to fill slots with these match probabilities at once
+pub(crate) fn expected_attempts_to_fill(probabilities: Vec<f64>) -> f64 {
+    expected_attempts_to_fill_counts(probabilities.into_iter().map(|p| (p, 1)).collect())
+}
+
+/// Expected attempts to fill `count` slots of each probability at once
+///
+/// Equivalent to [`expected_attempts_to_fill`] with each probability
+/// repeated `count` times, without materializing the slots.
+pub(crate) fn expected_attempts_to_fill_counts(mut groups: Vec<(f64, u64)>) -> f64 {
+    groups.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let mut remaining: f64 = groups.iter().map(|&(p, count)| p * count as f64).sum();
+    let mut attempts = 0.0;
+    for &(p, count) in groups.iter().rev() {
+        attempts += group_attempts(remaining, p, count);
+        remaining -= p * count as f64;
+    }
+    attempts
+}
+
+/// Slots summed one by one below this count, in closed form above it
+const DIRECT_SUM_LIMIT: u64 = 10_000;
+
+/// Sum of `1 / (remaining - k * p)` for `k` in `0..count`
+fn group_attempts(remaining: f64, p: f64, count: u64) -> f64 {
+    if count <= DIRECT_SUM_LIMIT {
+        return (0..count).map(|k| 1.0 / (remaining - k as f64 * p)).sum();
+    }
+    // With m = remaining / p the sum is (H(m) - H(m - count)) / p
+    let m = remaining / p;
+    (digamma(m + 1.0) - digamma((m - count as f64 + 1.0).max(1.0))) / p
+}
+
+/// The digamma function for `x > 0`, by recurrence and its asymptotic series
+fn digamma(mut x: f64) -> f64 {
+    let mut result = 0.0;
+    while x < 6.0 {
+        result -= 1.0 / x;
+        x += 1.0;
+    }
+    let f = 1.0 / (x * x);
+    result + x.ln() - 0.5 / x
+        - f * (1.0 / 12.0 - f * (1.0 / 120.0 - f * (1.0 / 252.0 - f * (1.0 / 240.0 - f / 132.0))))
+}
+
+/// A found series member
+#[derive(Debug)]
+pub struct SeriesMatch {
+    /// Name of the slot this address fills
+    pub slot: String,
+    /// The generated keypair and its statistics
+    pub address: GeneratedAddress,
+}
+
+/// One line of a series manifest
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    /// Slot name
+    pub slot: String,
+    /// Base58 public key of the slot's address
+    pub pubkey: String,
+    /// Keypair file written for the slot
+    pub file: String,
+}
+
+/// Manifest mapping each slot of a series to its public key
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SeriesManifest {
+    /// Template the series was rendered from
+    pub template: String,
+    /// Filled slots, in series order
+    pub slots: Vec<ManifestEntry>,
+    /// Slots that were not filled (e.g. after cancellation)
+    pub missing: Vec<String>,
+}
+
+/// Write a series manifest as pretty-printed JSON
+pub fn write_manifest(manifest: &SeriesManifest, path: &Path) -> OutputResult<()> {
+    let json = serde_json::to_string_pretty(manifest)?;
+    secure_write_file(path, json.as_bytes())?;
+    log::info!("Wrote series manifest to: {}", path.display());
+    Ok(())
+}
+
+/// Parallel search that fills every slot of a series
+pub struct SeriesSearch {
+    config: VanityGeneratorConfig,
+    series: Series,
+    matchers: Vec<OptimizedMatcher>,
+    exclusions: ExclusionRules,
+    progress: SearchProgress,
+    excluded: Arc<AtomicU64>,
+}
+
+impl SeriesSearch {
+    /// Create a new series search
+    pub fn new(series: Series, config: VanityGeneratorConfig) -> GeneratorResult<Self> {
+        verify_rng_quality().map_err(|e| GeneratorError::RngError(e.to_string()))?;
+
+        let matchers = series
+            .slots()
+            .iter()
+            .map(|slot| OptimizedMatcher::new(slot.target.clone()))
+            .collect();
+
+        Ok(Self {
+            config,
+            series,
+            matchers,
+            exclusions: ExclusionRules::default(),
+            progress: SearchProgress::default(),
+            excluded: Arc::new(AtomicU64::new(0)),
+        })
+    }
+
+    /// Reject matching addresses that violate these exclusion rules
+    pub fn with_exclusions(mut self, exclusions: ExclusionRules) -> Self {
+        self.exclusions = exclusions;
+        self
+    }
+
+    /// Get the series being searched
+    pub fn series(&self) -> &Series {
+        &self.series
+    }
+
+    /// Get the number of matching addresses dropped by exclusion rules
+    pub fn excluded(&self) -> u64 {
+        self.excluded.load(Ordering::Relaxed)
+    }
+
+    /// Search until every slot is filled or the search is cancelled
+    ///
+    /// Each key is checked against all unfilled slots and assigned to the
+    /// first one it matches. Results are in series order; after cancellation
+    /// only the filled slots are returned.
+    pub fn run(&self) -> GeneratorResult<Vec<SeriesMatch>> {
+        let slot_count = self.matchers.len();
+        let results: Mutex<Vec<Option<GeneratedAddress>>> =
+            Mutex::new((0..slot_count).map(|_| None).collect());
+
+        slot_search(
+            &self.config,
+            &self.progress,
+            &self.matchers,
+            &vec![1; slot_count],
+            || Some((Keypair::new(), ())),
+            |pubkey_str| match self.exclusions.check(pubkey_str) {
+                Some(violation) => {
+                    log::debug!("Excluded {}: {}", pubkey_str, violation);
+                    self.excluded.fetch_add(1, Ordering::Relaxed);
+                    false
+                }
+                None => true,
+            },
+            |slot, mut address, ()| {
+                address.add_detail("Series slot", self.series.slots[slot].name.clone());
+                results.lock().unwrap()[slot] = Some(address);
+            },
+        )?;
+
+        let results = results.into_inner().unwrap();
+        Ok(results
+            .into_iter()
+            .zip(&self.series.slots)
+            .filter_map(|(address, slot)| {
+                address.map(|address| SeriesMatch {
+                    slot: slot.name.clone(),
+                    address,
+                })
+            })
+            .collect())
+    }
+}
+
+impl ParallelSearch for SeriesSearch {
+    fn progress(&self) -> &SearchProgress {
+        &self.progress
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::config;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_numbered_series() {
+        let series = Series::numbered(Some("TEAM{n}"), None, 1..=3, false).unwrap();
+        let names: Vec<&str> = series.slots().iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["TEAM1", "TEAM2", "TEAM3"]);
+        assert_eq!(series.template(), "TEAM{n}...");
+    }
+
+    #[test]
+    fn test_numbered_series_rejects_zero() {
+        let err = Series::numbered(Some("A{n}"), None, 9..=10, false).unwrap_err();
+        assert!(matches!(err, ConfigError::InvalidTemplate(ref message) if message.contains("number 10")));
+        assert!(Series::numbered(None, Some("{n}"), 11..=19, false).is_ok());
+    }
+
+    #[test]
+    fn test_word_series_with_shared_prefix() {
+        let series =
+            Series::from_words(Some("TEAM"), Some("{word}"), ["ab", "# skip", "", "cd", "ab"], false)
+                .unwrap();
+        let names: Vec<&str> = series.slots().iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["TEAM_ab", "TEAM_cd"]);
+        assert!(series.slots()[0].target.matches("TEAMxyzxyzxyzxyzxyzxyzxyzxyzxyzab"));
+    }
+
+    #[test]
+    fn test_template_requires_placeholder() {
+        assert!(matches!(
+            Series::numbered(Some("TEAM"), None, 1..=3, false),
+            Err(ConfigError::InvalidTemplate(_))
+        ));
+        assert!(matches!(
+            Series::from_words(Some("{word}"), None, Vec::<String>::new(), false),
+            Err(ConfigError::InvalidTemplate(_))
+        ));
+    }
+
+    #[test]
+    fn test_expected_attempts() {
+        // One slot: plain expectation
+        let single = Series::numbered(Some("A{n}"), None, 1..=1, false).unwrap();
+        assert!((single.expected_attempts() - 58.0 * 58.0).abs() < 1e-6);
+
+        // Equal slots: each fill leaves one fewer slot to hit
+        let series = Series::numbered(Some("A{n}"), None, 1..=3, false).unwrap();
+        let expected = 58.0 * 58.0 * (1.0 / 3.0 + 1.0 / 2.0 + 1.0);
+        assert!((series.expected_attempts() - expected).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_expected_attempts_for_counts() {
+        // Counts match the same probabilities listed slot by slot
+        let slots = [vec![1e-3; 3], vec![1e-5; 20_000]].concat();
+        let expected = expected_attempts_to_fill(slots);
+        let grouped = expected_attempts_to_fill_counts(vec![(1e-5, 20_000), (1e-3, 3)]);
+        assert!((grouped - expected).abs() / expected < 1e-9);
+
+        // A huge count doesn't allocate a slot per key
+        let huge = expected_attempts_to_fill_counts(vec![(1e-3, u64::MAX)]);
+        assert!(huge.is_finite() && huge > 1e3 * (u64::MAX as f64).ln());
+    }
+
+    #[test]
+    fn test_search_fills_every_slot() {
+        let series = Series::numbered(None, Some("{n}"), 1..=3, false).unwrap();
+        let search = SeriesSearch::new(series, config()).unwrap();
+        let found = search.run().unwrap();
+
+        assert_eq!(found.len(), 3);
+        for (m, n) in found.iter().zip(1..) {
+            assert_eq!(m.slot, n.to_string());
+            assert!(m.address.public_key().ends_with(&n.to_string()));
+        }
+        let keys: HashSet<String> = found.iter().map(|m| m.address.public_key()).collect();
+        assert_eq!(keys.len(), 3);
+    }
+
+    #[test]
+    fn test_cancelled_search_returns_partial() {
+        let series = Series::numbered(Some("ZZZZ{n}"), None, 1..=2, false).unwrap();
+        let search = SeriesSearch::new(series, VanityGeneratorConfig::default()).unwrap();
+        search.cancel_handle().store(true, Ordering::SeqCst);
+        assert!(search.run().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_manifest_round_trip() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("manifest.json");
+        let manifest = SeriesManifest {
+            template: "TEAM{n}...".to_string(),
+            slots: vec![ManifestEntry {
+                slot: "TEAM1".to_string(),
+                pubkey: "TEAM1abc".to_string(),
+                file: "TEAM1-TEAM1abc.json".to_string(),
+            }],
+            missing: vec!["TEAM2".to_string()],
+        };
+        write_manifest(&manifest, &path).unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        let restored: SeriesManifest = serde_json::from_str(&content).unwrap();
+        assert_eq!(restored, manifest);
+    }
+}
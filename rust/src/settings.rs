@@ -0,0 +1,434 @@
+//! Defaults from configuration files and named profiles.
+//!
+//! Settings are read from the user file (`~/.config/solana-vanity/config.toml`)
+//! and then a project file (`solana-vanity.toml` in the current directory).
+//! Top-level keys apply to every run; a `[profile.NAME]` table applies only
+//! when that profile is selected and overrides the top level. Keys are the
+//! long option names:
+//!
+//! ```toml
+//! threads = 8
+//! verify = true
+//! exclude = ["1111"]
+//!
+//! [profile.ci]
+//! threads = 2
+//! format = "json"
+//! ```
+//!
+//! Environment variables (`SOLANA_VANITY_THREADS`, ...) override the files.
+//! The CLI turns the result into option defaults, so flags override both.
+
+use crate::config::ConfigError;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// File name of the user configuration, under the config directory
+pub const USER_CONFIG_FILE: &str = "solana-vanity/config.toml";
+
+/// File name of the project configuration, in the current directory
+pub const PROJECT_CONFIG_FILE: &str = "solana-vanity.toml";
+
+/// Prefix of the environment variable for each setting
+pub const ENV_PREFIX: &str = "SOLANA_VANITY_";
+
+/// Settings only the user file may set: a project file comes with the
+/// directory, so it must not make a run replace existing keypair files
+pub const USER_ONLY_KEYS: &[&str] = &["overwrite"];
+
+/// The kind of value a setting takes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueKind {
+    Bool,
+    Integer,
+    String,
+    /// A list of strings, for repeatable options
+    List,
+}
+
+/// A setting a config file may contain
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SettingKey {
+    /// Key in the file, the same as the long option name
+    pub name: &'static str,
+    /// Environment variable that overrides the file
+    pub env: &'static str,
+    pub kind: ValueKind,
+}
+
+impl SettingKey {
+    const fn new(name: &'static str, env: &'static str, kind: ValueKind) -> Self {
+        Self { name, env, kind }
+    }
+
+    /// Id of the matching command-line argument
+    pub fn arg_id(&self) -> String {
+        self.name.replace('-', "_")
+    }
+
+    /// Format command-line values as a TOML value
+    pub fn to_toml(&self, values: &[String]) -> String {
+        match self.kind {
+            ValueKind::Bool | ValueKind::Integer => values.join(" "),
+            ValueKind::String => toml::Value::from(values.join(" ")).to_string(),
+            ValueKind::List => toml::Value::from(values.to_vec()).to_string(),
+        }
+    }
+}
+
+/// Generator, output and security options that can be given defaults
+pub const SETTING_KEYS: &[SettingKey] = &[
+    // Generator
+    SettingKey::new("threads", "SOLANA_VANITY_THREADS", ValueKind::Integer),
+    SettingKey::new("ignore-case", "SOLANA_VANITY_IGNORE_CASE", ValueKind::Bool),
+    SettingKey::new("use-mnemonic", "SOLANA_VANITY_USE_MNEMONIC", ValueKind::Bool),
+    SettingKey::new("words", "SOLANA_VANITY_WORDS", ValueKind::Integer),
+    SettingKey::new("budget", "SOLANA_VANITY_BUDGET", ValueKind::Integer),
+    SettingKey::new("top", "SOLANA_VANITY_TOP", ValueKind::Integer),
+    SettingKey::new("min-word-len", "SOLANA_VANITY_MIN_WORD_LEN", ValueKind::Integer),
+    SettingKey::new("word-position", "SOLANA_VANITY_WORD_POSITION", ValueKind::String),
+    // Output
+    SettingKey::new("output-dir", "SOLANA_VANITY_OUTPUT_DIR", ValueKind::String),
+    SettingKey::new("output-template", "SOLANA_VANITY_OUTPUT_TEMPLATE", ValueKind::String),
+    SettingKey::new("key-format", "SOLANA_VANITY_KEY_FORMAT", ValueKind::String),
+    SettingKey::new("format", "SOLANA_VANITY_FORMAT", ValueKind::String),
+    SettingKey::new("events", "SOLANA_VANITY_EVENTS", ValueKind::String),
+    SettingKey::new("verify", "SOLANA_VANITY_VERIFY", ValueKind::Bool),
+    SettingKey::new("report", "SOLANA_VANITY_REPORT", ValueKind::Bool),
+    SettingKey::new("overwrite", "SOLANA_VANITY_OVERWRITE", ValueKind::Bool),
+    SettingKey::new("quiet", "SOLANA_VANITY_QUIET", ValueKind::Bool),
+    SettingKey::new("verbose", "SOLANA_VANITY_VERBOSE", ValueKind::Bool),
+    // Security
+    SettingKey::new("exclude", "SOLANA_VANITY_EXCLUDE", ValueKind::List),
+    SettingKey::new("exclude-ignore-case", "SOLANA_VANITY_EXCLUDE_IGNORE_CASE", ValueKind::Bool),
+    SettingKey::new("profanity-list", "SOLANA_VANITY_PROFANITY_LIST", ValueKind::String),
+    SettingKey::new("max-run", "SOLANA_VANITY_MAX_RUN", ValueKind::Integer),
+    SettingKey::new("rpc-url", "SOLANA_VANITY_RPC_URL", ValueKind::String),
+    SettingKey::new("rpc-policy", "SOLANA_VANITY_RPC_POLICY", ValueKind::String),
+    SettingKey::new("rpc-timeout", "SOLANA_VANITY_RPC_TIMEOUT", ValueKind::Integer),
+];
+
+/// Look up a setting by its key
+pub fn setting_key(name: &str) -> Option<&'static SettingKey> {
+    SETTING_KEYS.iter().find(|key| key.name == name)
+}
+
+/// Path of the user configuration file, if a home directory is known
+///
+/// Follows `XDG_CONFIG_HOME`, falling back to `~/.config`.
+pub fn user_config_path() -> Option<PathBuf> {
+    let dir = std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(dir.join(USER_CONFIG_FILE))
+}
+
+/// A setting's value, as command-line values
+pub type SettingValues = Vec<String>;
+
+/// A parsed configuration file
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigFile {
+    path: PathBuf,
+    base: BTreeMap<&'static str, SettingValues>,
+    profiles: BTreeMap<String, BTreeMap<&'static str, SettingValues>>,
+}
+
+impl ConfigFile {
+    /// Parse the TOML text of the file at `path`
+    pub fn parse(text: &str, path: &Path) -> Result<Self, ConfigError> {
+        let invalid = |message: String| {
+            ConfigError::InvalidConfigFile(format!("{}: {message}", path.display()))
+        };
+        let mut table: toml::Table = text.parse().map_err(|e: toml::de::Error| invalid(e.message().to_string()))?;
+
+        let mut profiles = BTreeMap::new();
+        if let Some(value) = table.remove("profile") {
+            let toml::Value::Table(entries) = value else {
+                return Err(invalid("'profile' must be a table of [profile.NAME] tables".to_string()));
+            };
+            for (name, value) in entries {
+                let toml::Value::Table(settings) = value else {
+                    return Err(invalid(format!("[profile.{name}] must be a table")));
+                };
+                let settings = parse_settings(settings).map_err(|e| invalid(format!("[profile.{name}] {e}")))?;
+                profiles.insert(name, settings);
+            }
+        }
+        let base = parse_settings(table).map_err(invalid)?;
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            base,
+            profiles,
+        })
+    }
+
+    /// Parse a project file, which may not set [`USER_ONLY_KEYS`]
+    pub fn parse_project(text: &str, path: &Path) -> Result<Self, ConfigError> {
+        let file = Self::parse(text, path)?;
+        let tables = std::iter::once(&file.base).chain(file.profiles.values());
+        if let Some(name) = USER_ONLY_KEYS.iter().find(|&&name| tables.clone().any(|table| table.contains_key(name))) {
+            return Err(ConfigError::InvalidConfigFile(format!(
+                "{}: '{name}' can only be set in the user config file",
+                path.display()
+            )));
+        }
+        Ok(file)
+    }
+
+    /// Path the file was read from
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Names of the profiles the file defines
+    pub fn profiles(&self) -> impl Iterator<Item = &str> {
+        self.profiles.keys().map(String::as_str)
+    }
+}
+
+/// Check each key and value against [`SETTING_KEYS`]
+fn parse_settings(table: toml::Table) -> Result<BTreeMap<&'static str, SettingValues>, String> {
+    let mut settings = BTreeMap::new();
+    for (name, value) in table {
+        let key = setting_key(&name).ok_or_else(|| format!("unknown setting '{name}'"))?;
+        let values = match (key.kind, value) {
+            (ValueKind::Bool, toml::Value::Boolean(b)) => vec![b.to_string()],
+            (ValueKind::Integer, toml::Value::Integer(i)) if i >= 0 => vec![i.to_string()],
+            (ValueKind::String, toml::Value::String(s)) => vec![s],
+            (ValueKind::List, toml::Value::Array(items)) => items
+                .into_iter()
+                .map(|item| match item {
+                    toml::Value::String(s) => Ok(s),
+                    _ => Err(format!("'{name}' must be a list of strings")),
+                })
+                .collect::<Result<_, _>>()?,
+            (kind, _) => {
+                let expected = match kind {
+                    ValueKind::Bool => "true or false",
+                    ValueKind::Integer => "a non-negative integer",
+                    ValueKind::String => "a string",
+                    ValueKind::List => "a list of strings",
+                };
+                return Err(format!("'{name}' must be {expected}"));
+            }
+        };
+        settings.insert(key.name, values);
+    }
+    Ok(settings)
+}
+
+/// Where a setting's value came from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SettingSource {
+    File {
+        path: PathBuf,
+        /// The profile table that set it, if not the top level
+        profile: Option<String>,
+    },
+    /// An environment variable
+    Env(&'static str),
+}
+
+impl std::fmt::Display for SettingSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SettingSource::File { path, profile: Some(profile) } => {
+                write!(f, "{} [profile.{profile}]", path.display())
+            }
+            SettingSource::File { path, profile: None } => write!(f, "{}", path.display()),
+            SettingSource::Env(name) => write!(f, "env {name}"),
+        }
+    }
+}
+
+/// Settings merged from config files for one run
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Settings {
+    values: BTreeMap<&'static str, (SettingValues, SettingSource)>,
+}
+
+impl Settings {
+    /// Merge `files` in order, later files winning, then the selected profile
+    ///
+    /// A requested profile must be defined by at least one of the files.
+    pub fn resolve(files: &[ConfigFile], profile: Option<&str>) -> Result<Self, ConfigError> {
+        let mut settings = Self::default();
+        for file in files {
+            settings.apply(&file.base, &file.path, None);
+        }
+        if let Some(name) = profile {
+            let mut found = false;
+            for file in files {
+                if let Some(table) = file.profiles.get(name) {
+                    settings.apply(table, &file.path, Some(name));
+                    found = true;
+                }
+            }
+            if !found {
+                return Err(ConfigError::UnknownProfile(name.to_string()));
+            }
+        }
+        Ok(settings)
+    }
+
+    /// Let the environment variables that `var` finds override the files
+    pub fn with_env(mut self, var: impl Fn(&str) -> Option<String>) -> Self {
+        for key in SETTING_KEYS {
+            if let Some(value) = var(key.env) {
+                self.values.insert(key.name, (vec![value], SettingSource::Env(key.env)));
+            }
+        }
+        self
+    }
+
+    fn apply(&mut self, table: &BTreeMap<&'static str, SettingValues>, path: &Path, profile: Option<&str>) {
+        for (&name, values) in table {
+            let source = SettingSource::File {
+                path: path.to_path_buf(),
+                profile: profile.map(str::to_string),
+            };
+            self.values.insert(name, (values.clone(), source));
+        }
+    }
+
+    /// The value and source of a setting, if a file set it
+    pub fn get(&self, name: &str) -> Option<(&[String], &SettingSource)> {
+        self.values.get(name).map(|(values, source)| (values.as_slice(), source))
+    }
+
+    /// Drop a setting, as if no file or variable had set it
+    pub fn remove(&mut self, name: &str) {
+        self.values.remove(name);
+    }
+
+    /// Settings with values, in key order
+    pub fn iter(&self) -> impl Iterator<Item = (&'static SettingKey, &[String])> {
+        SETTING_KEYS
+            .iter()
+            .filter_map(|key| self.values.get(key.name).map(|(values, _)| (key, values.as_slice())))
+    }
+
+    /// Check if no file set anything
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(text: &str, path: &str) -> ConfigFile {
+        ConfigFile::parse(text, Path::new(path)).unwrap()
+    }
+
+    #[test]
+    fn test_keys() {
+        let key = setting_key("ignore-case").unwrap();
+        assert_eq!(key.arg_id(), "ignore_case");
+        assert_eq!(key.to_toml(&["true".to_string()]), "true");
+        assert!(setting_key("prefix").is_none());
+
+        // Every setting has a distinct variable derived from its name
+        for key in SETTING_KEYS {
+            assert_eq!(key.env, format!("{ENV_PREFIX}{}", key.arg_id().to_ascii_uppercase()));
+        }
+        let list = ["1111".to_string(), "a\"b".to_string()];
+        assert_eq!(setting_key("exclude").unwrap().to_toml(&list), r#"["1111", 'a"b']"#);
+        assert_eq!(setting_key("format").unwrap().to_toml(&list[..1]), r#""1111""#);
+    }
+
+    #[test]
+    fn test_parse_file() {
+        let config = file(
+            r#"
+threads = 8
+verify = true
+exclude = ["1111", "pump"]
+rpc-url = "https://api.mainnet-beta.solana.com"
+
+[profile.ci]
+threads = 2
+format = "json"
+
+[profile.mint]
+report = true
+"#,
+            "config.toml",
+        );
+        assert_eq!(config.profiles().collect::<Vec<_>>(), vec!["ci", "mint"]);
+
+        let settings = Settings::resolve(std::slice::from_ref(&config), None).unwrap();
+        assert_eq!(settings.get("threads").unwrap().0, ["8"]);
+        assert_eq!(settings.get("verify").unwrap().0, ["true"]);
+        assert_eq!(settings.get("exclude").unwrap().0, ["1111", "pump"]);
+        assert!(settings.get("format").is_none());
+
+        let ci = Settings::resolve(std::slice::from_ref(&config), Some("ci")).unwrap();
+        let (threads, source) = ci.get("threads").unwrap();
+        assert_eq!(threads, ["2"]);
+        assert_eq!(source.to_string(), "config.toml [profile.ci]");
+        assert_eq!(ci.get("verify").unwrap().0, ["true"]);
+        assert_eq!(ci.iter().map(|(key, _)| key.name).collect::<Vec<_>>()[0], "threads");
+
+        assert!(matches!(
+            Settings::resolve(&[config], Some("missing")),
+            Err(ConfigError::UnknownProfile(_))
+        ));
+    }
+
+    #[test]
+    fn test_later_files_win() {
+        let user = file("threads = 8\nverify = true\n[profile.ci]\nquiet = true\n", "user.toml");
+        let project = file("threads = 4\n[profile.ci]\nthreads = 1\n", "project.toml");
+
+        let settings = Settings::resolve(&[user.clone(), project.clone()], None).unwrap();
+        assert_eq!(settings.get("threads").unwrap().0, ["4"]);
+        assert_eq!(settings.get("threads").unwrap().1.to_string(), "project.toml");
+        assert_eq!(settings.get("verify").unwrap().1.to_string(), "user.toml");
+
+        // A profile overrides the top level of every file
+        let ci = Settings::resolve(&[user, project], Some("ci")).unwrap();
+        assert_eq!(ci.get("threads").unwrap().0, ["1"]);
+        assert_eq!(ci.get("quiet").unwrap().0, ["true"]);
+    }
+
+    #[test]
+    fn test_env_overrides_files() {
+        let user = file("threads = 8\nverify = true\n", "user.toml");
+        let settings = Settings::resolve(&[user], None)
+            .unwrap()
+            .with_env(|name| (name == "SOLANA_VANITY_VERIFY").then(|| "false".to_string()));
+        assert_eq!(settings.get("threads").unwrap().0, ["8"]);
+        let (verify, source) = settings.get("verify").unwrap();
+        assert_eq!(verify, ["false"]);
+        assert_eq!(source.to_string(), "env SOLANA_VANITY_VERIFY");
+    }
+
+    #[test]
+    fn test_project_file_cannot_overwrite() {
+        let parse = |text: &str| ConfigFile::parse_project(text, Path::new("solana-vanity.toml"));
+        assert!(parse("threads = 2\nverify = true\n").is_ok());
+        let err = parse("overwrite = true\n").unwrap_err();
+        assert!(err.to_string().contains("'overwrite' can only be set in the user config file"));
+        assert!(parse("[profile.ci]\noverwrite = false\n").is_err());
+        assert!(ConfigFile::parse("overwrite = true\n", Path::new("config.toml")).is_ok());
+    }
+
+    #[test]
+    fn test_rejects_bad_files() {
+        let parse = |text: &str| ConfigFile::parse(text, Path::new("bad.toml"));
+        assert!(matches!(parse("threads = "), Err(ConfigError::InvalidConfigFile(_))));
+        assert!(matches!(parse("prefix = \"AB\""), Err(ConfigError::InvalidConfigFile(_))));
+        assert!(parse("threads = \"8\"").is_err());
+        assert!(parse("threads = -1").is_err());
+        assert!(parse("verify = 1").is_err());
+        assert!(parse("exclude = [1]").is_err());
+        assert!(parse("profile = 3").is_err());
+        assert!(parse("[profile.ci]\nthreds = 2").unwrap_err().to_string().contains("[profile.ci]"));
+        assert!(parse("").unwrap().profiles().next().is_none());
+    }
+}
@@ -0,0 +1,16 @@
+//! Helpers shared by the unit tests.
+
+mod buffer;
+
+pub use buffer::Buffer;
+
+use crate::generator::VanityGeneratorConfig;
+
+/// Search configuration for tests: two threads, keypair verification on
+pub fn config() -> VanityGeneratorConfig {
+    VanityGeneratorConfig {
+        threads: 2,
+        verify_keypairs: true,
+        progress_interval: 100_000,
+    }
+}
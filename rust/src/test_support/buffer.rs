@@ -0,0 +1,27 @@
+//! In-memory writer that tests can read back.
+//!
+//! Shared by the library tests and the binary's tests, so it only uses std.
+
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+
+/// In-memory sink that can be read back after events are written
+#[derive(Clone, Default)]
+pub struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+impl Buffer {
+    /// Everything written so far
+    pub fn text(&self) -> String {
+        String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+    }
+}
+
+impl Write for Buffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
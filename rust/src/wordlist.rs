@@ -0,0 +1,674 @@
+//! Dictionary matching for vanity addresses.
+//!
+//! This module loads a wordlist, repairs or drops words that cannot appear
+//! in a Base58 address, and compiles the survivors into prefix and suffix
+//! tries so that every word can be checked against an address in one pass.
+
+use crate::exclusion::ExclusionRules;
+use crate::generator::{
+    ranked_search, GeneratorError, GeneratorResult, ParallelSearch, SearchProgress, VanityGeneratorConfig,
+};
+use crate::matcher::{is_valid_base58_char, pattern_probability, suggest_valid_chars};
+use crate::output::GeneratedAddress;
+use crate::security::verify_rng_quality;
+use std::cmp::Ordering as CmpOrdering;
+use std::collections::HashSet;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use thiserror::Error;
+
+/// Default minimum word length for dictionary matching
+pub const DEFAULT_MIN_WORD_LENGTH: usize = 4;
+
+/// Default number of matches kept by a dictionary search
+pub const DEFAULT_TOP_N: usize = 10;
+
+/// Wordlist errors
+#[derive(Error, Debug)]
+pub enum WordlistError {
+    #[error("Failed to read wordlist: {0}")]
+    IoError(#[from] std::io::Error),
+
+    #[error("Invalid score '{score}' for word '{word}' on line {line}")]
+    InvalidScore {
+        line: usize,
+        word: String,
+        score: String,
+    },
+
+    #[error("Wordlist contains no usable words (minimum length {0})")]
+    NoUsableWords(usize),
+}
+
+/// Where in the address a word may appear
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WordPosition {
+    /// Only at the start of the address
+    Prefix,
+    /// Only at the end of the address
+    Suffix,
+    /// At either end of the address
+    Both,
+}
+
+impl WordPosition {
+    fn allows_prefix(self) -> bool {
+        matches!(self, Self::Prefix | Self::Both)
+    }
+
+    fn allows_suffix(self) -> bool {
+        matches!(self, Self::Suffix | Self::Both)
+    }
+}
+
+impl FromStr for WordPosition {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "prefix" | "start" => Ok(Self::Prefix),
+            "suffix" | "end" => Ok(Self::Suffix),
+            "both" | "any" => Ok(Self::Both),
+            other => Err(format!(
+                "unknown word position '{other}' (expected prefix, suffix or both)"
+            )),
+        }
+    }
+}
+
+/// A word accepted into the wordlist
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Word {
+    /// The Base58-valid spelling used for matching
+    pub text: String,
+    /// The spelling found in the wordlist file, if it had to be repaired
+    pub original: Option<String>,
+    /// Caller-provided weight (higher is better)
+    pub score: u32,
+}
+
+/// Counters describing how a wordlist was filtered
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct WordlistStats {
+    /// Words accepted as written
+    pub accepted: usize,
+    /// Words accepted after replacing non-Base58 characters
+    pub repaired: usize,
+    /// Words dropped because they could not be repaired
+    pub dropped_invalid: usize,
+    /// Words dropped for being shorter than the minimum length
+    pub dropped_short: usize,
+    /// Words dropped as duplicates of an earlier entry
+    pub duplicates: usize,
+}
+
+/// A filtered list of words that can appear in a Base58 address
+#[derive(Debug, Clone)]
+pub struct Wordlist {
+    words: Vec<Word>,
+    stats: WordlistStats,
+    min_length: usize,
+}
+
+impl Wordlist {
+    /// Load a wordlist file
+    ///
+    /// Each non-empty line holds a word, optionally followed by whitespace and
+    /// an integer score. Lines starting with `#` are ignored.
+    pub fn load(path: &Path, min_length: usize) -> Result<Self, WordlistError> {
+        let content = std::fs::read_to_string(path)?;
+        Self::parse(&content, min_length)
+    }
+
+    /// Parse wordlist text in the same format accepted by [`Wordlist::load`]
+    pub fn parse(content: &str, min_length: usize) -> Result<Self, WordlistError> {
+        let mut entries = Vec::new();
+
+        for (index, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut parts = line.split_whitespace();
+            let word = parts.next().unwrap_or_default();
+            let score = match parts.next() {
+                Some(raw) => raw.parse::<u32>().map_err(|_| WordlistError::InvalidScore {
+                    line: index + 1,
+                    word: word.to_string(),
+                    score: raw.to_string(),
+                })?,
+                None => 0,
+            };
+
+            entries.push((word.to_string(), score));
+        }
+
+        Self::from_words(entries, min_length)
+    }
+
+    /// Build a wordlist from `(word, score)` pairs
+    pub fn from_words<I>(entries: I, min_length: usize) -> Result<Self, WordlistError>
+    where
+        I: IntoIterator<Item = (String, u32)>,
+    {
+        let mut words: Vec<Word> = Vec::new();
+        let mut seen: HashSet<String> = HashSet::new();
+        let mut stats = WordlistStats::default();
+
+        for (raw, score) in entries {
+            if raw.chars().count() < min_length {
+                stats.dropped_short += 1;
+                continue;
+            }
+
+            let (text, original) = if raw.chars().all(is_valid_base58_char) {
+                (raw, None)
+            } else {
+                match repair_word(&raw) {
+                    Some(repaired) => (repaired, Some(raw)),
+                    None => {
+                        stats.dropped_invalid += 1;
+                        continue;
+                    }
+                }
+            };
+
+            if !seen.insert(text.clone()) {
+                stats.duplicates += 1;
+                continue;
+            }
+
+            if original.is_some() {
+                stats.repaired += 1;
+            } else {
+                stats.accepted += 1;
+            }
+
+            words.push(Word {
+                text,
+                original,
+                score,
+            });
+        }
+
+        if words.is_empty() {
+            return Err(WordlistError::NoUsableWords(min_length));
+        }
+
+        Ok(Self {
+            words,
+            stats,
+            min_length,
+        })
+    }
+
+    /// The accepted words
+    pub fn words(&self) -> &[Word] {
+        &self.words
+    }
+
+    /// Filtering counters
+    pub fn stats(&self) -> &WordlistStats {
+        &self.stats
+    }
+
+    /// Minimum word length used when loading
+    pub fn min_length(&self) -> usize {
+        self.min_length
+    }
+
+    /// Number of accepted words
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    /// Check if the wordlist is empty
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+}
+
+/// Replace every non-Base58 character with its first valid lookalike
+///
+/// Returns `None` if any character has no valid replacement.
+pub fn repair_word(word: &str) -> Option<String> {
+    word.chars()
+        .map(|c| {
+            if is_valid_base58_char(c) {
+                Some(c)
+            } else {
+                suggest_valid_chars(c)
+                    .into_iter()
+                    .find(|s| is_valid_base58_char(*s))
+            }
+        })
+        .collect()
+}
+
+/// A word found at one end of an address
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WordMatch {
+    /// The matched word, as spelled in the wordlist after repair
+    pub word: String,
+    /// Which end of the address the word was found at
+    pub position: WordPosition,
+    /// The word's score from the wordlist
+    pub score: u32,
+}
+
+impl WordMatch {
+    /// Ranking key: longer words first, then higher scores
+    fn rank(&self) -> (usize, u32) {
+        (self.word.len(), self.score)
+    }
+}
+
+impl Ord for WordMatch {
+    fn cmp(&self, other: &Self) -> CmpOrdering {
+        self.rank()
+            .cmp(&other.rank())
+            .then_with(|| other.word.cmp(&self.word))
+    }
+}
+
+impl PartialOrd for WordMatch {
+    fn partial_cmp(&self, other: &Self) -> Option<CmpOrdering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// A compact byte trie with sorted child edges
+#[derive(Debug, Clone, Default)]
+struct Trie {
+    nodes: Vec<TrieNode>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct TrieNode {
+    children: Vec<(u8, u32)>,
+    word: Option<u32>,
+}
+
+impl Trie {
+    fn new() -> Self {
+        Self {
+            nodes: vec![TrieNode::default()],
+        }
+    }
+
+    fn insert<I: Iterator<Item = u8>>(&mut self, bytes: I, word_index: u32) {
+        let mut node = 0usize;
+        for byte in bytes {
+            node = match self.nodes[node]
+                .children
+                .binary_search_by_key(&byte, |(b, _)| *b)
+            {
+                Ok(pos) => self.nodes[node].children[pos].1 as usize,
+                Err(pos) => {
+                    let next = self.nodes.len() as u32;
+                    self.nodes.push(TrieNode::default());
+                    self.nodes[node].children.insert(pos, (byte, next));
+                    next as usize
+                }
+            };
+        }
+        self.nodes[node].word.get_or_insert(word_index);
+    }
+
+    /// Visit every stored word that is a prefix of `bytes`
+    #[inline]
+    fn for_each_prefix<I, F>(&self, bytes: I, mut visit: F)
+    where
+        I: Iterator<Item = u8>,
+        F: FnMut(u32),
+    {
+        let mut node = 0usize;
+        for byte in bytes {
+            match self.nodes[node]
+                .children
+                .binary_search_by_key(&byte, |(b, _)| *b)
+            {
+                Ok(pos) => node = self.nodes[node].children[pos].1 as usize,
+                Err(_) => return,
+            }
+            if let Some(word) = self.nodes[node].word {
+                visit(word);
+            }
+        }
+    }
+}
+
+/// Matches addresses against every word in a wordlist at once
+#[derive(Debug, Clone)]
+pub struct WordlistMatcher {
+    words: Vec<Word>,
+    prefixes: Trie,
+    suffixes: Trie,
+    position: WordPosition,
+    case_insensitive: bool,
+}
+
+impl WordlistMatcher {
+    /// Compile a wordlist into prefix and suffix tries
+    pub fn new(wordlist: &Wordlist, position: WordPosition, case_insensitive: bool) -> Self {
+        let fold = move |b: u8| {
+            if case_insensitive {
+                b.to_ascii_lowercase()
+            } else {
+                b
+            }
+        };
+
+        let mut prefixes = Trie::new();
+        let mut suffixes = Trie::new();
+        for (index, word) in wordlist.words().iter().enumerate() {
+            let bytes = word.text.as_bytes();
+            if position.allows_prefix() {
+                prefixes.insert(bytes.iter().copied().map(fold), index as u32);
+            }
+            if position.allows_suffix() {
+                suffixes.insert(bytes.iter().rev().copied().map(fold), index as u32);
+            }
+        }
+
+        Self {
+            words: wordlist.words().to_vec(),
+            prefixes,
+            suffixes,
+            position,
+            case_insensitive,
+        }
+    }
+
+    /// Find the best-ranked word at either end of the address
+    pub fn best_match(&self, address: &str) -> Option<WordMatch> {
+        let bytes = address.as_bytes();
+        let fold = |b: &u8| {
+            if self.case_insensitive {
+                b.to_ascii_lowercase()
+            } else {
+                *b
+            }
+        };
+
+        let mut best: Option<WordMatch> = None;
+        let mut consider = |index: u32, position: WordPosition| {
+            let word = &self.words[index as usize];
+            let candidate = WordMatch {
+                word: word.text.clone(),
+                position,
+                score: word.score,
+            };
+            if best.as_ref().is_none_or(|b| candidate > *b) {
+                best = Some(candidate);
+            }
+        };
+
+        if self.position.allows_prefix() {
+            self.prefixes
+                .for_each_prefix(bytes.iter().map(fold), |i| consider(i, WordPosition::Prefix));
+        }
+        if self.position.allows_suffix() {
+            self.suffixes.for_each_prefix(bytes.iter().rev().map(fold), |i| {
+                consider(i, WordPosition::Suffix)
+            });
+        }
+
+        best
+    }
+
+    /// Number of words compiled into the matcher
+    pub fn word_count(&self) -> usize {
+        self.words.len()
+    }
+
+    /// Probability that a random address starts or ends with some word
+    ///
+    /// This sums the per-word probabilities, which slightly overestimates
+    /// when words overlap but is accurate for typical dictionaries.
+    pub fn match_probability(&self) -> f64 {
+        let ends = match self.position {
+            WordPosition::Both => 2.0,
+            _ => 1.0,
+        };
+        self.words
+            .iter()
+            .map(|w| ends * pattern_probability(&w.text, self.case_insensitive))
+            .sum::<f64>()
+            .min(1.0)
+    }
+
+    /// Human-readable description of the dictionary target
+    pub fn description(&self) -> String {
+        let where_str = match self.position {
+            WordPosition::Prefix => "starting with",
+            WordPosition::Suffix => "ending with",
+            WordPosition::Both => "starting or ending with",
+        };
+        let case_str = if self.case_insensitive { " (case-insensitive)" } else { "" };
+        format!("{where_str} any of {} words{case_str}", self.words.len())
+    }
+}
+
+/// An address found by a dictionary search
+#[derive(Debug)]
+pub struct DictionaryMatch {
+    /// The generated keypair and its statistics
+    pub address: GeneratedAddress,
+    /// The word found in the address
+    pub word: WordMatch,
+}
+
+/// Time-boxed search that keeps the best dictionary matches
+pub struct DictionarySearch {
+    config: VanityGeneratorConfig,
+    matcher: WordlistMatcher,
+    exclusions: ExclusionRules,
+    progress: SearchProgress,
+    excluded: Arc<AtomicU64>,
+}
+
+impl DictionarySearch {
+    /// Create a new dictionary search
+    pub fn new(matcher: WordlistMatcher, config: VanityGeneratorConfig) -> GeneratorResult<Self> {
+        verify_rng_quality().map_err(|e| GeneratorError::RngError(e.to_string()))?;
+
+        Ok(Self {
+            config,
+            matcher,
+            exclusions: ExclusionRules::default(),
+            progress: SearchProgress::default(),
+            excluded: Arc::new(AtomicU64::new(0)),
+        })
+    }
+
+    /// Reject matching addresses that violate these exclusion rules
+    pub fn with_exclusions(mut self, exclusions: ExclusionRules) -> Self {
+        self.exclusions = exclusions;
+        self
+    }
+
+    /// Get the number of matching addresses dropped by exclusion rules
+    pub fn excluded(&self) -> u64 {
+        self.excluded.load(Ordering::Relaxed)
+    }
+
+    /// Get the compiled matcher
+    pub fn matcher(&self) -> &WordlistMatcher {
+        &self.matcher
+    }
+
+    /// Search until `budget` elapses (or cancellation), keeping the `top_n` best matches
+    ///
+    /// Results are ordered best first. Cancelling keeps whatever was found so far.
+    pub fn run(&self, budget: Duration, top_n: usize) -> GeneratorResult<Vec<DictionaryMatch>> {
+        if top_n == 0 {
+            return Err(GeneratorError::ConfigError(
+                "top-N must be at least 1".to_string(),
+            ));
+        }
+
+        let matcher = &self.matcher;
+        let exclusions = &self.exclusions;
+        let excluded = &self.excluded;
+        let found = ranked_search(
+            &self.config,
+            &self.progress,
+            budget,
+            top_n,
+            |address: &str| {
+                let word = matcher.best_match(address)?;
+                if exclusions.check(address).is_some() {
+                    excluded.fetch_add(1, Ordering::Relaxed);
+                    return None;
+                }
+                Some(word)
+            },
+            |_, _| false,
+        )?;
+
+        Ok(found
+            .into_iter()
+            .map(|(address, word)| DictionaryMatch { address, word })
+            .collect())
+    }
+}
+
+impl ParallelSearch for DictionarySearch {
+    fn progress(&self) -> &SearchProgress {
+        &self.progress
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::config;
+
+    fn wordlist(words: &[&str]) -> Wordlist {
+        Wordlist::from_words(words.iter().map(|w| (w.to_string(), 0)), 3).unwrap()
+    }
+
+    #[test]
+    fn test_parse_with_scores_and_comments() {
+        let list = Wordlist::parse("# brands\nPUMP 5\nmoon\n\nrocket 2\n", 4).unwrap();
+        assert_eq!(list.len(), 3);
+        assert_eq!(list.words()[0].score, 5);
+        assert_eq!(list.words()[1].score, 0);
+    }
+
+    #[test]
+    fn test_invalid_score_rejected() {
+        let result = Wordlist::parse("moon high\n", 4);
+        assert!(matches!(result, Err(WordlistError::InvalidScore { line: 1, .. })));
+    }
+
+    #[test]
+    fn test_repair_and_drop() {
+        let list = Wordlist::parse("gold\nHOLD\nmoon\nca-fe\nab\n", 4).unwrap();
+        let texts: Vec<&str> = list.words().iter().map(|w| w.text.as_str()).collect();
+
+        // 'l' is repaired to 'L', 'O' to 'o'
+        assert!(texts.contains(&"goLd"));
+        assert!(texts.contains(&"HoLD"));
+        assert!(texts.contains(&"moon"));
+        assert_eq!(list.stats().repaired, 2);
+        assert_eq!(list.stats().dropped_invalid, 1);
+        assert_eq!(list.stats().dropped_short, 1);
+    }
+
+    #[test]
+    fn test_empty_wordlist_rejected() {
+        assert!(matches!(
+            Wordlist::parse("ab\n", 4),
+            Err(WordlistError::NoUsableWords(4))
+        ));
+    }
+
+    #[test]
+    fn test_prefix_and_suffix_matching() {
+        let matcher = WordlistMatcher::new(&wordlist(&["Cat", "Catz", "pump"]), WordPosition::Both, false);
+
+        let m = matcher.best_match("Catz23456789abcdefghijkmnopqrstuvwxy").unwrap();
+        assert_eq!(m.word, "Catz");
+        assert_eq!(m.position, WordPosition::Prefix);
+
+        let m = matcher.best_match("23456789abcdefghijkmnopqrstuvwxypump").unwrap();
+        assert_eq!(m.word, "pump");
+        assert_eq!(m.position, WordPosition::Suffix);
+
+        assert!(matcher.best_match("23456789abcdefghijkmnopqrstuvwxyzzz").is_none());
+    }
+
+    #[test]
+    fn test_position_restriction() {
+        let matcher = WordlistMatcher::new(&wordlist(&["pump"]), WordPosition::Prefix, false);
+        assert!(matcher.best_match("23456789abcdefghijkmnopqrstuvwxypump").is_none());
+        assert!(matcher.best_match("pump456789abcdefghijkmnopqrstuvwxyz").is_some());
+    }
+
+    #[test]
+    fn test_case_insensitive_matching() {
+        let matcher = WordlistMatcher::new(&wordlist(&["moon"]), WordPosition::Both, true);
+        assert!(matcher.best_match("MooN456789abcdefghijkmnopqrstuvwxyz").is_some());
+    }
+
+    #[test]
+    fn test_match_probability() {
+        // 'o' has no valid upper case, so ignoring case only 'm' and 'n' double
+        let matcher = WordlistMatcher::new(&wordlist(&["moon"]), WordPosition::Both, true);
+        let expected = 2.0 * (2.0 / 58.0) * (1.0 / 58.0f64).powi(2) * (2.0 / 58.0);
+        assert!((matcher.match_probability() - expected).abs() < 1e-15);
+
+        let matcher = WordlistMatcher::new(&wordlist(&["moon", "pump"]), WordPosition::Prefix, false);
+        assert!((matcher.match_probability() - 2.0 / 58.0f64.powi(4)).abs() < 1e-15);
+    }
+
+    #[test]
+    fn test_ranking_prefers_length_then_score() {
+        let list = Wordlist::from_words(
+            vec![("Abc".to_string(), 9), ("Abcd".to_string(), 1), ("xyz".to_string(), 5)],
+            3,
+        )
+        .unwrap();
+        let matcher = WordlistMatcher::new(&list, WordPosition::Both, false);
+
+        let m = matcher.best_match("Abcd456789abcdefghijkmnopqrstuvwxyz").unwrap();
+        assert_eq!(m.word, "Abcd");
+
+        let m = matcher.best_match("Abc2456789abcdefghijkmnopqrstuvwxyz").unwrap();
+        assert_eq!(m.word, "Abc");
+    }
+
+    #[test]
+    fn test_dictionary_search_keeps_top_n() {
+        let list = Wordlist::from_words(vec![("A".to_string(), 0)], 1).unwrap();
+        let matcher = WordlistMatcher::new(&list, WordPosition::Both, false);
+        let search = DictionarySearch::new(matcher, config()).unwrap();
+
+        let found = search.run(Duration::from_millis(300), 3).unwrap();
+        assert!(!found.is_empty() && found.len() <= 3);
+        for m in &found {
+            let pubkey = m.address.public_key();
+            assert!(pubkey.starts_with('A') || pubkey.ends_with('A'));
+        }
+    }
+
+    #[test]
+    fn test_dictionary_search_applies_exclusions() {
+        let list = Wordlist::from_words(vec![("A".to_string(), 0)], 1).unwrap();
+        let matcher = WordlistMatcher::new(&list, WordPosition::Prefix, false);
+        let search = DictionarySearch::new(matcher, config())
+            .unwrap()
+            .with_exclusions(ExclusionRules::new().forbid_at("A", 0, false));
+
+        // Every match starts with the forbidden 'A', so nothing is kept
+        let found = search.run(Duration::from_millis(200), 3).unwrap();
+        assert!(found.is_empty());
+        assert!(search.excluded() > 0);
+    }
+}
@@ -8,7 +8,7 @@
 use solana_vanity::{
     generator::{VanityGenerator, VanityGeneratorConfig},
     matcher::MatchTarget,
-    output::{verify_keypair_file, write_keypair_file, GeneratedAddress},
+    output::{verify_keypair_file, write_keypair_file, GeneratedAddress, KeypairEncoding},
     security::verify_file_permissions,
 };
 use solana_sdk::signature::Signer;
@@ -168,7 +168,7 @@ fn test_output_file_format() {
         let path = dir.path().join(format!("test_{}.json", i));
 
         // Write the keypair
-        write_keypair_file(&address, &path).unwrap();
+        write_keypair_file(&address, KeypairEncoding::SolanaJson, &path).unwrap();
 
         // Read and parse like Solana CLI would
         let content = fs::read_to_string(&path).unwrap();
@@ -204,7 +204,7 @@ fn test_file_permissions() {
         let address = GeneratedAddress::new(keypair, 100, 1000);
         let path = dir.path().join(format!("test_{}.json", i));
 
-        write_keypair_file(&address, &path).unwrap();
+        write_keypair_file(&address, KeypairEncoding::SolanaJson, &path).unwrap();
 
         // Verify permissions are secure (0600)
         let is_secure = verify_file_permissions(&path).unwrap();
@@ -237,7 +237,7 @@ fn test_verification_report() {
         let address = GeneratedAddress::new(keypair, 100, 1000);
         let path = dir.path().join(format!("test_{}.json", i));
 
-        write_keypair_file(&address, &path).unwrap();
+        write_keypair_file(&address, KeypairEncoding::SolanaJson, &path).unwrap();
 
         // Verify the file
         let report = verify_keypair_file(&path).unwrap();
@@ -376,7 +376,7 @@ fn test_solana_sdk_compatibility() {
 
         // Save it
         let path = dir.path().join(format!("{}.json", original_pubkey));
-        write_keypair_file(&address, &path).unwrap();
+        write_keypair_file(&address, KeypairEncoding::SolanaJson, &path).unwrap();
 
         // Load it using the same method Solana CLI uses
         let content = fs::read_to_string(&path).unwrap();
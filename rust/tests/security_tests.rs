@@ -10,7 +10,7 @@ use solana_vanity::{
     config::{validate_prefix, validate_suffix, ConfigError, BASE58_ALPHABET},
     generator::VanityGenerator,
     matcher::{is_valid_base58_char, MatchTarget},
-    output::{write_keypair_file, GeneratedAddress},
+    output::{write_keypair_file, GeneratedAddress, KeypairEncoding},
     security::{
         secure_clear, secure_write_file, verify_file_permissions, verify_keypair_integrity,
         verify_rng_quality, SecureBytes, ZeroizeGuard,
@@ -314,11 +314,11 @@ fn test_generated_address_debug_redacted() {
 
     // Get secret bytes and make sure they're not in the output
     let secret = address.secret_key_bytes();
-    for byte in &secret[..32] {
-        // Check first 32 bytes (private key portion)
-        let byte_str = format!("{}", byte);
-        // This is a loose check - just make sure the full array isn't printed
-    }
+    // This is a loose check - just make sure the full private key array isn't printed
+    assert!(
+        !debug_str.contains(&format!("{:?}", &secret[..32])),
+        "Debug output should not contain the private key bytes"
+    );
 }
 
 /// Test that keypair file doesn't contain plaintext identifiable as private key
@@ -329,7 +329,7 @@ fn test_keypair_file_format_security() {
     let address = GeneratedAddress::new(keypair, 100, 1000);
     let path = dir.path().join("test.json");
 
-    write_keypair_file(&address, &path).unwrap();
+    write_keypair_file(&address, KeypairEncoding::SolanaJson, &path).unwrap();
 
     let content = std::fs::read_to_string(&path).unwrap();
 